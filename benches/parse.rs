@@ -0,0 +1,129 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use serde_json::{json, Value};
+use wcif::types::Competition;
+
+const EVENTS: [&str; 17] = ["333", "222", "444", "555", "666", "777", "333bf", "333fm", "333oh", "clock", "minx", "pyram", "skewb", "sq1", "444bf", "555bf", "333mbf"];
+const PERSONS: u64 = 340;
+const ROUNDS: u64 = 2;
+const GROUPS: u64 = 8;
+
+// A championship sized document (about 5 MB) with results for every round. Values are derived from the
+// indices, so the document is the same for every run
+fn championship() -> String {
+    let activity_id = |e: usize, r: u64, g: u64| (e as u64 * 100 + r * 10 + g) as u32;
+    let countries = ["DE", "US", "GB", "FR", "PL", "CN"];
+    let genders = ["m", "f", "o"];
+    let persons: Vec<Value> = (1..=PERSONS).map(|id| json!({
+        "registrantId": id,
+        "name": format!("Competitor Number {id}"),
+        "wcaUserId": 10000 + id,
+        "wcaId": format!("20{:02}COMP{:02}", 10 + id % 14, id % 100),
+        "countryIso2": countries[id as usize % 6],
+        "gender": genders[id as usize % 3],
+        "avatar": { "url": format!("https://avatars.example/{id}.jpg"), "thumbUrl": format!("https://avatars.example/{id}_thumb.jpg") },
+        "roles": [],
+        "registration": {
+            "wcaRegistrationId": 50000 + id,
+            "eventIds": EVENTS,
+            "status": "accepted",
+            "isCompeting": true,
+        },
+        "assignments": EVENTS.iter().enumerate().flat_map(|(e, _)| (1..=ROUNDS).map(move |r| json!({
+            "activityId": activity_id(e, r, 1 + id % GROUPS),
+            "assignmentCode": "competitor",
+            "stationNumber": id % 40 + 1,
+        }))).collect::<Vec<_>>(),
+        "personalBests": EVENTS.iter().enumerate().flat_map(|(e, event)| ["single", "average"].map(|t| json!({
+            "eventId": event,
+            "best": 800 + (id * 37 + e as u64 * 11) % 5000,
+            "worldRanking": id * 3 + e as u64,
+            "continentalRanking": id * 2 + e as u64,
+            "nationalRanking": id + e as u64,
+            "type": t,
+        }))).collect::<Vec<_>>(),
+        "extensions": [],
+    })).collect();
+    let events: Vec<Value> = EVENTS.iter().map(|event| json!({
+        "id": event,
+        "rounds": (1..=ROUNDS).map(|r| json!({
+            "id": format!("{event}-r{r}"),
+            "format": "a",
+            "timeLimit": { "centiseconds": 60000, "cumulativeRoundIds": [] },
+            "cutoff": null,
+            "advancementCondition": if r < ROUNDS { json!({ "type": "percent", "level": 75 }) } else { Value::Null },
+            "results": (1..=PERSONS).map(|id| json!({
+                "personId": id,
+                "ranking": id,
+                "attempts": (0..5).map(|a| json!({ "result": if (id + a) % 23 == 0 { -1 } else { 800 + ((id * 31 + a * 7) % 4000) as i64 }, "reconstruction": null })).collect::<Vec<_>>(),
+                "best": 800 + id,
+                "average": 900 + id,
+            })).collect::<Vec<_>>(),
+            "scrambleSetCount": GROUPS,
+            "extensions": [],
+        })).collect::<Vec<_>>(),
+        "competitorLimit": null,
+        "qualification": null,
+        "extensions": [],
+    })).collect();
+    let activities: Vec<Value> = EVENTS.iter().enumerate().flat_map(|(e, event)| (1..=ROUNDS).map(move |r| json!({
+        "id": activity_id(e, r, 0),
+        "name": format!("{event} Round {r}"),
+        "activityCode": format!("{event}-r{r}"),
+        "startTime": "2024-07-04T07:00:00Z",
+        "endTime": "2024-07-04T09:00:00Z",
+        "childActivities": (1..=GROUPS).map(|g| json!({
+            "id": activity_id(e, r, g),
+            "name": format!("{event} Round {r} Group {g}"),
+            "activityCode": format!("{event}-r{r}-g{g}"),
+            "startTime": "2024-07-04T07:00:00Z",
+            "endTime": "2024-07-04T07:15:00Z",
+            "childActivities": [],
+            "scrambleSetId": null,
+            "extensions": [],
+        })).collect::<Vec<_>>(),
+        "scrambleSetId": null,
+        "extensions": [],
+    }))).collect();
+    json!({
+        "formatVersion": "1.0",
+        "id": "Championship2024",
+        "name": "Championship 2024",
+        "shortName": "Championship 2024",
+        "series": null,
+        "persons": persons,
+        "events": events,
+        "schedule": {
+            "startDate": "2024-07-04",
+            "numberOfDays": 4,
+            "venues": [{
+                "id": 1,
+                "name": "Arena",
+                "latitudeMicrodegrees": 52520008,
+                "longitudeMicrodegrees": 13404954,
+                "countryIso2": "DE",
+                "timezone": "Europe/Berlin",
+                "rooms": [{ "id": 1, "name": "Main", "color": "#ff0000", "activities": activities, "extensions": [] }],
+                "extensions": [],
+            }],
+        },
+        "registrationInfo": null,
+        "competitorLimit": PERSONS,
+        "extensions": [],
+    }).to_string()
+}
+
+fn parse(c: &mut Criterion) {
+    let json = championship();
+    let competition: Competition = serde_json::from_str(&json).expect("benchmark document is valid");
+    let mut group = c.benchmark_group("championship");
+    group.throughput(Throughput::Bytes(json.len() as u64));
+    group.sample_size(20);
+    group.bench_function("parse", |b| b.iter(|| serde_json::from_str::<Competition>(&json).unwrap()));
+    #[cfg(feature = "rayon")]
+    group.bench_function("parse_parallel", |b| b.iter(|| wcif::parallel::parse_parallel(&json).unwrap()));
+    group.bench_function("serialize", |b| b.iter(|| serde_json::to_string(&competition).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,24 @@
+#ifndef WCIF_H
+#define WCIF_H
+
+typedef struct Competition Competition;
+
+typedef enum WcifStatus {
+    WCIF_OK = 0,
+    WCIF_NULL_POINTER = 1,
+    WCIF_INVALID_UTF8 = 2,
+    WCIF_PARSE_ERROR = 3,
+    WCIF_SERIALIZE_ERROR = 4,
+    WCIF_PANIC = 5,
+} WcifStatus;
+
+const char *wcif_last_error_message(void);
+
+WcifStatus wcif_competition_parse(const char *json, Competition **out);
+WcifStatus wcif_competition_serialize(const Competition *competition, char **out);
+WcifStatus wcif_competition_validate(const Competition *competition, char **out);
+
+void wcif_competition_free(Competition *competition);
+void wcif_string_free(char *s);
+
+#endif
@@ -0,0 +1,81 @@
+use crate::types::{ActivityCode, Competition, EventId, GroupIdType, Room, RoundIdType};
+
+// Placeholders: {event} (e.g. "3x3x3 Cube"), {eventId} (e.g. "333"), {round}, {group}, {room} and {color}
+pub const DEFAULT_GROUP_NAME_TEMPLATE: &str = "{event}, Round {round}, Group {group} ({color})";
+
+const COLORS: [(&str, (u8, u8, u8)); 11] = [
+    ("Red", (220, 40, 40)),
+    ("Orange", (245, 140, 30)),
+    ("Yellow", (245, 215, 50)),
+    ("Green", (50, 160, 70)),
+    ("Blue", (40, 90, 200)),
+    ("Purple", (130, 60, 170)),
+    ("Pink", (240, 120, 180)),
+    ("Brown", (130, 85, 45)),
+    ("Black", (20, 20, 20)),
+    ("Gray", (128, 128, 128)),
+    ("White", (245, 245, 245)),
+];
+
+fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.strip_prefix('#')?;
+    let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok());
+    match hex.len() {
+        6 => Some((channel(0)?, channel(2)?, channel(4)?)),
+        3 => {
+            let short = |i: usize| hex.get(i..i + 1).and_then(|c| u8::from_str_radix(c, 16).ok()).map(|c| c * 17);
+            Some((short(0)?, short(1)?, short(2)?))
+        },
+        _ => None,
+    }
+}
+
+impl Room {
+    // The closest basic color name of the room color, e.g. "Red" for "#ff0000"
+    pub fn color_name(&self) -> Option<&'static str> {
+        let (r, g, b) = parse_hex_color(&self.color)?;
+        COLORS.iter()
+            .min_by_key(|(_, (cr, cg, cb))| {
+                let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+                d(r, *cr) + d(g, *cg) + d(b, *cb)
+            })
+            .map(|(name, _)| *name)
+    }
+}
+
+// Fills the template for a group activity. The color falls back to the room name if it's not a valid hex color
+pub fn format_group_name(template: &str, event_id: &EventId, round: RoundIdType, group: GroupIdType, room: &Room) -> String {
+    template
+        .replace("{eventId}", &event_id.to_string())
+        .replace("{event}", event_id.get_name())
+        .replace("{round}", &round.to_string())
+        .replace("{group}", &group.to_string())
+        .replace("{room}", &room.name)
+        .replace("{color}", room.color_name().unwrap_or(&room.name))
+}
+
+impl Competition {
+    // Renames all group activities (without attempt) according to the template. Returns the number of renamed activities
+    pub fn rename_group_activities(&mut self, template: &str) -> usize {
+        let mut renamed = 0;
+        for room in self.schedule.venues.iter_mut().flat_map(|v| v.rooms.iter_mut()) {
+            let mut activities = std::mem::take(&mut room.activities);
+            for activity in activities.iter_mut() {
+                activity.for_each_activity_mut(&mut |activity| {
+                    let ActivityCode::Official(code) = &activity.activity_code else {
+                        return;
+                    };
+                    if let (Some(round), Some(group), None) = (code.round, code.group, code.attempt) {
+                        let name = format_group_name(template, &code.event, round, group, room);
+                        if activity.name != name {
+                            activity.name = name;
+                            renamed += 1;
+                        }
+                    }
+                });
+            }
+            room.activities = activities;
+        }
+        renamed
+    }
+}
@@ -0,0 +1,41 @@
+use crate::types::{Activity, AssignmentCode, Competition, DateTime, PersonId, Room, Venue};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduledAssignment<'a> {
+    pub venue: &'a Venue,
+    pub room: &'a Room,
+    pub activity: &'a Activity,
+    pub assignment_code: &'a AssignmentCode,
+    pub station_number: Option<u32>,
+}
+
+impl Competition {
+    // All assignments of a person together with the activity, room and venue, ordered by start time
+    pub fn assignments_for(&self, person_id: PersonId) -> Vec<ScheduledAssignment<'_>> {
+        let Some(person) = self.find_person(person_id) else {
+            return vec![];
+        };
+        let activities = self.schedule.activities_with_rooms();
+        let mut assignments: Vec<ScheduledAssignment> = person.assignments.iter()
+            .filter_map(|assignment| {
+                let (venue, room, activity) = activities.iter().find(|(_, _, a)| a.id == assignment.activity_id)?;
+                Some(ScheduledAssignment {
+                    venue,
+                    room,
+                    activity,
+                    assignment_code: &assignment.assignment_code,
+                    station_number: assignment.station_number,
+                })
+            })
+            .collect();
+        assignments.sort_by_key(|a| (a.activity.start_time, a.activity.end_time));
+        assignments
+    }
+
+    // Assignments of a person that start at or after the given instant, ordered by start time
+    pub fn next_activities_for(&self, person_id: PersonId, now: &DateTime) -> Vec<ScheduledAssignment<'_>> {
+        self.assignments_for(person_id).into_iter()
+            .filter(|a| a.activity.start_time >= *now)
+            .collect()
+    }
+}
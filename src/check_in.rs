@@ -0,0 +1,83 @@
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::types::{Competition, DateTime, Extension, Person, PersonId, RegistrationStatus};
+
+pub const EXTENSION_ID: &str = crate::extensions::CHECK_IN_ID;
+pub const SPEC_URL: &str = crate::extensions::CHECK_IN_SPEC_URL;
+
+// Stored on the person once they arrived at the competition
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckInExtension {
+    pub id: MustBe!("wcif-rs.CheckIn"),
+    pub spec_url: String,
+    pub data: CheckIn,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckIn {
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
+    pub checked_in_at: DateTime,
+    pub badge_picked_up: bool,
+}
+
+impl Person {
+    pub fn check_in_info(&self) -> Option<&CheckIn> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::CheckIn(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    pub fn is_checked_in(&self) -> bool {
+        self.check_in_info().is_some()
+    }
+
+    // Replaces a previous check in
+    pub fn check_in(&mut self, now: DateTime, badge_picked_up: bool) {
+        self.undo_check_in();
+        self.extensions.push(Extension::CheckIn(CheckInExtension {
+            id: Default::default(),
+            spec_url: SPEC_URL.to_string(),
+            data: CheckIn { checked_in_at: now, badge_picked_up },
+        }));
+    }
+
+    pub fn undo_check_in(&mut self) {
+        self.extensions.retain(|e| !matches!(e, Extension::CheckIn(_)));
+    }
+
+    fn is_accepted(&self) -> bool {
+        self.registration.as_ref().is_some_and(|r| r.status == RegistrationStatus::Accepted)
+    }
+}
+
+impl Competition {
+    // Returns false if there is no such person
+    pub fn check_in(&mut self, person_id: PersonId, now: DateTime, badge_picked_up: bool) -> bool {
+        let Some(person) = self.find_person_mut(person_id) else {
+            return false;
+        };
+        person.check_in(now, badge_picked_up);
+        true
+    }
+
+    pub fn checked_in(&self) -> Vec<&Person> {
+        self.persons.iter()
+            .filter(|p| p.is_checked_in())
+            .collect()
+    }
+
+    pub fn accepted_not_checked_in(&self) -> Vec<&Person> {
+        self.persons.iter()
+            .filter(|p| p.is_accepted() && !p.is_checked_in())
+            .collect()
+    }
+
+    pub fn badges_not_picked_up(&self) -> Vec<&Person> {
+        self.persons.iter()
+            .filter(|p| p.is_accepted() && !p.check_in_info().is_some_and(|c| c.badge_picked_up))
+            .collect()
+    }
+}
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{ActivityCode, ActivityId, AttemptIdType, Competition, EventActivityCode, EventId, GroupIdType, RoundIdType, UnofficialActivityCode};
+
+// Names events are commonly called by, normalized like activity names. Short and ambiguous ones are less certain
+const EVENT_ALIASES: [(EventId, &[&str], &[&str]); 21] = [
+    (EventId::Cube333, &["3x3x3 cube", "3x3x3", "3x3", "333", "rubik s cube"], &[]),
+    (EventId::Cube222, &["2x2x2 cube", "2x2x2", "2x2", "222"], &["pocket cube"]),
+    (EventId::Cube444, &["4x4x4 cube", "4x4x4", "4x4", "444"], &["revenge"]),
+    (EventId::Cube555, &["5x5x5 cube", "5x5x5", "5x5", "555"], &["professor"]),
+    (EventId::Cube666, &["6x6x6 cube", "6x6x6", "6x6", "666"], &[]),
+    (EventId::Cube777, &["7x7x7 cube", "7x7x7", "7x7", "777"], &[]),
+    (EventId::Blind333, &["3x3x3 blindfolded", "3x3 blindfolded", "3x3 bld", "333bf", "3bld"], &["blindfolded", "bld"]),
+    (EventId::FewestMoves333, &["3x3x3 fewest moves", "fewest moves", "fewest moves challenge", "333fm", "fmc"], &["fm"]),
+    (EventId::OneHanded333, &["3x3x3 one handed", "3x3 one handed", "one handed", "3x3 oh", "333oh"], &["oh"]),
+    (EventId::Feet333, &["3x3x3 with feet", "3x3 with feet", "with feet", "333ft"], &["feet"]),
+    (EventId::Clock, &["clock", "rubik s clock"], &[]),
+    (EventId::Megaminx, &["megaminx", "minx"], &["mega"]),
+    (EventId::Pyraminx, &["pyraminx", "pyram"], &["pyra"]),
+    (EventId::Skewb, &["skewb"], &[]),
+    (EventId::Square1, &["square 1", "square one", "sq1", "sq 1"], &["square"]),
+    (EventId::Blind444, &["4x4x4 blindfolded", "4x4 blindfolded", "4x4 bld", "444bf", "4bld"], &[]),
+    (EventId::Blind555, &["5x5x5 blindfolded", "5x5 blindfolded", "5x5 bld", "555bf", "5bld"], &[]),
+    (EventId::MultiBlind333, &["3x3x3 multi blind", "multi blind", "multiblind", "multi bld", "333mbf", "mbld"], &["multi"]),
+    (EventId::Magic, &["magic", "rubik s magic"], &[]),
+    (EventId::MasterMagic, &["master magic", "mmagic"], &[]),
+    (EventId::MultiBlindOldStyle333, &["3x3x3 multi blind old style", "multi blind old style", "333mbo"], &[]),
+];
+
+// Aliases of activities without an event, with the code they stand for
+const UNOFFICIAL_ALIASES: [(&str, &str); 11] = [
+    ("registration", "registration"),
+    ("check in", "checkin"),
+    ("checkin", "checkin"),
+    ("tutorial", "tutorial"),
+    ("submission", "multi"),
+    ("breakfast", "breakfast"),
+    ("lunch", "lunch"),
+    ("dinner", "dinner"),
+    ("awards", "awards"),
+    ("award ceremony", "awards"),
+    ("podium", "awards"),
+];
+
+const ORDINALS: [&str; 4] = ["first", "second", "third", "fourth"];
+
+// Confidence of a guess, multiplied for every uncertain part of the name
+const AMBIGUOUS_EVENT: f64 = 0.7;
+const IMPLIED_ROUND: f64 = 0.8;
+const EVENT_NOT_HELD: f64 = 0.5;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferredActivityCode {
+    pub activity_code: ActivityCode,
+    // Between 0 and 1
+    pub confidence: f64,
+}
+
+// An activity whose code doesn't match its name. Only applied once confirmed
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityCodeSuggestion {
+    pub activity_id: ActivityId,
+    pub name: String,
+    pub current: ActivityCode,
+    pub suggested: ActivityCode,
+    pub confidence: f64,
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+impl ActivityCodeSuggestion {
+    pub fn confirm(&mut self) {
+        self.confirmed = true;
+    }
+}
+
+// Lowercase words separated by single spaces, with a space at both ends so aliases can be matched as whole words
+fn normalize(name: &str) -> String {
+    let words: Vec<String> = name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+// The event of the longest alias found in the name, with the confidence of the match
+fn infer_event(name: &str) -> Option<(EventId, f64)> {
+    EVENT_ALIASES.iter()
+        .flat_map(|(event_id, aliases, ambiguous)| aliases.iter().map(move |a| (event_id, *a, 1.0))
+            .chain(ambiguous.iter().map(move |a| (event_id, *a, AMBIGUOUS_EVENT))))
+        .filter(|(_, alias, _)| name.contains(&format!(" {alias} ")))
+        .max_by_key(|(_, alias, _)| alias.len())
+        .map(|(event_id, _, confidence)| (event_id.clone(), confidence))
+}
+
+// A number following one of the keywords ("round 2", "r2", "group b")
+fn numbered(words: &[&str], keywords: &[&str]) -> Option<u32> {
+    words.iter().enumerate().find_map(|(i, word)| {
+        let value = keywords.iter().find_map(|k| word.strip_prefix(k)).filter(|v| !v.is_empty())
+            .or_else(|| keywords.contains(word).then(|| words.get(i + 1).copied()).flatten())?;
+        match value.parse() {
+            Ok(n) => Some(n),
+            Err(_) if value.len() == 1 && keywords.contains(word) => value.chars().next()
+                .filter(char::is_ascii_alphabetic)
+                .map(|c| c as u32 - 'a' as u32 + 1),
+            Err(_) => None,
+        }
+    })
+}
+
+// The round of a name, resolving "final" and "semi final" with the number of rounds of the event if known
+fn infer_round(name: &str, round_count: Option<RoundIdType>) -> Option<RoundIdType> {
+    let words: Vec<&str> = name.split_whitespace().collect();
+    if let Some(round) = numbered(&words, &["round", "r"]) {
+        return Some(round);
+    }
+    if let Some(i) = ORDINALS.iter().position(|o| name.contains(&format!(" {o} round "))) {
+        return Some(i as RoundIdType + 1);
+    }
+    let rounds = round_count?;
+    if name.contains(" semi final ") || name.contains(" semifinal ") || name.contains(" semifinals ") {
+        rounds.checked_sub(1).filter(|r| *r > 0)
+    } else if name.contains(" final ") || name.contains(" finals ") {
+        Some(rounds)
+    } else {
+        None
+    }
+}
+
+impl Competition {
+    fn round_count(&self, event_id: &EventId) -> Option<RoundIdType> {
+        self.events.iter().find(|e| e.id == *event_id).map(|e| e.rounds.len() as RoundIdType)
+    }
+
+    // Best-effort guess of the activity code of an activity name like "3x3 Round 1 Group 2" or "Lunch". Events of
+    // the competition are used to resolve "Final" and rounds of single round events. None if the name doesn't
+    // mention an event, or the round can't be told
+    pub fn infer_activity_code(&self, name: &str) -> Option<InferredActivityCode> {
+        let name = normalize(name);
+        let Some((event, mut confidence)) = infer_event(&name) else {
+            return UNOFFICIAL_ALIASES.iter()
+                .find(|(alias, _)| name.contains(&format!(" {alias} ")))
+                .and_then(|(_, code)| code.parse().ok())
+                .map(|code| InferredActivityCode { activity_code: ActivityCode::Unofficial(code), confidence: 1.0 });
+        };
+        if name.contains(" submission ") {
+            return Some(InferredActivityCode { activity_code: ActivityCode::Unofficial(UnofficialActivityCode::MultiSubmission), confidence });
+        }
+        let round_count = self.round_count(&event);
+        if round_count.is_none() {
+            confidence *= EVENT_NOT_HELD;
+        }
+        let round = match infer_round(&name, round_count) {
+            Some(round) => round,
+            None if round_count == Some(1) => {
+                confidence *= IMPLIED_ROUND;
+                1
+            },
+            None => return None,
+        };
+        if round_count.is_some_and(|count| round > count) {
+            confidence *= EVENT_NOT_HELD;
+        }
+        let words: Vec<&str> = name.split_whitespace().collect();
+        let group = numbered(&words, &["group", "g"]).map(|g| g as GroupIdType);
+        let attempt = numbered(&words, &["attempt", "a"]).and_then(|a| AttemptIdType::try_from(a).ok());
+        let activity_code = ActivityCode::Official(EventActivityCode { event, round: Some(round), group, attempt });
+        Some(InferredActivityCode { activity_code, confidence })
+    }
+
+    // Activities whose code differs from the one inferred from their name with at least `min_confidence`
+    pub fn suggest_activity_codes(&self, min_confidence: f64) -> Vec<ActivityCodeSuggestion> {
+        self.schedule.all_activities().into_iter()
+            .filter_map(|activity| {
+                let inferred = self.infer_activity_code(&activity.name)?;
+                (inferred.confidence >= min_confidence && inferred.activity_code != activity.activity_code).then(|| ActivityCodeSuggestion {
+                    activity_id: activity.id,
+                    name: activity.name.clone(),
+                    current: activity.activity_code.clone(),
+                    suggested: inferred.activity_code,
+                    confidence: inferred.confidence,
+                    confirmed: false,
+                })
+            })
+            .collect()
+    }
+
+    // Applies the confirmed suggestions whose activity still has the code it had when suggested. Returns the
+    // number of changed activities
+    pub fn apply_activity_code_suggestions(&mut self, suggestions: &[ActivityCodeSuggestion]) -> usize {
+        let mut applied = 0;
+        self.schedule.for_each_activity_mut(|activity| {
+            let suggestion = suggestions.iter()
+                .find(|s| s.confirmed && s.activity_id == activity.id && s.current == activity.activity_code);
+            if let Some(suggestion) = suggestion {
+                trace_change!(activity_id = activity.id, from = %suggestion.current, to = %suggestion.suggested, "changed activity code");
+                activity.activity_code = suggestion.suggested.clone();
+                applied += 1;
+            }
+        });
+        applied
+    }
+}
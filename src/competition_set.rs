@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use crate::types::{AttemptResult, Competition, CompetitionId, CountryCode, EventId, Person, ResultType, RoundId, RoundResult, WCAId};
+
+#[derive(Debug)]
+pub enum SetError {
+    Io(std::io::Error),
+    Json { path: PathBuf, error: serde_json::Error },
+}
+
+impl Display for SetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Json { path, error } => write!(f, "{}: {error}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for SetError {}
+
+// Many competitions, ordered by start date, for queries across them. Persons are identified by their WCA id,
+// persons without one can't be followed across competitions
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompetitionSet {
+    competitions: Vec<Competition>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersonResult<'a> {
+    pub competition: &'a Competition,
+    pub round_id: &'a RoundId,
+    pub result: &'a RoundResult,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgressionEntry<'a> {
+    pub competition: &'a Competition,
+    pub round_id: &'a RoundId,
+    pub person: &'a Person,
+    pub result: AttemptResult,
+}
+
+impl CompetitionSet {
+    pub fn new(competitions: Vec<Competition>) -> Self {
+        let mut set = Self { competitions };
+        set.competitions.sort_by_key(|c| (c.schedule.start_date, c.id.clone()));
+        set
+    }
+
+    // Loads all .json files of a directory
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, SetError> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir).map_err(SetError::Io)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()
+            .map_err(SetError::Io)?;
+        paths.retain(|p| p.extension().is_some_and(|e| e == "json"));
+        paths.sort();
+        let competitions = paths.into_iter()
+            .map(|path| {
+                let file = File::open(&path).map_err(SetError::Io)?;
+                serde_json::from_reader(BufReader::new(file)).map_err(|error| SetError::Json { path, error })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(competitions))
+    }
+
+    // Adds a competition, replacing one with the same id
+    pub fn insert(&mut self, competition: Competition) {
+        self.competitions.retain(|c| c.id != competition.id);
+        let index = self.competitions.partition_point(|c| (c.schedule.start_date, &c.id) < (competition.schedule.start_date, &competition.id));
+        self.competitions.insert(index, competition);
+    }
+
+    pub fn competitions(&self) -> &[Competition] {
+        &self.competitions
+    }
+
+    pub fn find(&self, id: &CompetitionId) -> Option<&Competition> {
+        self.competitions.iter().find(|c| c.id == *id)
+    }
+
+    pub fn competitions_of(&self, wca_id: &WCAId) -> Vec<&Competition> {
+        self.competitions.iter()
+            .filter(|c| c.persons.iter().any(|p| p.wca_id.as_ref() == Some(wca_id)))
+            .collect()
+    }
+
+    // All results of a person, oldest first
+    pub fn person_results(&self, wca_id: &WCAId) -> Vec<PersonResult<'_>> {
+        self.competitions.iter()
+            .filter_map(|c| c.persons.iter().find(|p| p.wca_id.as_ref() == Some(wca_id)).map(|p| (c, p)))
+            .flat_map(|(competition, person)| competition.events.iter()
+                .flat_map(|e| e.rounds.iter())
+                .flat_map(move |round| round.results.iter()
+                    .filter(move |r| Some(r.person_id) == person.registrant_id)
+                    .map(move |result| PersonResult { competition, round_id: &round.id, result })))
+            .collect()
+    }
+
+    // Number of competitions per country of their first venue
+    pub fn competitions_per_country(&self) -> BTreeMap<CountryCode, usize> {
+        let mut counts = BTreeMap::new();
+        for venue in self.competitions.iter().filter_map(|c| c.schedule.venues.first()) {
+            *counts.entry(venue.country_iso2.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    // Results that improved or tied the best result of an event within the set, oldest first. With `country_iso2`
+    // only competitors from that country are considered, e.g. for the progression of a national record
+    pub fn record_progression(&self, event_id: &EventId, result_type: &ResultType, country_iso2: Option<&str>) -> Vec<ProgressionEntry<'_>> {
+        let mut progression = vec![];
+        let mut current: Option<AttemptResult> = None;
+        for competition in &self.competitions {
+            for round in competition.events.iter().filter(|e| e.id == *event_id).flat_map(|e| e.rounds.iter()) {
+                let candidates: Vec<(&Person, AttemptResult)> = round.results.iter()
+                    .filter(|r| r.get_result(result_type).is_success())
+                    .filter_map(|r| competition.find_person(r.person_id).map(|p| (p, *r.get_result(result_type))))
+                    .filter(|(p, _)| country_iso2.is_none_or(|c| p.country_iso2 == c))
+                    .collect();
+                let Some(round_best) = candidates.iter().map(|(_, r)| *r).min() else {
+                    continue;
+                };
+                if current.is_some_and(|c| round_best > c) {
+                    continue;
+                }
+                current = Some(round_best);
+                progression.extend(candidates.into_iter()
+                    .filter(|(_, r)| *r == round_best)
+                    .map(|(person, result)| ProgressionEntry { competition, round_id: &round.id, person, result }));
+            }
+        }
+        progression
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt::{Display, Formatter};
+use serde::Serialize;
+use serde_json::Value;
+use crate::diff::escape;
+#[cfg(feature = "parse_activity_code")]
+use crate::repair::{normalize_activity_codes, Repair};
+use crate::types::{Competition, FormatVersion};
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+use crate::validation::ValidationError;
+
+// Field names used by some producers instead of the ones in the specification, as (alias, specified name).
+// Documents using them are accepted when parsing leniently
+pub const FIELD_ALIASES: &[(&str, &str)] = &[
+    ("competitionIds", "competitionsIds"),
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ParseMode {
+    // Accept documents with known deviations from the specification
+    #[default]
+    Lenient,
+    // Reject documents that deviate from the specification
+    Strict,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum SpecDeviation {
+    AliasedField { path: String, found: String, expected: String },
+    MissingField { path: String, field: String },
+    UnknownFormatVersion { version: String },
+    IncompatibleFormatVersion { version: String },
+}
+
+impl Display for SpecDeviation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AliasedField { path, found, expected } => write!(f, "{path}: found field {found} instead of {expected}"),
+            Self::UnknownFormatVersion { version } => write!(f, "Unknown format version {version}"),
+            Self::IncompatibleFormatVersion { version } => write!(f, "Format version {version} is not compatible with this crate"),
+            Self::MissingField { path, field } if path.is_empty() => write!(f, "Missing field {field}"),
+            Self::MissingField { path, field } => write!(f, "{path}: missing field {field}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    Json(serde_json::Error),
+    Deviations(Vec<SpecDeviation>),
+    #[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+    Invalid(Vec<ValidationError>),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "{e}"),
+            Self::Deviations(deviations) => {
+                write!(f, "Document deviates from the specification:")?;
+                for deviation in deviations {
+                    write!(f, "\n{deviation}")?;
+                }
+                Ok(())
+            },
+            #[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+            Self::Invalid(errors) => {
+                write!(f, "Document is invalid:")?;
+                for error in errors {
+                    write!(f, "\n{error}")?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// How `parse` treats documents that deviate from the specification or fail validation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Profile {
+    // Accept known deviations and report validation errors
+    #[default]
+    Lenient,
+    // Reject documents with deviations or validation errors
+    Strict,
+    // Like lenient, but fix common data corruptions first (see `repair`)
+    #[cfg(feature = "parse_activity_code")]
+    Repair,
+}
+
+// What parsing found and changed, serializable for tools consuming it
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseReport {
+    pub profile: Profile,
+    pub deviations: Vec<SpecDeviation>,
+    #[cfg(feature = "parse_activity_code")]
+    pub repairs: Vec<Repair>,
+    // Of the document after repairs
+    #[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+    pub validation_errors: Vec<ValidationError>,
+}
+
+// Finds the known deviations from the specification in a raw document
+pub fn spec_deviations(value: &Value) -> Vec<SpecDeviation> {
+    let mut deviations = vec![];
+    deviations.extend(format_version_deviation(value));
+    collect_deviations(value, "", &mut deviations);
+    collect_missing_fields(value, &mut deviations);
+    deviations
+}
+
+fn require(value: &Value, path: &str, fields: &[&str], deviations: &mut Vec<SpecDeviation>) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    for field in fields {
+        if !map.contains_key(*field) && !FIELD_ALIASES.iter().any(|(alias, expected)| expected == field && map.contains_key(*alias)) {
+            deviations.push(SpecDeviation::MissingField { path: path.to_string(), field: field.to_string() });
+        }
+    }
+}
+
+fn elements<'a>(value: &'a Value, path: &str, field: &str) -> Vec<(String, &'a Value)> {
+    value.get(field)
+        .and_then(|v| v.as_array())
+        .map(|values| values.iter()
+            .enumerate()
+            .map(|(i, v)| (format!("{path}/{field}/{i}"), v))
+            .collect())
+        .unwrap_or_default()
+}
+
+fn require_activity(activity: &Value, path: &str, deviations: &mut Vec<SpecDeviation>) {
+    require(activity, path, &["childActivities", "extensions"], deviations);
+    for (child_path, child) in elements(activity, path, "childActivities") {
+        require_activity(child, &child_path, deviations);
+    }
+}
+
+// Fields required by the specification that real documents sometimes omit and which are defaulted when parsing
+fn collect_missing_fields(value: &Value, deviations: &mut Vec<SpecDeviation>) {
+    require(value, "", &["registrationInfo", "extensions"], deviations);
+    for (path, person) in elements(value, "", "persons") {
+        require(person, &path, &["roles", "assignments", "personalBests", "extensions"], deviations);
+        if let Some(avatar) = person.get("avatar").filter(|a| a.is_object()) {
+            require(avatar, &format!("{path}/avatar"), &["url", "thumbUrl"], deviations);
+        }
+    }
+    for (path, event) in elements(value, "", "events") {
+        require(event, &path, &["extensions"], deviations);
+        for (path, round) in elements(event, &path, "rounds") {
+            require(round, &path, &["results", "extensions"], deviations);
+        }
+    }
+    let Some(schedule) = value.get("schedule") else {
+        return;
+    };
+    for (path, venue) in elements(schedule, "/schedule", "venues") {
+        require(venue, &path, &["extensions"], deviations);
+        for (path, room) in elements(venue, &path, "rooms") {
+            require(room, &path, &["extensions"], deviations);
+            for (path, activity) in elements(room, &path, "activities") {
+                require_activity(activity, &path, deviations);
+            }
+        }
+    }
+}
+
+fn collect_deviations(value: &Value, path: &str, deviations: &mut Vec<SpecDeviation>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = format!("{path}/{}", escape(key));
+                if let Some((_, expected)) = FIELD_ALIASES.iter().find(|(alias, _)| alias == key) {
+                    deviations.push(SpecDeviation::AliasedField {
+                        path: child_path.clone(),
+                        found: key.clone(),
+                        expected: expected.to_string(),
+                    });
+                }
+                collect_deviations(child, &child_path, deviations);
+            }
+        },
+        Value::Array(values) => {
+            for (i, child) in values.iter().enumerate() {
+                collect_deviations(child, &format!("{path}/{i}"), deviations);
+            }
+        },
+        _ => {},
+    }
+}
+
+fn format_version_deviation(value: &Value) -> Option<SpecDeviation> {
+    let version = value.get("formatVersion")?.as_str()?;
+    match FormatVersion::from_document(version) {
+        FormatVersion::V1_0 => None,
+        v if v.is_compatible() => Some(SpecDeviation::UnknownFormatVersion { version: version.to_string() }),
+        _ => Some(SpecDeviation::IncompatibleFormatVersion { version: version.to_string() }),
+    }
+}
+
+// Deserializing rejects versions this crate doesn't know, so documents of a compatible unknown version are
+// deserialized as the latest known version and get their own version back afterwards
+fn deserialize_lenient(mut value: Value) -> Result<Competition, ParseError> {
+    let version = value.get("formatVersion").and_then(|v| v.as_str()).map(FormatVersion::from_document);
+    let unknown = version.filter(|v| matches!(v, FormatVersion::Unknown(_)));
+    if unknown.is_some() {
+        value["formatVersion"] = Value::String(FormatVersion::LATEST.to_string());
+    }
+    let mut competition: Competition = serde_json::from_value(value).map_err(ParseError::Json)?;
+    if let Some(version) = unknown {
+        competition.format_version = version;
+    }
+    Ok(competition)
+}
+
+// Lenient parsing accepts unknown minor versions of the specification, strict parsing only accepts known versions.
+// Documents of an incompatible major version are always rejected
+pub fn parse_competition(json: &str, mode: ParseMode) -> Result<Competition, ParseError> {
+    let value: Value = serde_json::from_str(json).map_err(ParseError::Json)?;
+    if let Some(deviation @ SpecDeviation::IncompatibleFormatVersion { .. }) = format_version_deviation(&value) {
+        return Err(ParseError::Deviations(vec![deviation]));
+    }
+    if mode == ParseMode::Strict {
+        let deviations = spec_deviations(&value);
+        if !deviations.is_empty() {
+            return Err(ParseError::Deviations(deviations));
+        }
+    }
+    deserialize_lenient(value)
+}
+
+impl ParseReport {
+    pub fn is_clean(&self) -> bool {
+        #[allow(unused_mut)]
+        let mut clean = self.deviations.is_empty();
+        #[cfg(feature = "parse_activity_code")]
+        {
+            clean &= self.repairs.is_empty();
+        }
+        #[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+        {
+            clean &= self.validation_errors.is_empty();
+        }
+        clean
+    }
+}
+
+impl Display for ParseReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_clean() {
+            return write!(f, "No problems found");
+        }
+        #[allow(unused_mut)]
+        let mut lines: Vec<String> = self.deviations.iter().map(|d| format!("Deviation: {d}")).collect();
+        #[cfg(feature = "parse_activity_code")]
+        lines.extend(self.repairs.iter().map(|r| format!("Repaired: {r}")));
+        #[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+        lines.extend(self.validation_errors.iter().map(|e| format!("Invalid: {e}")));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+// Single entry point combining lenient parsing, strict validation and repairs as chosen by the profile
+pub fn parse(json: &str, profile: Profile) -> Result<(Competition, ParseReport), ParseError> {
+    #[allow(unused_mut)]
+    let mut value: Value = serde_json::from_str(json).map_err(ParseError::Json)?;
+    #[cfg(feature = "parse_activity_code")]
+    let mut repairs = match profile {
+        Profile::Repair => normalize_activity_codes(&mut value),
+        _ => vec![],
+    };
+    if let Some(deviation @ SpecDeviation::IncompatibleFormatVersion { .. }) = format_version_deviation(&value) {
+        return Err(ParseError::Deviations(vec![deviation]));
+    }
+    let deviations = spec_deviations(&value);
+    if profile == Profile::Strict && !deviations.is_empty() {
+        return Err(ParseError::Deviations(deviations));
+    }
+    #[allow(unused_mut)]
+    let mut competition = deserialize_lenient(value)?;
+    #[cfg(feature = "parse_activity_code")]
+    if profile == Profile::Repair {
+        repairs.extend(competition.repair());
+    }
+    #[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+    let validation_errors = competition.validate();
+    #[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+    if profile == Profile::Strict && !validation_errors.is_empty() {
+        return Err(ParseError::Invalid(validation_errors));
+    }
+    let report = ParseReport {
+        profile,
+        deviations,
+        #[cfg(feature = "parse_activity_code")]
+        repairs,
+        #[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+        validation_errors,
+    };
+    Ok((competition, report))
+}
@@ -0,0 +1,99 @@
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, BufReader, Read, Write};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use crate::types::Competition;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CompressionFormat {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl CompressionFormat {
+    // Detects the format from the magic bytes at the start of a payload, anything else is assumed to be plain JSON
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&GZIP_MAGIC) {
+            Self::Gzip
+        } else if bytes.starts_with(&ZSTD_MAGIC) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            Self::None => "json",
+            Self::Gzip => "json.gz",
+            Self::Zstd => "json.zst",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CompressionError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl Display for CompressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for CompressionError {}
+
+// Reads a plain, gzip or zstd compressed document, detecting the compression from the payload
+pub fn from_reader_compressed<R: Read>(reader: R) -> Result<Competition, CompressionError> {
+    let mut reader = BufReader::new(reader);
+    let format = CompressionFormat::detect(reader.fill_buf().map_err(CompressionError::Io)?);
+    match format {
+        CompressionFormat::None => serde_json::from_reader(reader),
+        CompressionFormat::Gzip => serde_json::from_reader(GzDecoder::new(reader)),
+        CompressionFormat::Zstd => {
+            let decoder = zstd::Decoder::with_buffer(reader).map_err(CompressionError::Io)?;
+            serde_json::from_reader(decoder)
+        },
+    }.map_err(CompressionError::Json)
+}
+
+// Decompresses a gzip or zstd payload, plain payloads are returned as they are
+pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    let mut decompressed = vec![];
+    match CompressionFormat::detect(bytes) {
+        CompressionFormat::None => return Ok(bytes.to_vec()),
+        CompressionFormat::Gzip => GzDecoder::new(bytes).read_to_end(&mut decompressed)?,
+        CompressionFormat::Zstd => zstd::Decoder::new(bytes)?.read_to_end(&mut decompressed)?,
+    };
+    Ok(decompressed)
+}
+
+pub fn to_writer_compressed<W: Write>(competition: &Competition, writer: W, format: CompressionFormat) -> Result<(), CompressionError> {
+    match format {
+        CompressionFormat::None => {
+            let mut writer = writer;
+            serde_json::to_writer(&mut writer, competition).map_err(CompressionError::Json)?;
+            writer.flush().map_err(CompressionError::Io)
+        },
+        CompressionFormat::Gzip => {
+            let mut encoder = GzEncoder::new(writer, flate2::Compression::default());
+            serde_json::to_writer(&mut encoder, competition).map_err(CompressionError::Json)?;
+            encoder.finish().and_then(|mut w| w.flush()).map_err(CompressionError::Io)
+        },
+        CompressionFormat::Zstd => {
+            let mut encoder = zstd::Encoder::new(writer, 0).map_err(CompressionError::Io)?;
+            serde_json::to_writer(&mut encoder, competition).map_err(CompressionError::Json)?;
+            encoder.finish().and_then(|mut w| w.flush()).map_err(CompressionError::Io)
+        },
+    }
+}
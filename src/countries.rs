@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use crate::regions::ContinentId;
+use crate::types::CountryCode;
+
+// A country as listed by the WCA. The id is what the WCA uses to refer to the country, e.g. in records
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Country {
+    pub iso2: &'static str,
+    pub id: &'static str,
+    pub continent_id: &'static str,
+}
+
+const fn country(iso2: &'static str, id: &'static str, continent_id: &'static str) -> Country {
+    Country { iso2, id, continent_id }
+}
+
+const AF: &str = "_Africa";
+const AS: &str = "_Asia";
+const EU: &str = "_Europe";
+const NA: &str = "_North America";
+const OC: &str = "_Oceania";
+const SA: &str = "_South America";
+
+// Countries and the "multiple countries" codes of a continent. XM (Americas) and XW (World) belong to no continent
+pub static COUNTRIES: &[Country] = &[
+    country("AF", "Afghanistan", AS),
+    country("AL", "Albania", EU),
+    country("DZ", "Algeria", AF),
+    country("AD", "Andorra", EU),
+    country("AO", "Angola", AF),
+    country("AG", "Antigua and Barbuda", NA),
+    country("AR", "Argentina", SA),
+    country("AM", "Armenia", EU),
+    country("AU", "Australia", OC),
+    country("AT", "Austria", EU),
+    country("AZ", "Azerbaijan", EU),
+    country("BS", "Bahamas", NA),
+    country("BH", "Bahrain", AS),
+    country("BD", "Bangladesh", AS),
+    country("BB", "Barbados", NA),
+    country("BY", "Belarus", EU),
+    country("BE", "Belgium", EU),
+    country("BZ", "Belize", NA),
+    country("BJ", "Benin", AF),
+    country("BT", "Bhutan", AS),
+    country("BO", "Bolivia", SA),
+    country("BA", "Bosnia and Herzegovina", EU),
+    country("BW", "Botswana", AF),
+    country("BR", "Brazil", SA),
+    country("BN", "Brunei", AS),
+    country("BG", "Bulgaria", EU),
+    country("BF", "Burkina Faso", AF),
+    country("BI", "Burundi", AF),
+    country("CV", "Cabo Verde", AF),
+    country("KH", "Cambodia", AS),
+    country("CM", "Cameroon", AF),
+    country("CA", "Canada", NA),
+    country("CF", "Central African Republic", AF),
+    country("TD", "Chad", AF),
+    country("CL", "Chile", SA),
+    country("CN", "China", AS),
+    country("TW", "Chinese Taipei", AS),
+    country("CO", "Colombia", SA),
+    country("KM", "Comoros", AF),
+    country("CG", "Congo", AF),
+    country("CR", "Costa Rica", NA),
+    country("CI", "Cote d_Ivoire", AF),
+    country("HR", "Croatia", EU),
+    country("CU", "Cuba", NA),
+    country("CY", "Cyprus", EU),
+    country("CZ", "Czech Republic", EU),
+    country("KP", "Democratic People's Republic of Korea", AS),
+    country("CD", "Democratic Republic of the Congo", AF),
+    country("DK", "Denmark", EU),
+    country("DJ", "Djibouti", AF),
+    country("DM", "Dominica", NA),
+    country("DO", "Dominican Republic", NA),
+    country("EC", "Ecuador", SA),
+    country("EG", "Egypt", AF),
+    country("SV", "El Salvador", NA),
+    country("GQ", "Equatorial Guinea", AF),
+    country("ER", "Eritrea", AF),
+    country("EE", "Estonia", EU),
+    country("SZ", "Eswatini", AF),
+    country("ET", "Ethiopia", AF),
+    country("FM", "Federated States of Micronesia", OC),
+    country("FJ", "Fiji", OC),
+    country("FI", "Finland", EU),
+    country("FR", "France", EU),
+    country("GA", "Gabon", AF),
+    country("GM", "Gambia", AF),
+    country("GE", "Georgia", EU),
+    country("DE", "Germany", EU),
+    country("GH", "Ghana", AF),
+    country("GR", "Greece", EU),
+    country("GD", "Grenada", NA),
+    country("GT", "Guatemala", NA),
+    country("GN", "Guinea", AF),
+    country("GW", "Guinea Bissau", AF),
+    country("GY", "Guyana", SA),
+    country("HT", "Haiti", NA),
+    country("HN", "Honduras", NA),
+    country("HK", "Hong Kong", AS),
+    country("HU", "Hungary", EU),
+    country("IS", "Iceland", EU),
+    country("IN", "India", AS),
+    country("ID", "Indonesia", AS),
+    country("IR", "Iran", AS),
+    country("IQ", "Iraq", AS),
+    country("IE", "Ireland", EU),
+    country("IL", "Israel", EU),
+    country("IT", "Italy", EU),
+    country("JM", "Jamaica", NA),
+    country("JP", "Japan", AS),
+    country("JO", "Jordan", AS),
+    country("KZ", "Kazakhstan", AS),
+    country("KE", "Kenya", AF),
+    country("KI", "Kiribati", OC),
+    country("XK", "Kosovo", EU),
+    country("KW", "Kuwait", AS),
+    country("KG", "Kyrgyzstan", AS),
+    country("LA", "Laos", AS),
+    country("LV", "Latvia", EU),
+    country("LB", "Lebanon", AS),
+    country("LS", "Lesotho", AF),
+    country("LR", "Liberia", AF),
+    country("LY", "Libya", AF),
+    country("LI", "Liechtenstein", EU),
+    country("LT", "Lithuania", EU),
+    country("LU", "Luxembourg", EU),
+    country("MO", "Macau", AS),
+    country("MG", "Madagascar", AF),
+    country("MW", "Malawi", AF),
+    country("MY", "Malaysia", AS),
+    country("MV", "Maldives", AS),
+    country("ML", "Mali", AF),
+    country("MT", "Malta", EU),
+    country("MH", "Marshall Islands", OC),
+    country("MR", "Mauritania", AF),
+    country("MU", "Mauritius", AF),
+    country("MX", "Mexico", NA),
+    country("MD", "Moldova", EU),
+    country("MC", "Monaco", EU),
+    country("MN", "Mongolia", AS),
+    country("ME", "Montenegro", EU),
+    country("MA", "Morocco", AF),
+    country("MZ", "Mozambique", AF),
+    country("MM", "Myanmar", AS),
+    country("NA", "Namibia", AF),
+    country("NR", "Nauru", OC),
+    country("NP", "Nepal", AS),
+    country("NL", "Netherlands", EU),
+    country("NZ", "New Zealand", OC),
+    country("NI", "Nicaragua", NA),
+    country("NE", "Niger", AF),
+    country("NG", "Nigeria", AF),
+    country("MK", "North Macedonia", EU),
+    country("NO", "Norway", EU),
+    country("OM", "Oman", AS),
+    country("PK", "Pakistan", AS),
+    country("PW", "Palau", OC),
+    country("PS", "Palestine", AS),
+    country("PA", "Panama", NA),
+    country("PG", "Papua New Guinea", OC),
+    country("PY", "Paraguay", SA),
+    country("PE", "Peru", SA),
+    country("PH", "Philippines", AS),
+    country("PL", "Poland", EU),
+    country("PT", "Portugal", EU),
+    country("QA", "Qatar", AS),
+    country("KR", "Korea", AS),
+    country("RO", "Romania", EU),
+    country("RU", "Russia", EU),
+    country("RW", "Rwanda", AF),
+    country("KN", "Saint Kitts and Nevis", NA),
+    country("LC", "Saint Lucia", NA),
+    country("VC", "Saint Vincent and the Grenadines", NA),
+    country("WS", "Samoa", OC),
+    country("SM", "San Marino", EU),
+    country("ST", "Sao Tome and Principe", AF),
+    country("SA", "Saudi Arabia", AS),
+    country("SN", "Senegal", AF),
+    country("RS", "Serbia", EU),
+    country("SC", "Seychelles", AF),
+    country("SL", "Sierra Leone", AF),
+    country("SG", "Singapore", AS),
+    country("SK", "Slovakia", EU),
+    country("SI", "Slovenia", EU),
+    country("SB", "Solomon Islands", OC),
+    country("SO", "Somalia", AF),
+    country("ZA", "South Africa", AF),
+    country("SS", "South Sudan", AF),
+    country("ES", "Spain", EU),
+    country("LK", "Sri Lanka", AS),
+    country("SD", "Sudan", AF),
+    country("SR", "Suriname", SA),
+    country("SE", "Sweden", EU),
+    country("CH", "Switzerland", EU),
+    country("SY", "Syria", AS),
+    country("TJ", "Tajikistan", AS),
+    country("TZ", "Tanzania", AF),
+    country("TH", "Thailand", AS),
+    country("TL", "Timor-Leste", AS),
+    country("TG", "Togo", AF),
+    country("TO", "Tonga", OC),
+    country("TT", "Trinidad and Tobago", NA),
+    country("TN", "Tunisia", AF),
+    country("TR", "Turkey", EU),
+    country("TM", "Turkmenistan", AS),
+    country("TV", "Tuvalu", OC),
+    country("UG", "Uganda", AF),
+    country("UA", "Ukraine", EU),
+    country("AE", "United Arab Emirates", AS),
+    country("GB", "United Kingdom", EU),
+    country("US", "USA", NA),
+    country("UY", "Uruguay", SA),
+    country("UZ", "Uzbekistan", AS),
+    country("VU", "Vanuatu", OC),
+    country("VA", "Vatican City", EU),
+    country("VE", "Venezuela", SA),
+    country("VN", "Vietnam", AS),
+    country("YE", "Yemen", AS),
+    country("ZM", "Zambia", AF),
+    country("ZW", "Zimbabwe", AF),
+    country("XF", "XF", AF),
+    country("XA", "XA", AS),
+    country("XE", "XE", EU),
+    country("XN", "XN", NA),
+    country("XO", "XO", OC),
+    country("XS", "XS", SA),
+];
+
+pub fn find_country(iso2: &str) -> Option<&'static Country> {
+    COUNTRIES.iter().find(|c| c.iso2 == iso2)
+}
+
+pub fn find_country_by_id(id: &str) -> Option<&'static Country> {
+    COUNTRIES.iter().find(|c| c.id == id)
+}
+
+// The mapping expected by `Competition::regional_results`
+pub fn continents() -> HashMap<CountryCode, ContinentId> {
+    COUNTRIES.iter()
+        .map(|c| (c.iso2.to_string(), c.continent_id.to_string()))
+        .collect()
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use crate::types::{Attempt, AttemptResult, Competition, Event, EventId, Person, PersonId, Round, RoundIdType, RoundResult};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CursorError {
+    UnknownEvent { path: String, event_id: EventId },
+    UnknownRound { path: String, round: RoundIdType },
+    UnknownResult { path: String, person_id: PersonId },
+    UnknownPerson { path: String, person_id: PersonId },
+    // The cursor is not at or below the kind of location the operation needs
+    NotAt { path: String, expected: &'static str },
+}
+
+impl Display for CursorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownEvent { path, event_id } => write!(f, "{path}: no event {event_id}"),
+            Self::UnknownRound { path, round } => write!(f, "{path}: no round {round}"),
+            Self::UnknownResult { path, person_id } => write!(f, "{path}: no result of person {person_id}"),
+            Self::UnknownPerson { path, person_id } => write!(f, "{path}: no person {person_id}"),
+            Self::NotAt { path, expected } => write!(f, "{path}: not at {expected}"),
+        }
+    }
+}
+
+impl std::error::Error for CursorError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Location {
+    Competition,
+    Person(usize),
+    Event(usize),
+    Round(usize, usize),
+    Result(usize, usize, usize),
+}
+
+// Points to a part of a competition for reading and changing it, e.g.
+// `competition.cursor().event(&event_id)?.round(2)?.result(42)?.set_attempt(0, result)?`.
+// Rounds whose results are changed through the cursor get their best, average and rankings recomputed when the
+// cursor is dropped, or earlier with `update_rankings`
+pub struct Cursor<'a> {
+    competition: &'a mut Competition,
+    location: Location,
+    stale_rounds: BTreeSet<(usize, usize)>,
+}
+
+impl Competition {
+    pub fn cursor(&mut self) -> Cursor<'_> {
+        Cursor { competition: self, location: Location::Competition, stale_rounds: BTreeSet::new() }
+    }
+}
+
+impl Cursor<'_> {
+    // JSON pointer of the current location, as used by diffs and spec deviations
+    pub fn path(&self) -> String {
+        match self.location {
+            Location::Competition => String::new(),
+            Location::Person(p) => format!("/persons/{p}"),
+            Location::Event(e) => format!("/events/{e}"),
+            Location::Round(e, r) => format!("/events/{e}/rounds/{r}"),
+            Location::Result(e, r, i) => format!("/events/{e}/rounds/{r}/results/{i}"),
+        }
+    }
+
+    fn not_at(&self, expected: &'static str) -> CursorError {
+        CursorError::NotAt { path: self.path(), expected }
+    }
+
+    // Moves to the parent of the current location
+    pub fn up(&mut self) -> &mut Self {
+        self.location = match self.location {
+            Location::Competition | Location::Person(_) | Location::Event(_) => Location::Competition,
+            Location::Round(e, _) => Location::Event(e),
+            Location::Result(e, r, _) => Location::Round(e, r),
+        };
+        self
+    }
+
+    pub fn person(&mut self, person_id: PersonId) -> Result<&mut Self, CursorError> {
+        let index = self.competition.persons.iter()
+            .position(|p| p.registrant_id == Some(person_id))
+            .ok_or_else(|| CursorError::UnknownPerson { path: self.path(), person_id })?;
+        self.location = Location::Person(index);
+        Ok(self)
+    }
+
+    pub fn event(&mut self, event_id: &EventId) -> Result<&mut Self, CursorError> {
+        let index = self.competition.events.iter()
+            .position(|e| e.id == *event_id)
+            .ok_or_else(|| CursorError::UnknownEvent { path: self.path(), event_id: event_id.clone() })?;
+        self.location = Location::Event(index);
+        Ok(self)
+    }
+
+    // A round of the current event
+    pub fn round(&mut self, round: RoundIdType) -> Result<&mut Self, CursorError> {
+        let event_index = match self.location {
+            Location::Event(e) | Location::Round(e, _) | Location::Result(e, _, _) => e,
+            _ => return Err(self.not_at("an event")),
+        };
+        let index = self.competition.events[event_index].rounds.iter()
+            .position(|r| r.id.round == round)
+            .ok_or_else(|| CursorError::UnknownRound { path: self.path(), round })?;
+        self.location = Location::Round(event_index, index);
+        Ok(self)
+    }
+
+    // The result of a person in the current round
+    pub fn result(&mut self, person_id: PersonId) -> Result<&mut Self, CursorError> {
+        let (event_index, round_index) = match self.location {
+            Location::Round(e, r) | Location::Result(e, r, _) => (e, r),
+            _ => return Err(self.not_at("a round")),
+        };
+        let index = self.competition.events[event_index].rounds[round_index].results.iter()
+            .position(|r| r.person_id == person_id)
+            .ok_or_else(|| CursorError::UnknownResult { path: self.path(), person_id })?;
+        self.location = Location::Result(event_index, round_index, index);
+        Ok(self)
+    }
+
+    pub fn current_person(&self) -> Option<&Person> {
+        match self.location {
+            Location::Person(p) => Some(&self.competition.persons[p]),
+            _ => None,
+        }
+    }
+
+    pub fn current_person_mut(&mut self) -> Option<&mut Person> {
+        match self.location {
+            Location::Person(p) => Some(&mut self.competition.persons[p]),
+            _ => None,
+        }
+    }
+
+    // The event the cursor is at or below
+    pub fn current_event(&self) -> Option<&Event> {
+        match self.location {
+            Location::Event(e) | Location::Round(e, _) | Location::Result(e, _, _) => Some(&self.competition.events[e]),
+            _ => None,
+        }
+    }
+
+    // The round the cursor is at or below
+    pub fn current_round(&self) -> Option<&Round> {
+        match self.location {
+            Location::Round(e, r) | Location::Result(e, r, _) => Some(&self.competition.events[e].rounds[r]),
+            _ => None,
+        }
+    }
+
+    // Marks the rankings of the round as stale. Moves the cursor up to the round, as its results may be reordered
+    pub fn current_round_mut(&mut self) -> Option<&mut Round> {
+        match self.location {
+            Location::Round(e, r) | Location::Result(e, r, _) => {
+                self.location = Location::Round(e, r);
+                self.stale_rounds.insert((e, r));
+                Some(&mut self.competition.events[e].rounds[r])
+            },
+            _ => None,
+        }
+    }
+
+    pub fn current_result(&self) -> Option<&RoundResult> {
+        match self.location {
+            Location::Result(e, r, i) => Some(&self.competition.events[e].rounds[r].results[i]),
+            _ => None,
+        }
+    }
+
+    // Marks the rankings of the round as stale
+    pub fn current_result_mut(&mut self) -> Option<&mut RoundResult> {
+        match self.location {
+            Location::Result(e, r, i) => {
+                self.stale_rounds.insert((e, r));
+                Some(&mut self.competition.events[e].rounds[r].results[i])
+            },
+            _ => None,
+        }
+    }
+
+    // Sets an attempt of the current result, filling skipped attempts before it
+    pub fn set_attempt(&mut self, index: usize, result: AttemptResult) -> Result<&mut Self, CursorError> {
+        let Some(round_result) = self.current_result_mut() else {
+            return Err(self.not_at("a result"));
+        };
+        if round_result.attempts.len() <= index {
+            round_result.attempts.resize(index + 1, Attempt { result: AttemptResult::Skipped, reconstruction: None });
+        }
+        round_result.attempts[index].result = result;
+        trace_change!(path = %self.path(), index, "set attempt");
+        Ok(self)
+    }
+
+    // Whether results were changed since the rankings were last recomputed
+    pub fn rankings_invalidated(&self) -> bool {
+        !self.stale_rounds.is_empty()
+    }
+
+    // Recomputes best, average and rankings of the rounds changed through the cursor
+    pub fn update_rankings(&mut self) {
+        for (e, r) in std::mem::take(&mut self.stale_rounds) {
+            self.competition.events[e].rounds[r].update_results();
+        }
+    }
+}
+
+impl Drop for Cursor<'_> {
+    fn drop(&mut self) {
+        self.update_rankings();
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime as ChronoDateTime, NaiveDateTime, Utc};
+use std::fmt::Formatter;
+use serde::Deserializer;
+use serde::de::{Error, Visitor};
+use crate::types::DateTime;
+
+const NAIVE_FORMATS: [&str; 4] = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%d %H:%M"];
+const OFFSET_FORMATS: [&str; 2] = ["%Y-%m-%dT%H:%M%#z", "%Y-%m-%d %H:%M%#z"];
+
+// Parses RFC 3339 timestamps with any offset, normalized to UTC. Also accepts timestamps without seconds
+// and timestamps without offset, which are interpreted as UTC.
+pub fn parse(s: &str) -> Option<DateTime> {
+    let s = s.trim();
+    if let Ok(dt) = ChronoDateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    let s_upper = s.replace('z', "Z");
+    let with_offset = match s_upper.strip_suffix('Z') {
+        Some(naive) => format!("{naive}+00:00"),
+        None => s_upper.clone(),
+    };
+    if let Some(dt) = OFFSET_FORMATS.iter().find_map(|f| ChronoDateTime::parse_from_str(&with_offset, f).ok()) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    NAIVE_FORMATS.iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(&s_upper, f).ok())
+        .map(|dt| dt.and_utc())
+}
+
+// Whether the timestamp is in the canonical form written by the WCA, e.g. "2024-05-04T07:00:00Z"
+pub fn is_canonical(s: &str) -> bool {
+    ChronoDateTime::parse_from_rfc3339(s).is_ok_and(|dt| dt.offset().local_minus_utc() == 0 && s.ends_with('Z') && s.as_bytes().get(10) == Some(&b'T'))
+}
+
+struct DateTimeVisitor;
+
+impl Visitor<'_> for DateTimeVisitor {
+    type Value = DateTime;
+
+    fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "a timestamp")
+    }
+
+    fn visit_str<E: Error>(self, s: &str) -> Result<Self::Value, E> {
+        parse(s).ok_or_else(|| Error::custom(format!("invalid timestamp {s}")))
+    }
+}
+
+// Parses the borrowed string without allocating
+pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<DateTime, D::Error> {
+    d.deserialize_str(DateTimeVisitor)
+}
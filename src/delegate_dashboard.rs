@@ -1,19 +1,32 @@
 use monostate::MustBe;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde::de::Error;
 use serde_with::serde_as;
 use serde_with::NoneAsEmptyString;
 use crate::types::WCAUserId;
 
+pub const EXTENSION_ID: &str = crate::extensions::DELEGATE_DASHBOARD_GROUPS_ID;
+pub const SPEC_URL: &str = crate::extensions::DELEGATE_DASHBOARD_GROUPS_SPEC_URL;
+
 // According to spec the id must be com.delegate-dashboard.groups, but that's not what is used in practice
 // To reliably identify it this library matches against the spec url, which could potentially break
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupsExtension {
     pub id: MustBe!("undefined.groups"),
-    pub spec_url: MustBe!("https://github.com/coder13/delegateDashboard/blob/main/public/wcif-extensions/groups.json"),
+    #[serde(deserialize_with = "deserialize_spec_url")]
+    pub spec_url: String,
     pub data: GroupsConfig,
 }
 
+fn deserialize_spec_url<'de, D: Deserializer<'de>>(d: D) -> Result<String, D::Error> {
+    let spec_url = String::deserialize(d)?;
+    if spec_url != SPEC_URL {
+        return Err(D::Error::custom(format!("unexpected spec url {spec_url}")));
+    }
+    Ok(spec_url)
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GroupsConfig {
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::types::Competition;
+
+// A single structural difference between two documents. Paths are JSON pointers (RFC 6901)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "op")]
+pub enum Change {
+    Added { path: String, value: Value },
+    Removed { path: String, value: Value },
+    Changed { path: String, old: Value, new: Value },
+}
+
+impl Change {
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. } => path,
+            Change::Removed { path, .. } => path,
+            Change::Changed { path, .. } => path,
+        }
+    }
+}
+
+impl Competition {
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(competition_id = %self.id)))]
+    pub fn diff(&self, other: &Competition) -> Result<Vec<Change>, serde_json::Error> {
+        let changes = diff_values(&serde_json::to_value(self)?, &serde_json::to_value(other)?);
+        trace_change!(changes = changes.len(), "computed diff");
+        Ok(changes)
+    }
+}
+
+pub fn diff_values(old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = vec![];
+    diff_at(String::new(), old, new, &mut changes);
+    changes
+}
+
+fn diff_at(path: String, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => diff_objects(&path, old, new, changes),
+        (Value::Array(old), Value::Array(new)) => {
+            for (i, (o, n)) in old.iter().zip(new.iter()).enumerate() {
+                diff_at(format!("{path}/{i}"), o, n, changes);
+            }
+            for (i, o) in old.iter().enumerate().skip(new.len()) {
+                changes.push(Change::Removed { path: format!("{path}/{i}"), value: o.clone() });
+            }
+            for (i, n) in new.iter().enumerate().skip(old.len()) {
+                changes.push(Change::Added { path: format!("{path}/{i}"), value: n.clone() });
+            }
+        },
+        (o, n) if o != n => changes.push(Change::Changed { path, old: o.clone(), new: n.clone() }),
+        _ => {}
+    }
+}
+
+fn diff_objects(path: &str, old: &Map<String, Value>, new: &Map<String, Value>, changes: &mut Vec<Change>) {
+    for (key, o) in old {
+        let child = format!("{path}/{}", escape(key));
+        match new.get(key) {
+            Some(n) => diff_at(child, o, n, changes),
+            None => changes.push(Change::Removed { path: child, value: o.clone() }),
+        }
+    }
+    for (key, n) in new {
+        if !old.contains_key(key) {
+            changes.push(Change::Added { path: format!("{path}/{}", escape(key)), value: n.clone() });
+        }
+    }
+}
+
+pub(crate) fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,575 @@
+use std::fmt::{Display, Formatter};
+use chrono::TimeDelta;
+use crate::types::{Activity, ActivityCode, ActivityId, AdvancementCondition, AssignmentCode, Competition, CompetitionId, Cutoff, Date, DateTime, Event, EventId, Extension, Person, PersonId, Round, RoundFormat, RoundId, RoundResult, TimeLimit, Venue};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EditError {
+    EventExists { event_id: EventId },
+    NoRounds { event_id: EventId },
+}
+
+impl Display for EditError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EventExists { event_id } => write!(f, "Event {event_id} is already held"),
+            Self::NoRounds { event_id } => write!(f, "Event {event_id} needs at least one round"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundSpec {
+    pub format: RoundFormat,
+    pub time_limit: Option<TimeLimit>,
+    pub cutoff: Option<Cutoff>,
+    pub advancement_condition: Option<AdvancementCondition>,
+}
+
+impl RoundSpec {
+    // The format and time limit the WCA website suggests for new rounds of an event. Fewest moves and multi blind
+    // don't have a time limit in WCIF
+    pub fn default_for(event_id: &EventId) -> Self {
+        let (format, centiseconds) = match event_id {
+            EventId::Cube666 | EventId::Cube777 => (RoundFormat::MeanOf3, Some(60000)),
+            EventId::Feet333 => (RoundFormat::MeanOf3, Some(60000)),
+            EventId::FewestMoves333 => (RoundFormat::MeanOf3, None),
+            EventId::Blind333 => (RoundFormat::BestOf3, Some(60000)),
+            EventId::Blind444 | EventId::Blind555 => (RoundFormat::BestOf3, Some(360000)),
+            EventId::MultiBlind333 | EventId::MultiBlindOldStyle333 => (RoundFormat::BestOf1, None),
+            _ => (RoundFormat::AverageOf5, Some(60000)),
+        };
+        RoundSpec {
+            format,
+            time_limit: centiseconds.map(|centiseconds| TimeLimit { centiseconds, cumulative_round_ids: vec![] }),
+            cutoff: None,
+            advancement_condition: None,
+        }
+    }
+
+    // Default specs for all rounds of an event. All rounds but the final advance the top 75%, the most regulation 9p1 allows
+    pub fn defaults(event_id: &EventId, round_count: usize) -> Vec<Self> {
+        (0..round_count)
+            .map(|i| RoundSpec {
+                advancement_condition: (i + 1 < round_count).then_some(AdvancementCondition::Percent { level: 75 }),
+                ..Self::default_for(event_id)
+            })
+            .collect()
+    }
+}
+
+// Everything that was removed from the document together with a person
+#[derive(Clone, Debug, PartialEq)]
+pub struct RemovedPerson {
+    // Includes the assignments and the registration of the person
+    pub person: Person,
+    pub results: Vec<(RoundId, RoundResult)>,
+}
+
+// The effect of changing the events of a registration
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventsChange {
+    pub added: Vec<EventId>,
+    pub dropped: Vec<EventId>,
+    // Competitor assignments of the person to activities of dropped events
+    pub removed_assignments: Vec<ActivityId>,
+    // Groups the person was removed from that now have fewer competitors than the minimum, with their remaining size
+    pub small_groups: Vec<(ActivityId, usize)>,
+}
+
+// Drops a person from the extensions of the crate that refer to persons by id. Incidents are kept without the person,
+// and staff who entered or granted attempts are forgotten
+#[allow(unused_variables)]
+fn remove_person_references(extensions: &mut [Extension], person_id: PersonId) {
+    for extension in extensions.iter_mut() {
+        match extension {
+            #[cfg(feature = "result_verification")]
+            Extension::ResultVerification(x) => x.data.entries.retain(|e| e.person_id != person_id),
+            #[cfg(feature = "attempt_provenance")]
+            Extension::AttemptProvenance(x) => {
+                x.data.entries.retain(|e| e.person_id != person_id);
+                x.data.entries.iter_mut()
+                    .filter(|e| e.entered_by == Some(person_id))
+                    .for_each(|e| e.entered_by = None);
+            }
+            #[cfg(feature = "extra_attempts")]
+            Extension::ExtraAttempts(x) => {
+                x.data.replaced.retain(|e| e.person_id != person_id);
+                x.data.replaced.iter_mut()
+                    .filter(|e| e.granted_by == Some(person_id))
+                    .for_each(|e| e.granted_by = None);
+            }
+            #[cfg(feature = "penalties")]
+            Extension::Penalties(x) => x.data.entries.retain(|e| e.person_id != person_id),
+            #[cfg(feature = "replication")]
+            Extension::AttemptClocks(x) => x.data.entries.retain(|e| e.person_id != person_id),
+            #[cfg(feature = "incidents")]
+            Extension::IncidentLog(x) => x.data.incidents.iter_mut()
+                .filter(|i| i.person_id == Some(person_id))
+                .for_each(|i| i.person_id = None),
+            #[cfg(feature = "teams")]
+            Extension::Teams(x) => x.data.teams.iter_mut().for_each(|t| t.person_ids.retain(|id| *id != person_id)),
+            #[cfg(feature = "waiting_list")]
+            Extension::WaitingList(x) => x.data.person_ids.retain(|id| *id != person_id),
+            #[cfg(feature = "unofficial_events")]
+            Extension::UnofficialEvents(x) => x.data.events.iter_mut()
+                .flat_map(|e| e.rounds.iter_mut())
+                .for_each(|r| r.results.retain(|r| r.person_id != person_id)),
+            _ => {}
+        }
+    }
+}
+
+impl Competition {
+    // Removes a person together with their results. Rounds the person had results in are re-ranked,
+    // which doesn't change who advanced to subsequent rounds
+    pub fn remove_person(&mut self, person_id: PersonId) -> Option<RemovedPerson> {
+        let index = self.persons.iter().position(|p| p.registrant_id == Some(person_id))?;
+        let person = self.persons.remove(index);
+        let mut results = vec![];
+        remove_person_references(&mut self.extensions, person_id);
+        for round in self.events.iter_mut().flat_map(|e| e.rounds.iter_mut()) {
+            remove_person_references(&mut round.extensions, person_id);
+            let Some(position) = round.results.iter().position(|r| r.person_id == person_id) else {
+                continue;
+            };
+            results.push((round.id.clone(), round.results.remove(position)));
+            round.update_rankings();
+        }
+        trace_change!(person_id, results = results.len(), "removed person");
+        Some(RemovedPerson { person, results })
+    }
+
+    // Adds an event with one round per spec. Events are kept in the order of the WCA website
+    pub fn add_event(&mut self, event_id: EventId, rounds: Vec<RoundSpec>) -> Result<&mut Event, EditError> {
+        if self.events.iter().any(|e| e.id == event_id) {
+            return Err(EditError::EventExists { event_id });
+        }
+        if rounds.is_empty() {
+            return Err(EditError::NoRounds { event_id });
+        }
+        let rounds: Vec<Round> = rounds.into_iter()
+            .enumerate()
+            .map(|(i, spec)| Round {
+                id: RoundId { event: event_id.clone(), round: i as u32 + 1 },
+                format: spec.format,
+                time_limit: spec.time_limit,
+                cutoff: spec.cutoff,
+                advancement_condition: spec.advancement_condition,
+                results: vec![],
+                scramble_set_count: 1,
+                scramble_sets: vec![],
+                extensions: vec![],
+            })
+            .collect();
+        trace_change!(event_id = %event_id, rounds = rounds.len(), "added event");
+        let position = self.events.iter().position(|e| e.id > event_id).unwrap_or(self.events.len());
+        self.events.insert(position, Event {
+            id: event_id,
+            rounds,
+            competitor_limit: None,
+            qualification: None,
+            extensions: vec![],
+        });
+        Ok(&mut self.events[position])
+    }
+
+    // Removes an event with its rounds and results, its activities and the assignments to them, and drops it from
+    // registrations and from cumulative time limits of other events
+    pub fn remove_event(&mut self, event_id: &EventId) -> Option<Event> {
+        let position = self.events.iter().position(|e| &e.id == event_id)?;
+        let event = self.events.remove(position);
+        for registration in self.persons.iter_mut().filter_map(|p| p.registration.as_mut()) {
+            registration.event_ids.retain(|e| e != event_id);
+        }
+        for time_limit in self.events.iter_mut().flat_map(|e| e.rounds.iter_mut()).filter_map(|r| r.time_limit.as_mut()) {
+            time_limit.cumulative_round_ids.retain(|r| &r.event != event_id);
+        }
+        while let Some(activity_id) = self.schedule.all_activities().iter()
+            .find(|a| matches!(&a.activity_code, ActivityCode::Official(code) if &code.event == event_id))
+            .map(|a| a.id) {
+            self.remove_activity(activity_id, false).expect("activity was just found");
+        }
+        trace_change!(event_id = %event_id, "removed event");
+        Some(event)
+    }
+
+    // Changes the events a person is registered for and removes their competitor assignments to activities of dropped
+    // events. `is_competing` is left as it is. Returns None if the person doesn't exist or has no registration
+    pub fn set_events(&mut self, person_id: PersonId, event_ids: Vec<EventId>, min_group_size: usize) -> Option<EventsChange> {
+        let activity_events: Vec<(ActivityId, EventId)> = self.schedule.all_activities().iter()
+            .filter_map(|a| match &a.activity_code {
+                ActivityCode::Official(code) => Some((a.id, code.event.clone())),
+                ActivityCode::Unofficial(_) => None,
+            })
+            .collect();
+        let person = self.find_person_mut(person_id)?;
+        let registration = person.registration.as_mut()?;
+        let mut change = EventsChange {
+            added: event_ids.iter().filter(|e| !registration.event_ids.contains(e)).cloned().collect(),
+            dropped: registration.event_ids.iter().filter(|e| !event_ids.contains(e)).cloned().collect(),
+            ..Default::default()
+        };
+        registration.event_ids = event_ids;
+        person.assignments.retain(|assignment| {
+            let dropped = assignment.assignment_code == AssignmentCode::Competitor && activity_events.iter()
+                .any(|(id, event)| *id == assignment.activity_id && change.dropped.contains(event));
+            if dropped {
+                change.removed_assignments.push(assignment.activity_id);
+            }
+            !dropped
+        });
+        change.small_groups = change.removed_assignments.iter()
+            .map(|activity_id| (*activity_id, self.persons.iter()
+                .filter(|p| p.assignments.iter().any(|a| a.activity_id == *activity_id && a.assignment_code == AssignmentCode::Competitor))
+                .count()))
+            .filter(|(_, size)| *size < min_group_size)
+            .collect();
+        trace_change!(person_id, added = change.added.len(), dropped = change.dropped.len(), removed_assignments = change.removed_assignments.len(), "changed events");
+        Some(change)
+    }
+
+    // The setup of the next edition of a competition: events, rounds, limits, the schedule (shifted to start on
+    // `start_date` in the local time of each venue), registration dates (shifted by the same amount in the local time
+    // of the first venue) and extensions. Persons, results, scrambles
+    // and crate-defined extensions holding data of a single edition are left out. Names are kept as they are
+    pub fn template_from(previous: &Competition, start_date: Date, id: CompetitionId) -> Competition {
+        let shift = start_date - previous.schedule.start_date;
+        let mut competition = Competition {
+            id,
+            series: None,
+            persons: vec![],
+            ..previous.clone()
+        };
+        competition.extensions.retain(|e| !is_edition_data(e));
+        for round in competition.events.iter_mut().flat_map(|e| e.rounds.iter_mut()) {
+            round.results.clear();
+            round.scramble_sets.clear();
+            round.extensions.retain(|e| !is_edition_data(e));
+        }
+        #[cfg(feature = "unofficial_events")]
+        if competition.unofficial_events().is_some() {
+            for round in competition.unofficial_events_mut().events.iter_mut().flat_map(|e| e.rounds.iter_mut()) {
+                round.results.clear();
+            }
+        }
+        competition.schedule.start_date = start_date;
+        for venue in competition.schedule.venues.iter_mut() {
+            let mut rooms = std::mem::take(&mut venue.rooms);
+            for activity in rooms.iter_mut().flat_map(|r| r.activities.iter_mut()) {
+                shift_activity(activity, venue, shift);
+            }
+            venue.rooms = rooms;
+        }
+        if let Some(registration_info) = competition.registration_info.as_mut() {
+            match competition.schedule.venues.first() {
+                Some(venue) => {
+                    registration_info.open_time = shift_local(&registration_info.open_time, venue, shift);
+                    registration_info.close_time = shift_local(&registration_info.close_time, venue, shift);
+                }
+                None => {
+                    registration_info.open_time += shift;
+                    registration_info.close_time += shift;
+                }
+            }
+        }
+        competition
+    }
+}
+
+fn shift_activity(activity: &mut Activity, venue: &Venue, shift: TimeDelta) {
+    activity.start_time = shift_local(&activity.start_time, venue, shift);
+    activity.end_time = shift_local(&activity.end_time, venue, shift);
+    activity.scramble_set_id = None;
+    for child in &mut activity.child_activities {
+        shift_activity(child, venue, shift);
+    }
+}
+
+// Shifts by whole days in the local time of the venue, so times stay the same on the clock across daylight saving
+// time changes. Local times skipped by a change are shifted in UTC instead
+fn shift_local(time: &DateTime, venue: &Venue, shift: TimeDelta) -> DateTime {
+    venue.utc_time(&(venue.local_time(time) + shift)).unwrap_or(*time + shift)
+}
+
+// Crate-defined extensions that refer to persons, results or scrambles of one edition
+fn is_edition_data(extension: &Extension) -> bool {
+    match extension {
+        #[cfg(feature = "waiting_list")]
+        Extension::WaitingList(_) => true,
+        #[cfg(feature = "scramble_encryption")]
+        Extension::EncryptedScrambleSets(_) => true,
+        #[cfg(feature = "result_verification")]
+        Extension::ResultVerification(_) => true,
+        #[cfg(feature = "attempt_provenance")]
+        Extension::AttemptProvenance(_) => true,
+        #[cfg(feature = "teams")]
+        Extension::Teams(_) => true,
+        #[cfg(feature = "incidents")]
+        Extension::IncidentLog(_) => true,
+        #[cfg(feature = "extra_attempts")]
+        Extension::ExtraAttempts(_) => true,
+        #[cfg(feature = "penalties")]
+        Extension::Penalties(_) => true,
+        #[cfg(feature = "replication")]
+        Extension::AttemptClocks(_) => true,
+        #[cfg(feature = "signing")]
+        Extension::ResultsSignature(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use super::*;
+
+    fn person(registrant_id: PersonId) -> Value {
+        json!({
+            "registrantId": registrant_id,
+            "name": format!("Person {registrant_id}"),
+            "wcaUserId": registrant_id,
+            "wcaId": null,
+            "countryIso2": "US",
+            "gender": "o",
+            "birthdate": "2000-01-01",
+            "email": "person@example.com",
+            "registration": null,
+        })
+    }
+
+    // Persons 1 to 3, where 1 and 2 competed in the first round of 3x3x3
+    fn competition() -> Competition {
+        let result = |person_id: PersonId| json!({
+            "personId": person_id,
+            "ranking": null,
+            "attempts": [{ "result": 1000 + person_id, "reconstruction": null }],
+            "best": 1000 + person_id,
+            "average": 0,
+        });
+        serde_json::from_value(json!({
+            "formatVersion": "1.0",
+            "id": "Test2026",
+            "name": "Test 2026",
+            "shortName": "Test 2026",
+            "series": null,
+            "persons": [person(1), person(2), person(3)],
+            "events": [{
+                "id": "333",
+                "rounds": [{
+                    "id": "333-r1",
+                    "format": "1",
+                    "timeLimit": null,
+                    "cutoff": null,
+                    "advancementCondition": null,
+                    "results": [result(1), result(2)],
+                    "scrambleSetCount": 1,
+                }],
+                "competitorLimit": null,
+                "qualification": null,
+            }],
+            "schedule": { "startDate": "2026-03-28", "numberOfDays": 2, "venues": [] },
+            "registrationInfo": null,
+            "competitorLimit": null,
+        })).unwrap()
+    }
+
+    fn round(competition: &mut Competition) -> &mut Round {
+        competition.find_round_mut(&RoundId { event: EventId::Cube333, round: 1 }).unwrap()
+    }
+
+    #[cfg(any(feature = "attempt_provenance", feature = "extra_attempts", feature = "replication", feature = "incidents"))]
+    fn at(seconds: i64) -> DateTime {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    #[test]
+    fn remove_person_removes_results() {
+        let mut competition = competition();
+        let removed = competition.remove_person(2).unwrap();
+        assert_eq!(removed.person.registrant_id, Some(2));
+        assert_eq!(removed.results.len(), 1);
+        assert!(competition.find_person(2).is_none());
+        assert_eq!(round(&mut competition).results.iter().map(|r| r.person_id).collect::<Vec<_>>(), [1]);
+        assert!(competition.remove_person(2).is_none());
+    }
+
+    #[cfg(feature = "result_verification")]
+    #[test]
+    fn remove_person_removes_verification() {
+        let mut competition = competition();
+        let attempts = |c: &mut Competition, id| round(c).results.iter().find(|r| r.person_id == id).unwrap()
+            .attempts.iter().map(|a| a.result).collect();
+        for person_id in [1, 2] {
+            let attempts = attempts(&mut competition, person_id);
+            round(&mut competition).enter_verification(person_id, attempts);
+        }
+        competition.remove_person(2);
+        let entries = &round(&mut competition).result_verification().unwrap().entries;
+        assert_eq!(entries.iter().map(|e| e.person_id).collect::<Vec<_>>(), [1]);
+    }
+
+    #[cfg(feature = "attempt_provenance")]
+    #[test]
+    fn remove_person_removes_provenance() {
+        use crate::provenance::AttemptProvenance;
+        let mut competition = competition();
+        for (person_id, entered_by) in [(1, Some(2)), (2, Some(3)), (1, Some(3))] {
+            round(&mut competition).record_attempt_provenance(AttemptProvenance {
+                person_id,
+                attempt_index: 0,
+                entered_at: at(0),
+                entered_by,
+                device_id: None,
+            });
+        }
+        competition.remove_person(2);
+        let entries = &round(&mut competition).attempt_provenance_log().unwrap().entries;
+        assert_eq!(entries.iter().map(|e| (e.person_id, e.entered_by)).collect::<Vec<_>>(), [(1, None), (1, Some(3))]);
+    }
+
+    #[cfg(feature = "extra_attempts")]
+    #[test]
+    fn remove_person_removes_extra_attempts() {
+        let mut competition = competition();
+        round(&mut competition).grant_extra_attempt(1, 0, at(0), Some(2), None).unwrap();
+        round(&mut competition).grant_extra_attempt(2, 0, at(1), Some(3), None).unwrap();
+        competition.remove_person(2);
+        let replaced = &round(&mut competition).extra_attempts().unwrap().replaced;
+        assert_eq!(replaced.iter().map(|e| (e.person_id, e.granted_by)).collect::<Vec<_>>(), [(1, None)]);
+    }
+
+    #[cfg(feature = "penalties")]
+    #[test]
+    fn remove_person_removes_penalties() {
+        use crate::penalties::{AttemptBuilder, PenaltyCause};
+        let mut competition = competition();
+        for person_id in [1, 2] {
+            let attempt = AttemptBuilder::new(1000).plus_two(PenaltyCause::Inspection);
+            round(&mut competition).enter_attempt(person_id, 0, attempt).unwrap();
+        }
+        competition.remove_person(2);
+        let entries = &round(&mut competition).penalty_log().unwrap().entries;
+        assert_eq!(entries.iter().map(|e| e.person_id).collect::<Vec<_>>(), [1]);
+    }
+
+    #[cfg(feature = "replication")]
+    #[test]
+    fn remove_person_removes_attempt_clocks() {
+        let mut competition = competition();
+        for person_id in [1, 2] {
+            let attempt = round(&mut competition).results[0].attempts[0].clone();
+            round(&mut competition).record_attempt(person_id, 0, attempt, "a", at(0)).unwrap();
+        }
+        competition.remove_person(2);
+        let entries = &round(&mut competition).attempt_clocks().unwrap().entries;
+        assert_eq!(entries.iter().map(|e| e.person_id).collect::<Vec<_>>(), [1]);
+    }
+
+    #[cfg(feature = "incidents")]
+    #[test]
+    fn remove_person_keeps_incidents_without_the_person() {
+        let mut competition = competition();
+        let round_id = RoundId { event: EventId::Cube333, round: 1 };
+        competition.log_attempt_incident(at(0), 2, &round_id, 1, vec!["A6e".to_string()], "Timer reset".to_string()).unwrap();
+        competition.log_incident(at(1), Some(1), vec![], "Late".to_string()).unwrap();
+        competition.remove_person(2);
+        let incidents = competition.incidents();
+        assert_eq!(incidents.iter().map(|i| i.person_id).collect::<Vec<_>>(), [None, Some(1)]);
+        assert_eq!(incidents[0].round_id, Some(round_id));
+    }
+
+    #[cfg(feature = "teams")]
+    #[test]
+    fn remove_person_removes_team_members() {
+        use crate::teams::Team;
+        let mut competition = competition();
+        competition.teams_mut().teams = vec![
+            Team { id: 1, name: "A".to_string(), person_ids: vec![1, 2] },
+            Team { id: 2, name: "B".to_string(), person_ids: vec![3] },
+        ];
+        competition.remove_person(2);
+        let teams = &competition.teams().unwrap().teams;
+        assert_eq!(teams.iter().map(|t| t.person_ids.clone()).collect::<Vec<_>>(), [vec![1], vec![3]]);
+    }
+
+    #[cfg(feature = "waiting_list")]
+    #[test]
+    fn remove_person_removes_from_waiting_list() {
+        let mut competition = competition();
+        competition.waiting_list_order_mut().person_ids = vec![3, 2, 1];
+        competition.remove_person(2);
+        assert_eq!(competition.waiting_list_order().unwrap().person_ids, [3, 1]);
+    }
+
+    #[cfg(feature = "unofficial_events")]
+    #[test]
+    fn remove_person_removes_unofficial_results() {
+        use crate::unofficial::{UnofficialEvent, UnofficialRound};
+        let mut competition = competition();
+        let results = round(&mut competition).results.clone();
+        competition.unofficial_events_mut().events.push(UnofficialEvent {
+            id: "mirror".to_string(),
+            name: "Mirror Blocks".to_string(),
+            rounds: vec![UnofficialRound {
+                format: RoundFormat::BestOf1,
+                time_limit: None,
+                cutoff: None,
+                advancement_condition: None,
+                results,
+            }],
+        });
+        competition.remove_person(2);
+        let results = &competition.unofficial_events().unwrap().events[0].rounds[0].results;
+        assert_eq!(results.iter().map(|r| r.person_id).collect::<Vec<_>>(), [1]);
+    }
+
+    #[cfg(feature = "timezones")]
+    #[test]
+    fn template_keeps_local_times_across_daylight_saving_time() {
+        let mut previous = competition();
+        // Europe switches to summer time on 2026-03-29
+        previous.schedule = serde_json::from_value(json!({
+            "startDate": "2026-03-21",
+            "numberOfDays": 1,
+            "venues": [{
+                "id": 1,
+                "name": "Hall",
+                "latitudeMicrodegrees": 0,
+                "longitudeMicrodegrees": 0,
+                "countryIso2": "DE",
+                "timezone": "Europe/Berlin",
+                "rooms": [{
+                    "id": 1,
+                    "name": "Main",
+                    "color": "#000000",
+                    "activities": [{
+                        "id": 1,
+                        "name": "3x3x3 Cube, Round 1",
+                        "activityCode": "333-r1",
+                        "startTime": "2026-03-21T08:00:00Z",
+                        "endTime": "2026-03-21T09:00:00Z",
+                        "childActivities": [],
+                        "scrambleSetId": null,
+                    }],
+                }],
+            }],
+        })).unwrap();
+        previous.registration_info = serde_json::from_value(json!({
+            "openTime": "2026-01-10T17:00:00Z",
+            "closeTime": "2026-03-20T11:00:00Z",
+            "baseEntryFee": 1000,
+            "currencyCode": "EUR",
+            "onTheSpotRegistration": false,
+            "useWcaRegistration": true,
+        })).unwrap();
+        let start_date = Date::from_ymd_opt(2026, 4, 4).unwrap();
+        let template = Competition::template_from(&previous, start_date, "Test2026b".parse().unwrap());
+        let activity = &template.schedule.venues[0].rooms[0].activities[0];
+        assert_eq!(activity.start_time.to_rfc3339(), "2026-04-04T07:00:00+00:00");
+        assert_eq!(activity.end_time.to_rfc3339(), "2026-04-04T08:00:00+00:00");
+        let registration_info = template.registration_info.unwrap();
+        // Opening stays in winter time, closing moves into summer time
+        assert_eq!(registration_info.open_time.to_rfc3339(), "2026-01-24T17:00:00+00:00");
+        assert_eq!(registration_info.close_time.to_rfc3339(), "2026-04-03T10:00:00+00:00");
+    }
+}
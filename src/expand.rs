@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use chrono::TimeDelta;
+use crate::activity_names::format_group_name;
+use crate::types::{Activity, ActivityCode, ActivityId, Competition, DateTime, EventActivityCode, GroupIdType, RoundId};
+
+// How the time of a round activity is split into groups
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GroupSplit {
+    Equal(u32),
+    // One weight per group, e.g. a longer first group for slower competitors
+    Weighted(Vec<u32>),
+}
+
+impl GroupSplit {
+    fn weights(&self) -> Vec<u32> {
+        match self {
+            Self::Equal(groups) => vec![1; *groups as usize],
+            Self::Weighted(weights) => weights.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExpandError {
+    UnknownActivity { activity_id: ActivityId },
+    NotARoundActivity { activity_id: ActivityId },
+    HasGroups { activity_id: ActivityId },
+    NoGroups { activity_id: ActivityId },
+}
+
+impl Display for ExpandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownActivity { activity_id } => write!(f, "Activity {activity_id} does not exist"),
+            Self::NotARoundActivity { activity_id } => write!(f, "Activity {activity_id} is not the activity of a round"),
+            Self::HasGroups { activity_id } => write!(f, "Activity {activity_id} already has child activities"),
+            Self::NoGroups { activity_id } => write!(f, "Activity {activity_id} would be split into no groups"),
+        }
+    }
+}
+
+impl Activity {
+    // Number of groups configured for this activity by Groupifier or Delegate Dashboard
+    pub fn configured_group_count(&self) -> Option<u32> {
+        self.extensions.iter().find_map(|e| match e {
+            #[cfg(feature = "groupifier")]
+            crate::types::Extension::GroupifierActivityConfig(x) => Some(x.data.groups),
+            #[cfg(feature = "delegate_dashboard")]
+            crate::types::Extension::DelegateDashboardGroups(x) => Some(x.data.groups),
+            _ => None,
+        })
+    }
+}
+
+impl Competition {
+    // Splits the activity of a round into group activities named after the template (see `format_group_name`).
+    // The groups use the scramble set of the same index as the group, cycling if the round has fewer scramble sets.
+    // Returns the ids of the new activities
+    pub fn expand_groups(&mut self, activity_id: ActivityId, split: &GroupSplit, template: &str) -> Result<Vec<ActivityId>, ExpandError> {
+        let activity = self.schedule.find_activity(activity_id).ok_or(ExpandError::UnknownActivity { activity_id })?;
+        let code = match &activity.activity_code {
+            ActivityCode::Official(code) if code.round.is_some() && code.group.is_none() && code.attempt.is_none() => code.clone(),
+            _ => return Err(ExpandError::NotARoundActivity { activity_id }),
+        };
+        if !activity.child_activities.is_empty() {
+            return Err(ExpandError::HasGroups { activity_id });
+        }
+        let weights = split.weights();
+        let total: u64 = weights.iter().map(|w| *w as u64).sum();
+        if total == 0 {
+            return Err(ExpandError::NoGroups { activity_id });
+        }
+        let scramble_set_ids: Vec<_> = self.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .find(|r| r.id == code)
+            .map(|r| r.scramble_sets.iter().map(|s| s.id).collect())
+            .unwrap_or_default();
+        let room = self.schedule.room_of(activity_id).expect("activity was just found").clone();
+        let first_id = self.schedule.all_activities().iter().map(|a| a.id).max().unwrap_or_default() + 1;
+        let duration = activity.get_duration().num_seconds();
+        let start_time = activity.start_time;
+        let mut elapsed = 0;
+        let groups: Vec<Activity> = weights.iter()
+            .enumerate()
+            .map(|(i, weight)| {
+                let group = i as GroupIdType + 1;
+                let start = start_time + TimeDelta::seconds(duration * elapsed as i64 / total as i64);
+                elapsed += *weight as u64;
+                let end = start_time + TimeDelta::seconds(duration * elapsed as i64 / total as i64);
+                Activity {
+                    id: first_id + i as ActivityId,
+                    name: format_group_name(template, &code.event, code.round.unwrap_or_default(), group, &room),
+                    activity_code: ActivityCode::Official(EventActivityCode { group: Some(group), ..code.clone() }),
+                    start_time: start,
+                    end_time: end,
+                    child_activities: vec![],
+                    scramble_set_id: (!scramble_set_ids.is_empty()).then(|| scramble_set_ids[i % scramble_set_ids.len()]),
+                    extensions: vec![],
+                }
+            })
+            .collect();
+        let ids = groups.iter().map(|a| a.id).collect();
+        let activity = self.schedule.venues.iter_mut()
+            .flat_map(|v| v.rooms.iter_mut())
+            .flat_map(|r| r.activities.iter_mut())
+            .find(|a| a.id == activity_id)
+            .ok_or(ExpandError::NotARoundActivity { activity_id })?;
+        activity.child_activities = groups;
+        Ok(ids)
+    }
+
+    // Splits every round activity without groups that has a group count configured by Groupifier or
+    // Delegate Dashboard into equal groups. Returns the ids of the expanded activities
+    pub fn expand_configured_groups(&mut self, template: &str) -> Vec<ActivityId> {
+        let configured: Vec<(ActivityId, u32)> = self.schedule.venues.iter()
+            .flat_map(|v| v.rooms.iter())
+            .flat_map(|r| r.activities.iter())
+            .filter(|a| a.child_activities.is_empty())
+            .filter_map(|a| Some((a.id, a.configured_group_count()?)))
+            .collect();
+        configured.into_iter()
+            .filter(|(activity_id, groups)| self.expand_groups(*activity_id, &GroupSplit::Equal(*groups), template).is_ok())
+            .map(|(activity_id, _)| activity_id)
+            .collect()
+    }
+
+    // Gives every group of a round split across rooms the time window it has in the first room it is scheduled in,
+    // so the same group runs simultaneously on all stages. Round activities are extended to cover their groups.
+    // Returns the ids of the changed activities
+    pub fn align_groups(&mut self, round_id: &RoundId) -> Vec<ActivityId> {
+        let group_of = |activity: &Activity| match &activity.activity_code {
+            ActivityCode::Official(code) if code.event == round_id.event && code.round == Some(round_id.round) => code.group,
+            _ => None,
+        };
+        let mut windows: HashMap<GroupIdType, (DateTime, DateTime)> = HashMap::new();
+        for activity in self.schedule.all_activities() {
+            if let Some(group) = group_of(activity) {
+                windows.entry(group).or_insert((activity.start_time, activity.end_time));
+            }
+        }
+        let mut changed = vec![];
+        self.schedule.for_each_activity_mut(|activity| {
+            let Some(&(start_time, end_time)) = group_of(activity).and_then(|g| windows.get(&g)) else {
+                return;
+            };
+            if (activity.start_time, activity.end_time) != (start_time, end_time) {
+                activity.start_time = start_time;
+                activity.end_time = end_time;
+                changed.push(activity.id);
+            }
+        });
+        for activity in self.schedule.venues.iter_mut()
+            .flat_map(|v| v.rooms.iter_mut())
+            .flat_map(|r| r.activities.iter_mut())
+            .filter(|a| a.child_activities.iter().any(|c| group_of(c).is_some())) {
+            let start_time = activity.child_activities.iter().map(|c| c.start_time).min().unwrap_or(activity.start_time);
+            let end_time = activity.child_activities.iter().map(|c| c.end_time).max().unwrap_or(activity.end_time);
+            if start_time < activity.start_time || end_time > activity.end_time {
+                activity.start_time = activity.start_time.min(start_time);
+                activity.end_time = activity.end_time.max(end_time);
+                changed.push(activity.id);
+            }
+        }
+        trace_change!(round_id = %round_id, changed = changed.len(), "aligned groups across rooms");
+        changed
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use polars::prelude::*;
+use crate::export::wire_name;
+use crate::types::Competition;
+
+// One row per attempt of every round result
+pub fn attempts_frame(competitions: &[Competition]) -> PolarsResult<DataFrame> {
+    let mut competition_id = vec![];
+    let mut event_id = vec![];
+    let mut round_number = vec![];
+    let mut person_id = vec![];
+    let mut wca_id = vec![];
+    let mut ranking = vec![];
+    let mut attempt_number = vec![];
+    let mut result = vec![];
+    for competition in competitions {
+        let wca_ids: HashMap<_, _> = competition.persons.iter()
+            .filter_map(|p| Some((p.registrant_id?, p.wca_id.as_ref()?.to_string())))
+            .collect();
+        for event in &competition.events {
+            for round in &event.rounds {
+                for round_result in &round.results {
+                    for (i, attempt) in round_result.attempts.iter().enumerate() {
+                        competition_id.push(competition.id.clone());
+                        event_id.push(event.id.to_string());
+                        round_number.push(round.id.round);
+                        person_id.push(round_result.person_id);
+                        wca_id.push(wca_ids.get(&round_result.person_id).cloned());
+                        ranking.push(round_result.ranking);
+                        attempt_number.push(i as u32 + 1);
+                        result.push(attempt.result.to_raw());
+                    }
+                }
+            }
+        }
+    }
+    df!(
+        "competition_id" => competition_id,
+        "event_id" => event_id,
+        "round_number" => round_number,
+        "person_id" => person_id,
+        "wca_id" => wca_id,
+        "ranking" => ranking,
+        "attempt_number" => attempt_number,
+        "result" => result,
+    )
+}
+
+// One row per person with a registration
+pub fn registrations_frame(competitions: &[Competition]) -> PolarsResult<DataFrame> {
+    let mut competition_id = vec![];
+    let mut person_id = vec![];
+    let mut wca_user_id = vec![];
+    let mut wca_id = vec![];
+    let mut name = vec![];
+    let mut country_iso2 = vec![];
+    let mut gender = vec![];
+    let mut status = vec![];
+    let mut is_competing = vec![];
+    let mut event_ids = vec![];
+    for competition in competitions {
+        for person in &competition.persons {
+            let Some(registration) = person.registration.as_ref() else {
+                continue;
+            };
+            competition_id.push(competition.id.clone());
+            person_id.push(person.registrant_id);
+            wca_user_id.push(person.wca_user_id);
+            wca_id.push(person.wca_id.as_ref().map(|id| id.to_string()));
+            name.push(person.name.clone());
+            country_iso2.push(person.country_iso2.clone());
+            gender.push(wire_name(&person.gender));
+            status.push(wire_name(&registration.status));
+            is_competing.push(registration.is_competing);
+            event_ids.push(registration.event_ids.iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join(","));
+        }
+    }
+    df!(
+        "competition_id" => competition_id,
+        "person_id" => person_id,
+        "wca_user_id" => wca_user_id,
+        "wca_id" => wca_id,
+        "name" => name,
+        "country_iso2" => country_iso2,
+        "gender" => gender,
+        "status" => status,
+        "is_competing" => is_competing,
+        "event_ids" => event_ids,
+    )
+}
+
+// One row per assignment of every person
+pub fn assignments_frame(competitions: &[Competition]) -> PolarsResult<DataFrame> {
+    let mut competition_id = vec![];
+    let mut person_id = vec![];
+    let mut activity_id = vec![];
+    let mut activity_code = vec![];
+    let mut assignment_code = vec![];
+    let mut station_number = vec![];
+    for competition in competitions {
+        let activity_codes: HashMap<_, _> = competition.schedule.all_activities().into_iter()
+            .map(|a| (a.id, a.activity_code.to_string()))
+            .collect();
+        for person in &competition.persons {
+            for assignment in &person.assignments {
+                competition_id.push(competition.id.clone());
+                person_id.push(person.registrant_id);
+                activity_id.push(assignment.activity_id);
+                activity_code.push(activity_codes.get(&assignment.activity_id).cloned());
+                assignment_code.push(assignment.assignment_code.to_string());
+                station_number.push(assignment.station_number);
+            }
+        }
+    }
+    df!(
+        "competition_id" => competition_id,
+        "person_id" => person_id,
+        "activity_id" => activity_id,
+        "activity_code" => activity_code,
+        "assignment_code" => assignment_code,
+        "station_number" => station_number,
+    )
+}
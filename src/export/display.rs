@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{Activity, ActivityId, AssignmentCode, Competition, DateTime, PersonId, RoomId};
+
+// Current and next activities of every room, for TV displays at the venue
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayFeed {
+    pub generated_at: DateTime,
+    pub rooms: Vec<RoomFeed>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomFeed {
+    pub room_id: RoomId,
+    pub name: String,
+    pub color: String,
+    pub current: Vec<FeedActivity>,
+    pub next: Option<FeedActivity>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedActivity {
+    pub activity_id: ActivityId,
+    pub name: String,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    // Only filled for current activities
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub competitors: Vec<FeedCompetitor>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedCompetitor {
+    pub registrant_id: Option<PersonId>,
+    pub name: String,
+    pub station_number: Option<u32>,
+}
+
+impl Competition {
+    fn feed_activity(&self, activity: &Activity, with_competitors: bool) -> FeedActivity {
+        let mut competitors: Vec<FeedCompetitor> = if with_competitors {
+            self.persons.iter()
+                .filter_map(|p| p.assignments.iter()
+                    .find(|a| a.activity_id == activity.id && a.assignment_code == AssignmentCode::Competitor)
+                    .map(|a| FeedCompetitor { registrant_id: p.registrant_id, name: p.name.clone(), station_number: a.station_number }))
+                .collect()
+        } else {
+            vec![]
+        };
+        competitors.sort_by(|a, b| (a.station_number.is_none(), a.station_number, &a.name).cmp(&(b.station_number.is_none(), b.station_number, &b.name)));
+        FeedActivity {
+            activity_id: activity.id,
+            name: activity.name.clone(),
+            start_time: activity.start_time,
+            end_time: activity.end_time,
+            competitors,
+        }
+    }
+
+    // Only the most specific activities (e.g. groups instead of their round) are considered
+    pub fn display_feed(&self, now: &DateTime) -> DisplayFeed {
+        let rooms = self.schedule.venues.iter()
+            .flat_map(|v| v.rooms.iter())
+            .map(|room| {
+                let mut leaves: Vec<&Activity> = room.activities.iter()
+                    .flat_map(|a| a.all_activities())
+                    .filter(|a| a.child_activities.is_empty())
+                    .collect();
+                leaves.sort_by_key(|a| (a.start_time, a.end_time, a.id));
+                RoomFeed {
+                    room_id: room.id,
+                    name: room.name.clone(),
+                    color: room.color.clone(),
+                    current: leaves.iter()
+                        .filter(|a| a.start_time <= *now && *now < a.end_time)
+                        .map(|a| self.feed_activity(a, true))
+                        .collect(),
+                    next: leaves.iter()
+                        .find(|a| a.start_time > *now)
+                        .map(|a| self.feed_activity(a, false)),
+                }
+            })
+            .collect();
+        DisplayFeed { generated_at: *now, rooms }
+    }
+}
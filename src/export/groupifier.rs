@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+use crate::groupifier::{ActivityConfig, ActivityConfigExtension, CompetitionConfig, CompetitionConfigExtension, CompetitorsSortingRule, RoomConfig, RoomConfigExtension};
+use crate::types::{Activity, ActivityCode, ActivityId, AssignmentCode, Competition, Extension, RoomId, StaffAssignment};
+
+pub use crate::groupifier::{ACTIVITY_CONFIG_SPEC_URL, COMPETITION_CONFIG_SPEC_URL, ROOM_CONFIG_SPEC_URL};
+
+fn is_round(activity: &Activity) -> bool {
+    matches!(&activity.activity_code, ActivityCode::Official(code) if code.round.is_some() && code.group.is_none() && code.attempt.is_none())
+}
+
+fn is_group(activity: &Activity) -> bool {
+    matches!(&activity.activity_code, ActivityCode::Official(code) if code.group.is_some() && code.attempt.is_none())
+}
+
+impl Competition {
+    // Copy of the competition with the extensions Groupifier (and Delegate Dashboard with that feature) need to show
+    // and edit the existing groups and assignments. Configs are derived from the group activities and assignments,
+    // extensions that are already present are kept as they are
+    pub fn to_groupifier(&self) -> Competition {
+        let mut competitors: HashMap<ActivityId, usize> = HashMap::new();
+        let mut max_station: HashMap<ActivityId, u32> = HashMap::new();
+        let mut staff: HashMap<(ActivityId, StaffAssignment), u32> = HashMap::new();
+        for assignment in self.persons.iter().flat_map(|p| p.assignments.iter()) {
+            match &assignment.assignment_code {
+                AssignmentCode::Competitor => *competitors.entry(assignment.activity_id).or_default() += 1,
+                AssignmentCode::Staff(task) => *staff.entry((assignment.activity_id, task.clone())).or_default() += 1,
+            }
+            if let Some(station_number) = assignment.station_number {
+                let max = max_station.entry(assignment.activity_id).or_default();
+                *max = (*max).max(station_number);
+            }
+        }
+        let per_group = |activity: &Activity, task: StaffAssignment| activity.child_activities.iter()
+            .filter(|a| is_group(a))
+            .map(|a| staff.get(&(a.id, task.clone())).copied().unwrap_or_default())
+            .max()
+            .unwrap_or_default();
+
+        // Stations of a room: the most competitors or the highest station number in any of its groups
+        let mut stations: HashMap<RoomId, u32> = HashMap::new();
+        for room in self.schedule.venues.iter().flat_map(|v| v.rooms.iter()) {
+            let needed = room.activities.iter()
+                .flat_map(|a| a.all_activities())
+                .filter(|a| is_group(a))
+                .map(|a| (competitors.get(&a.id).copied().unwrap_or_default() as u32).max(max_station.get(&a.id).copied().unwrap_or_default()))
+                .max()
+                .unwrap_or_default();
+            stations.insert(room.id, needed);
+        }
+        // Rooms a round takes place in, Groupifier splits competitors between them according to the capacity
+        let mut round_rooms: HashMap<ActivityCode, Vec<RoomId>> = HashMap::new();
+        for room in self.schedule.venues.iter().flat_map(|v| v.rooms.iter()) {
+            for activity in room.activities.iter().filter(|a| is_round(a)) {
+                round_rooms.entry(activity.activity_code.clone()).or_default().push(room.id);
+            }
+        }
+
+        let mut competition = self.clone();
+        for room in competition.schedule.venues.iter_mut().flat_map(|v| v.rooms.iter_mut()) {
+            let room_stations = stations[&room.id];
+            for activity in room.activities.iter_mut().filter(|a| is_round(a)) {
+                let groups = activity.child_activities.iter().filter(|a| is_group(a)).count() as u32;
+                if groups == 0 {
+                    continue;
+                }
+                let rooms = &round_rooms[&activity.activity_code];
+                let total_stations: u32 = rooms.iter().map(|r| stations[r]).sum();
+                let capacity = if total_stations == 0 {
+                    1.0 / rooms.len() as f32
+                } else {
+                    room_stations as f32 / total_stations as f32
+                };
+                if !activity.extensions.iter().any(|e| matches!(e, Extension::GroupifierActivityConfig(_))) {
+                    let config = ActivityConfig {
+                        capacity,
+                        groups,
+                        scramblers: per_group(activity, StaffAssignment::Scrambler),
+                        runners: per_group(activity, StaffAssignment::Runner),
+                        assign_judges: per_group(activity, StaffAssignment::Judge) > 0,
+                        featured_competitors_wca_user_ids: vec![],
+                    };
+                    activity.extensions.push(Extension::GroupifierActivityConfig(ActivityConfigExtension {
+                        id: Default::default(),
+                        spec_url: ACTIVITY_CONFIG_SPEC_URL.to_string(),
+                        data: config,
+                    }));
+                }
+                #[cfg(feature = "delegate_dashboard")]
+                if !activity.extensions.iter().any(|e| matches!(e, Extension::DelegateDashboardGroups(_))) {
+                    activity.extensions.push(Extension::DelegateDashboardGroups(crate::delegate_dashboard::GroupsExtension {
+                        id: Default::default(),
+                        spec_url: crate::delegate_dashboard::SPEC_URL.to_string(),
+                        data: crate::delegate_dashboard::GroupsConfig {
+                            groups,
+                            spread_groups_across_all_stages: Some(rooms.len() > 1),
+                        },
+                    }));
+                }
+            }
+            if room_stations > 0 && room.groupifier_config().is_none() {
+                room.extensions.push(Extension::GroupifierRoomConfig(RoomConfigExtension {
+                    id: Default::default(),
+                    spec_url: ROOM_CONFIG_SPEC_URL.to_string(),
+                    data: RoomConfig { stations: room_stations },
+                }));
+            }
+        }
+        if competition.groupifier_config().is_none() {
+            competition.extensions.push(Extension::GroupifierCompetitionConfig(CompetitionConfigExtension {
+                id: Default::default(),
+                spec_url: COMPETITION_CONFIG_SPEC_URL.to_string(),
+                data: CompetitionConfig {
+                    local_names_first: false,
+                    scorecards_background_url: None,
+                    competitors_sorting_rule: CompetitorsSortingRule::Ranks,
+                    no_tasks_for_newcomers: false,
+                    tasks_for_own_events_only: false,
+                    no_running_for_foreigners: None,
+                    print_stations: Some(!max_station.is_empty()),
+                    scorecard_paper_size: None,
+                    scorecard_order: None,
+                },
+            }));
+        }
+        competition
+    }
+
+    pub fn to_groupifier_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.to_groupifier())
+    }
+}
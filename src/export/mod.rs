@@ -0,0 +1,33 @@
+pub mod display;
+pub mod timeline;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+pub mod sql;
+#[cfg(all(feature = "groupifier", feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod groupifier;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod static_site;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod schedule_table;
+
+// The string a unit-like enum variant is serialized to in WCIF documents, e.g. "accepted" for RegistrationStatus::Accepted
+pub fn wire_name<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(v) => v.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+// Text in HTML and SVG elements and attribute values
+#[cfg(any(feature = "timeline_svg", all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type")))]
+pub(crate) fn escape_markup(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// Text in a cell of a Markdown table, which can't contain pipes or span lines
+#[cfg(any(feature = "incidents", all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type")))]
+pub(crate) fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
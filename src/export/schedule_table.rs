@@ -0,0 +1,193 @@
+use std::fmt::Write;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::export::{escape_markdown_cell, escape_markup};
+use crate::summary::{format_advancement, format_cutoff, format_name, format_time_limit};
+use crate::types::{Activity, ActivityCode, Competition, Date};
+
+// The schedule as posted on competition pages: a table per day and room with the top level activities. Times are
+// local to the venue
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleTable {
+    pub days: Vec<ScheduleTableDay>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleTableDay {
+    pub date: Date,
+    // Rooms without activities on the day are left out
+    pub rooms: Vec<RoomSchedule>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomSchedule {
+    pub venue: String,
+    pub room: String,
+    pub rows: Vec<ScheduleRow>,
+}
+
+// Round details are only set for rounds of official events
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleRow {
+    pub start_time: NaiveDateTime,
+    pub end_time: NaiveDateTime,
+    pub activity: String,
+    pub format: Option<String>,
+    pub time_limit: Option<String>,
+    pub cutoff: Option<String>,
+    pub proceed: Option<String>,
+}
+
+impl Competition {
+    fn schedule_row(&self, activity: &Activity, start_time: NaiveDateTime, end_time: NaiveDateTime) -> ScheduleRow {
+        let mut row = ScheduleRow { start_time, end_time, activity: activity.name.clone(), format: None, time_limit: None, cutoff: None, proceed: None };
+        let ActivityCode::Official(code) = &activity.activity_code else {
+            return row;
+        };
+        let Some(event) = self.events.iter().find(|e| e.id == code.event) else {
+            return row;
+        };
+        let Some(round) = code.round.and_then(|r| event.rounds.iter().find(|round| round.id.round == r)) else {
+            return row;
+        };
+        row.activity = if round.id.round as usize == event.rounds.len() {
+            format!("{}, Final", event.id.get_name())
+        } else {
+            format!("{}, Round {}", event.id.get_name(), round.id.round)
+        };
+        row.format = Some(format_name(&round.format).to_string());
+        row.time_limit = round.time_limit.as_ref().map(format_time_limit);
+        row.cutoff = round.cutoff.as_ref().map(|c| format_cutoff(&event.id, c));
+        row.proceed = round.advancement_condition.as_ref().map(|a| format_advancement(&event.id, &round.format, a));
+        row
+    }
+
+    pub fn schedule_table(&self) -> ScheduleTable {
+        let mut days: Vec<ScheduleTableDay> = vec![];
+        for venue in &self.schedule.venues {
+            for room in &venue.rooms {
+                for activity in &room.activities {
+                    let date = venue.local_date(&activity.start_time);
+                    let index = match days.iter().position(|d| d.date == date) {
+                        Some(index) => index,
+                        None => {
+                            days.push(ScheduleTableDay { date, rooms: vec![] });
+                            days.len() - 1
+                        },
+                    };
+                    let rooms = &mut days[index].rooms;
+                    let room_index = match rooms.iter().position(|r| r.venue == venue.name && r.room == room.name) {
+                        Some(index) => index,
+                        None => {
+                            rooms.push(RoomSchedule { venue: venue.name.clone(), room: room.name.clone(), rows: vec![] });
+                            rooms.len() - 1
+                        },
+                    };
+                    let row = self.schedule_row(activity, venue.local_time(&activity.start_time), venue.local_time(&activity.end_time));
+                    rooms[room_index].rows.push(row);
+                }
+            }
+        }
+        days.sort_by_key(|d| d.date);
+        for room in days.iter_mut().flat_map(|d| d.rooms.iter_mut()) {
+            room.rows.sort_by_key(|r| (r.start_time, r.end_time));
+        }
+        ScheduleTable { days }
+    }
+}
+
+const HEADERS: [&str; 6] = ["Time", "Activity", "Format", "Time limit", "Cutoff", "Proceed"];
+
+impl ScheduleRow {
+    fn cells(&self) -> [String; 6] {
+        [
+            format!("{} - {}", self.start_time.format("%H:%M"), self.end_time.format("%H:%M")),
+            self.activity.clone(),
+            self.format.clone().unwrap_or_default(),
+            self.time_limit.clone().unwrap_or_default(),
+            self.cutoff.clone().unwrap_or_default(),
+            self.proceed.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl ScheduleTable {
+    // Rooms are only named if a day has more than one
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        for day in &self.days {
+            let _ = writeln!(md, "## {}", day.date.format("%A, %B %-d, %Y"));
+            for room in &day.rooms {
+                let _ = writeln!(md);
+                if day.rooms.len() > 1 {
+                    let _ = writeln!(md, "### {}", room.room);
+                    let _ = writeln!(md);
+                }
+                let _ = writeln!(md, "| {} |", HEADERS.join(" | "));
+                let _ = writeln!(md, "|{}", "---|".repeat(HEADERS.len()));
+                for row in &room.rows {
+                    let cells = row.cells().map(|c| escape_markdown_cell(&c));
+                    let _ = writeln!(md, "| {} |", cells.join(" | "));
+                }
+            }
+            let _ = writeln!(md);
+        }
+        md
+    }
+
+    // One table with the date, venue and room as additional columns, with start and end in separate columns
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("Date,Venue,Room,Start,End,Activity,Format,Time limit,Cutoff,Proceed\n");
+        for day in &self.days {
+            for room in &day.rooms {
+                for row in &room.rows {
+                    let cells = [
+                        day.date.to_string(),
+                        room.venue.clone(),
+                        room.room.clone(),
+                        row.start_time.format("%H:%M").to_string(),
+                        row.end_time.format("%H:%M").to_string(),
+                    ];
+                    let [_, activity, format, time_limit, cutoff, proceed] = row.cells();
+                    let line: Vec<String> = cells.into_iter()
+                        .chain([activity, format, time_limit, cutoff, proceed])
+                        .map(|c| escape_csv(&c))
+                        .collect();
+                    let _ = writeln!(csv, "{}", line.join(","));
+                }
+            }
+        }
+        csv
+    }
+
+    // Tables only, to be embedded in a page
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        for day in &self.days {
+            let _ = writeln!(html, "<h2>{}</h2>", day.date.format("%A, %B %-d, %Y"));
+            for room in &day.rooms {
+                if day.rooms.len() > 1 {
+                    let _ = writeln!(html, "<h3>{}</h3>", escape_markup(&room.room));
+                }
+                let _ = writeln!(html, "<table>\n<tr>{}</tr>", HEADERS.iter().map(|h| format!("<th>{h}</th>")).collect::<String>());
+                for row in &room.rows {
+                    let _ = writeln!(html, "<tr>{}</tr>", row.cells().iter().map(|c| format!("<td>{}</td>", escape_markup(c))).collect::<String>());
+                }
+                let _ = writeln!(html, "</table>");
+            }
+        }
+        html
+    }
+}
+
+pub(crate) fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
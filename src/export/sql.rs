@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+use crate::export::wire_name;
+use crate::types::{Activity, AdvancementCondition, Competition, Date, DateTime, RoomId, VenueId};
+
+// Normalized rows of a competition, suitable for inserting into relational tables.
+// Every row carries the competition id, persons are keyed by their registrant id within the competition,
+// rounds by their round id (e.g. "333-r1") and activities by their activity id.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SqlRows {
+    pub competitions: Vec<CompetitionRow>,
+    pub persons: Vec<PersonRow>,
+    pub registrations: Vec<RegistrationRow>,
+    pub registration_events: Vec<RegistrationEventRow>,
+    pub rounds: Vec<RoundRow>,
+    pub results: Vec<ResultRow>,
+    pub attempts: Vec<AttemptRow>,
+    pub activities: Vec<ActivityRow>,
+    pub assignments: Vec<AssignmentRow>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct CompetitionRow {
+    pub id: String,
+    pub name: String,
+    pub short_name: String,
+    pub start_date: Date,
+    pub number_of_days: i32,
+    pub competitor_limit: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct PersonRow {
+    pub competition_id: String,
+    pub wca_user_id: i64,
+    pub registrant_id: Option<i64>,
+    pub wca_id: Option<String>,
+    pub name: String,
+    pub country_iso2: String,
+    pub gender: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct RegistrationRow {
+    pub competition_id: String,
+    pub registrant_id: i64,
+    pub wca_registration_id: i64,
+    pub status: String,
+    pub is_competing: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct RegistrationEventRow {
+    pub competition_id: String,
+    pub registrant_id: i64,
+    pub event_id: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct RoundRow {
+    pub competition_id: String,
+    pub round_id: String,
+    pub event_id: String,
+    pub round_number: i32,
+    pub format: String,
+    pub time_limit_centiseconds: Option<i64>,
+    pub cutoff_number_of_attempts: Option<i32>,
+    pub cutoff_attempt_result: Option<i64>,
+    pub advancement_type: Option<String>,
+    pub advancement_level: Option<i64>,
+    pub scramble_set_count: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct ResultRow {
+    pub competition_id: String,
+    pub round_id: String,
+    pub registrant_id: i64,
+    pub ranking: Option<i64>,
+    pub best: i64,
+    pub average: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct AttemptRow {
+    pub competition_id: String,
+    pub round_id: String,
+    pub registrant_id: i64,
+    pub attempt_number: i32,
+    pub result: i64,
+    pub reconstruction: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct ActivityRow {
+    pub competition_id: String,
+    pub activity_id: i64,
+    pub parent_activity_id: Option<i64>,
+    pub venue_id: i64,
+    pub room_id: i64,
+    pub name: String,
+    pub activity_code: String,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    pub scramble_set_id: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
+pub struct AssignmentRow {
+    pub competition_id: String,
+    pub registrant_id: i64,
+    pub activity_id: i64,
+    pub assignment_code: String,
+    pub station_number: Option<i32>,
+}
+
+impl Competition {
+    pub fn to_sql_rows(&self) -> SqlRows {
+        let mut rows = SqlRows::default();
+        rows.extend(self);
+        rows
+    }
+}
+
+impl SqlRows {
+    // Appends the rows of another competition, so multiple competitions can be stored in one batch
+    pub fn extend(&mut self, competition: &Competition) {
+        let competition_id = &competition.id;
+        self.competitions.push(CompetitionRow {
+            id: competition_id.clone(),
+            name: competition.name.clone(),
+            short_name: competition.short_name.clone(),
+            start_date: competition.schedule.start_date,
+            number_of_days: competition.schedule.number_of_days as i32,
+            competitor_limit: competition.competitor_limit.map(|x| x as i64),
+        });
+        for person in &competition.persons {
+            self.persons.push(PersonRow {
+                competition_id: competition_id.clone(),
+                wca_user_id: person.wca_user_id as i64,
+                registrant_id: person.registrant_id.map(|x| x as i64),
+                wca_id: person.wca_id.as_ref().map(|x| x.to_string()),
+                name: person.name.clone(),
+                country_iso2: person.country_iso2.clone(),
+                gender: wire_name(&person.gender),
+            });
+            let Some(registrant_id) = person.registrant_id.map(|x| x as i64) else {
+                continue;
+            };
+            if let Some(registration) = person.registration.as_ref() {
+                self.registrations.push(RegistrationRow {
+                    competition_id: competition_id.clone(),
+                    registrant_id,
+                    wca_registration_id: registration.wca_registration_id as i64,
+                    status: wire_name(&registration.status),
+                    is_competing: registration.is_competing,
+                });
+                for event_id in &registration.event_ids {
+                    self.registration_events.push(RegistrationEventRow {
+                        competition_id: competition_id.clone(),
+                        registrant_id,
+                        event_id: event_id.to_string(),
+                    });
+                }
+            }
+            for assignment in &person.assignments {
+                self.assignments.push(AssignmentRow {
+                    competition_id: competition_id.clone(),
+                    registrant_id,
+                    activity_id: assignment.activity_id as i64,
+                    assignment_code: assignment.assignment_code.to_string(),
+                    station_number: assignment.station_number.map(|x| x as i32),
+                });
+            }
+        }
+        for event in &competition.events {
+            for round in &event.rounds {
+                let round_id = round.id.to_string();
+                let (advancement_type, advancement_level) = match &round.advancement_condition {
+                    Some(AdvancementCondition::Ranking { level }) => (Some("ranking"), Some(*level as i64)),
+                    Some(AdvancementCondition::Percent { level }) => (Some("percent"), Some(*level as i64)),
+                    Some(AdvancementCondition::AttemptResult { level }) => (Some("attemptResult"), Some(level.to_raw())),
+                    None => (None, None),
+                };
+                self.rounds.push(RoundRow {
+                    competition_id: competition_id.clone(),
+                    round_id: round_id.clone(),
+                    event_id: event.id.to_string(),
+                    round_number: round.id.round as i32,
+                    format: wire_name(&round.format),
+                    time_limit_centiseconds: round.time_limit.as_ref().map(|t| t.centiseconds as i64),
+                    cutoff_number_of_attempts: round.cutoff.as_ref().map(|c| c.number_of_attempts as i32),
+                    cutoff_attempt_result: round.cutoff.as_ref().map(|c| c.attempt_result.to_raw()),
+                    advancement_type: advancement_type.map(|x| x.to_string()),
+                    advancement_level,
+                    scramble_set_count: round.scramble_set_count as i32,
+                });
+                for result in &round.results {
+                    self.results.push(ResultRow {
+                        competition_id: competition_id.clone(),
+                        round_id: round_id.clone(),
+                        registrant_id: result.person_id as i64,
+                        ranking: result.ranking.map(|x| x as i64),
+                        best: result.best.to_raw(),
+                        average: result.average.to_raw(),
+                    });
+                    for (i, attempt) in result.attempts.iter().enumerate() {
+                        self.attempts.push(AttemptRow {
+                            competition_id: competition_id.clone(),
+                            round_id: round_id.clone(),
+                            registrant_id: result.person_id as i64,
+                            attempt_number: i as i32 + 1,
+                            result: attempt.result.to_raw(),
+                            reconstruction: attempt.reconstruction.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        for venue in &competition.schedule.venues {
+            for room in &venue.rooms {
+                for activity in &room.activities {
+                    self.push_activity(competition_id, venue.id, room.id, None, activity);
+                }
+            }
+        }
+    }
+
+    fn push_activity(&mut self, competition_id: &str, venue_id: VenueId, room_id: RoomId, parent: Option<&Activity>, activity: &Activity) {
+        self.activities.push(ActivityRow {
+            competition_id: competition_id.to_string(),
+            activity_id: activity.id as i64,
+            parent_activity_id: parent.map(|p| p.id as i64),
+            venue_id: venue_id as i64,
+            room_id: room_id as i64,
+            name: activity.name.clone(),
+            activity_code: activity.activity_code.to_string(),
+            start_time: activity.start_time,
+            end_time: activity.end_time,
+            scramble_set_id: activity.scramble_set_id.map(|x| x as i64),
+        });
+        for child in &activity.child_activities {
+            self.push_activity(competition_id, venue_id, room_id, Some(activity), child);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::fmt::{Display, Formatter, Write};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use crate::export::escape_markup;
+use crate::results::format_result;
+use crate::summary::format_name;
+use crate::types::{AttemptResult, Competition, EventId, PersonId, ResultType, Round, RoundId};
+
+#[derive(Debug)]
+pub enum SiteError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl Display for SiteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for SiteError {}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteIndex {
+    pub competition_id: String,
+    pub name: String,
+    pub events: Vec<SiteEvent>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteEvent {
+    pub event_id: EventId,
+    pub name: String,
+    pub rounds: Vec<SiteRoundEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteRoundEntry {
+    pub round_id: RoundId,
+    // Relative to the index
+    pub path: String,
+    pub competitors: usize,
+    // Competitors with at least one attempt
+    pub entered: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteRound {
+    pub round_id: RoundId,
+    pub name: String,
+    pub format: String,
+    pub results: Vec<SiteResult>,
+}
+
+// A result with its values formatted as on the WCA website, ordered by ranking
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteResult {
+    pub ranking: Option<u64>,
+    pub person_id: PersonId,
+    pub name: String,
+    pub country_iso2: String,
+    pub attempts: Vec<String>,
+    pub best: String,
+    pub average: String,
+    pub advancing: bool,
+}
+
+impl Competition {
+    pub fn site_index(&self) -> SiteIndex {
+        SiteIndex {
+            competition_id: self.id.clone(),
+            name: self.name.clone(),
+            events: self.events.iter()
+                .map(|event| SiteEvent {
+                    event_id: event.id.clone(),
+                    name: event.id.get_name().to_string(),
+                    rounds: event.rounds.iter()
+                        .map(|round| SiteRoundEntry {
+                            round_id: round.id.clone(),
+                            path: format!("rounds/{}.json", round.id),
+                            competitors: round.results.len(),
+                            entered: round.results.iter().filter(|r| r.attempts.iter().any(|a| a.result != AttemptResult::Skipped)).count(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn site_round(&self, round: &Round) -> SiteRound {
+        let event_id = &round.id.event;
+        let advancing = round.advancing_persons().unwrap_or_default();
+        let mut results: Vec<SiteResult> = round.results.iter()
+            .map(|result| {
+                let person = self.find_person(result.person_id);
+                SiteResult {
+                    ranking: result.ranking,
+                    person_id: result.person_id,
+                    name: person.map(|p| p.name.clone()).unwrap_or_default(),
+                    country_iso2: person.map(|p| p.country_iso2.clone()).unwrap_or_default(),
+                    attempts: result.attempts.iter().map(|a| format_result(event_id, &ResultType::Single, &a.result)).collect(),
+                    best: format_result(event_id, &ResultType::Single, &result.best),
+                    average: format_result(event_id, &ResultType::Average, &result.average),
+                    advancing: advancing.contains(&result.person_id),
+                }
+            })
+            .collect();
+        results.sort_by(|a, b| a.ranking.is_none().cmp(&b.ranking.is_none())
+            .then(a.ranking.cmp(&b.ranking))
+            .then_with(|| a.name.cmp(&b.name)));
+        SiteRound {
+            round_id: round.id.clone(),
+            name: format!("{} Round {}", event_id.get_name(), round.id.round),
+            format: format_name(&round.format).to_string(),
+            results,
+        }
+    }
+
+    // Writes live results as a static site for hosting when the connection of the venue is unreliable: index.json and
+    // index.html, and a JSON and HTML file per round in rounds/. Files whose content didn't change are not written
+    // again, so the site can be regenerated after every entered result and synced cheaply. Returns the written files
+    pub fn write_static_site(&self, dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, SiteError> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir.join("rounds")).map_err(SiteError::Io)?;
+        let mut written = vec![];
+        let index = self.site_index();
+        write_if_changed(&dir.join("index.json"), &serde_json::to_vec_pretty(&index).map_err(SiteError::Json)?, &mut written)?;
+        write_if_changed(&dir.join("index.html"), index_html(&index).as_bytes(), &mut written)?;
+        for round in self.events.iter().flat_map(|e| e.rounds.iter()) {
+            let site_round = self.site_round(round);
+            let path = dir.join("rounds").join(round.id.to_string());
+            write_if_changed(&path.with_extension("json"), &serde_json::to_vec_pretty(&site_round).map_err(SiteError::Json)?, &mut written)?;
+            write_if_changed(&path.with_extension("html"), round_html(&index.name, &site_round).as_bytes(), &mut written)?;
+        }
+        Ok(written)
+    }
+}
+
+fn write_if_changed(path: &Path, content: &[u8], written: &mut Vec<PathBuf>) -> Result<(), SiteError> {
+    if std::fs::read(path).is_ok_and(|existing| existing == content) {
+        return Ok(());
+    }
+    std::fs::write(path, content).map_err(SiteError::Io)?;
+    written.push(path.to_path_buf());
+    Ok(())
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n</head>\n<body>\n{body}</body>\n</html>\n", escape_markup(title))
+}
+
+fn index_html(index: &SiteIndex) -> String {
+    let mut body = String::new();
+    let _ = writeln!(body, "<h1>{}</h1>", escape_markup(&index.name));
+    for event in &index.events {
+        let _ = writeln!(body, "<h2>{}</h2>\n<ul>", escape_markup(&event.name));
+        for round in &event.rounds {
+            let _ = writeln!(body, "<li><a href=\"rounds/{}.html\">Round {}</a> ({}/{})</li>", round.round_id, round.round_id.round, round.entered, round.competitors);
+        }
+        let _ = writeln!(body, "</ul>");
+    }
+    page(&index.name, &body)
+}
+
+fn round_html(competition_name: &str, round: &SiteRound) -> String {
+    let mut body = String::new();
+    let _ = writeln!(body, "<p><a href=\"../index.html\">{}</a></p>", escape_markup(competition_name));
+    let _ = writeln!(body, "<h1>{}</h1>\n<p>{}</p>", escape_markup(&round.name), round.format);
+    let _ = writeln!(body, "<table>\n<tr><th>#</th><th>Name</th><th>Country</th><th>Attempts</th><th>Best</th><th>Average</th></tr>");
+    for result in &round.results {
+        let _ = writeln!(body, "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            if result.advancing { " class=\"advancing\"" } else { "" },
+            result.ranking.map(|r| r.to_string()).unwrap_or_default(),
+            escape_markup(&result.name),
+            escape_markup(&result.country_iso2),
+            escape_markup(&result.attempts.join(" ")),
+            escape_markup(&result.best),
+            escape_markup(&result.average),
+        );
+    }
+    let _ = writeln!(body, "</table>");
+    page(&format!("{} - {}", round.name, competition_name), &body)
+}
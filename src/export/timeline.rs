@@ -0,0 +1,155 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::types::{Activity, ActivityId, Competition, Date, RoomId, Venue};
+
+// The schedule as rows of rooms per day, for rendering timelines and Gantt charts. Times are local to the venue
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timeline {
+    pub days: Vec<TimelineDay>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineDay {
+    pub date: Date,
+    // Earliest start and latest end of all activities of the day
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    // Every room of every venue, also those without activities on the day
+    pub rows: Vec<TimelineRow>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineRow {
+    pub venue: String,
+    pub room_id: RoomId,
+    pub name: String,
+    pub color: String,
+    pub bars: Vec<TimelineBar>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineBar {
+    pub activity_id: ActivityId,
+    pub name: String,
+    pub activity_code: String,
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    // Groups of a round, in the order of their start
+    pub children: Vec<TimelineBar>,
+}
+
+impl TimelineBar {
+    fn from_activity(venue: &Venue, activity: &Activity) -> Self {
+        let mut children: Vec<TimelineBar> = activity.child_activities.iter().map(|a| Self::from_activity(venue, a)).collect();
+        children.sort_by_key(|b| (b.start, b.end));
+        TimelineBar {
+            activity_id: activity.id,
+            name: activity.name.clone(),
+            activity_code: activity.activity_code.to_string(),
+            start: venue.local_time(&activity.start_time),
+            end: venue.local_time(&activity.end_time),
+            children,
+        }
+    }
+}
+
+impl Competition {
+    pub fn timeline(&self) -> Timeline {
+        let mut dates: Vec<Date> = self.schedule.venues.iter()
+            .flat_map(|v| v.rooms.iter().flat_map(move |r| r.activities.iter().map(move |a| v.local_date(&a.start_time))))
+            .collect();
+        dates.sort();
+        dates.dedup();
+        let days = dates.into_iter()
+            .map(|date| {
+                let rows: Vec<TimelineRow> = self.schedule.venues.iter()
+                    .flat_map(|venue| venue.rooms.iter().map(move |room| {
+                        let mut bars: Vec<TimelineBar> = room.activities.iter()
+                            .filter(|a| venue.local_date(&a.start_time) == date)
+                            .map(|a| TimelineBar::from_activity(venue, a))
+                            .collect();
+                        bars.sort_by_key(|b| (b.start, b.end));
+                        TimelineRow { venue: venue.name.clone(), room_id: room.id, name: room.name.clone(), color: room.color.clone(), bars }
+                    }))
+                    .collect();
+                let bars = || rows.iter().flat_map(|r| r.bars.iter());
+                let fallback = date.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+                TimelineDay {
+                    date,
+                    start: bars().map(|b| b.start).min().unwrap_or(fallback),
+                    end: bars().map(|b| b.end).max().unwrap_or(fallback),
+                    rows,
+                }
+            })
+            .collect();
+        Timeline { days }
+    }
+}
+
+#[cfg(feature = "timeline_svg")]
+const LABEL_WIDTH: i64 = 160;
+#[cfg(feature = "timeline_svg")]
+const ROW_HEIGHT: i64 = 48;
+#[cfg(feature = "timeline_svg")]
+const HEADER_HEIGHT: i64 = 40;
+#[cfg(feature = "timeline_svg")]
+const PIXELS_PER_MINUTE: i64 = 2;
+
+#[cfg(feature = "timeline_svg")]
+impl Timeline {
+    // Renders all days below each other, with a bar per activity in the color of its room. Groups are drawn as
+    // smaller bars inside the bar of their round
+    pub fn to_svg(&self) -> String {
+        use std::fmt::Write;
+        use crate::export::escape_markup;
+        let width = self.days.iter()
+            .map(|d| LABEL_WIDTH + (d.end - day_start(d)).num_minutes() * PIXELS_PER_MINUTE + 40)
+            .max()
+            .unwrap_or(LABEL_WIDTH);
+        let height: i64 = self.days.iter().map(|d| HEADER_HEIGHT + d.rows.len() as i64 * ROW_HEIGHT).sum();
+        let mut svg = String::new();
+        let _ = writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" font-family=\"sans-serif\" font-size=\"12\">");
+        let mut y = 0;
+        for day in &self.days {
+            let start = day_start(day);
+            let x_of = |time: NaiveDateTime| LABEL_WIDTH + (time - start).num_minutes() * PIXELS_PER_MINUTE;
+            let _ = writeln!(svg, "<text x=\"4\" y=\"{}\" font-weight=\"bold\">{}</text>", y + 16, day.date.format("%A, %B %-d, %Y"));
+            let mut hour = start;
+            while hour <= day.end {
+                let x = x_of(hour);
+                let _ = writeln!(svg, "<line x1=\"{x}\" y1=\"{}\" x2=\"{x}\" y2=\"{}\" stroke=\"#ddd\"/>", y + HEADER_HEIGHT - 8, y + HEADER_HEIGHT + day.rows.len() as i64 * ROW_HEIGHT);
+                let _ = writeln!(svg, "<text x=\"{x}\" y=\"{}\" fill=\"#666\">{}</text>", y + HEADER_HEIGHT - 12, hour.format("%H:%M"));
+                hour += chrono::TimeDelta::hours(1);
+            }
+            y += HEADER_HEIGHT;
+            for row in &day.rows {
+                let _ = writeln!(svg, "<text x=\"4\" y=\"{}\">{}</text>", y + ROW_HEIGHT / 2 + 4, escape_markup(&row.name));
+                for bar in &row.bars {
+                    let _ = writeln!(svg, "<g><title>{}</title>", escape_markup(&bar.name));
+                    let _ = writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"0.35\" stroke=\"{}\"/>",
+                        x_of(bar.start), y + 2, (x_of(bar.end) - x_of(bar.start)).max(1), ROW_HEIGHT - 4, escape_markup(&row.color), escape_markup(&row.color));
+                    let _ = writeln!(svg, "<text x=\"{}\" y=\"{}\">{}</text>", x_of(bar.start) + 3, y + 15, escape_markup(&bar.activity_code));
+                    for child in &bar.children {
+                        let _ = writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#fff\"><title>{}</title></rect>",
+                            x_of(child.start), y + ROW_HEIGHT / 2, (x_of(child.end) - x_of(child.start)).max(1), ROW_HEIGHT / 2 - 4, escape_markup(&row.color), escape_markup(&child.name));
+                    }
+                    let _ = writeln!(svg, "</g>");
+                }
+                y += ROW_HEIGHT;
+            }
+        }
+        let _ = writeln!(svg, "</svg>");
+        svg
+    }
+}
+
+// The full hour before the first activity of the day
+#[cfg(feature = "timeline_svg")]
+fn day_start(day: &TimelineDay) -> NaiveDateTime {
+    use chrono::Timelike;
+    day.start.with_minute(0).and_then(|t| t.with_second(0)).and_then(|t| t.with_nanosecond(0)).unwrap_or(day.start)
+}
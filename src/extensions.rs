@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::types::{Activity, Competition, Event, Extension, Person, Room, Round, Venue};
+
+// An extension defined by this crate or another tool, available whether or not the feature that parses it is enabled
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KnownExtension {
+    pub id: &'static str,
+    pub spec_url: &'static str,
+    pub tool: &'static str,
+}
+
+// Generates the ids and spec urls of known extensions as constants, which the modules parsing them refer to, and
+// `KNOWN_EXTENSIONS` from the same values. Extensions of this crate are named "wcif-rs.<Name>", with their schema
+// in the extensions directory of the repository
+macro_rules! known_extensions {
+    (
+        tools { $($id:ident, $spec_url:ident: $id_value:literal, $spec_url_value:literal, $tool:literal;)* }
+        wcif_rs { $($crate_id:ident, $crate_spec_url:ident: $name:literal;)* }
+    ) => {
+        $(
+            pub const $id: &str = $id_value;
+            pub const $spec_url: &str = $spec_url_value;
+        )*
+        $(
+            pub const $crate_id: &str = concat!("wcif-rs.", $name);
+            pub const $crate_spec_url: &str = concat!("https://github.com/Jobarion/wcif/blob/master/extensions/", $name, ".json");
+        )*
+
+        pub const KNOWN_EXTENSIONS: [KnownExtension; [$($id_value,)* $($name,)*].len()] = [
+            $(KnownExtension { id: $id, spec_url: $spec_url, tool: $tool },)*
+            $(KnownExtension { id: $crate_id, spec_url: $crate_spec_url, tool: "wcif-rs" },)*
+        ];
+    };
+}
+
+known_extensions! {
+    tools {
+        GROUPIFIER_COMPETITION_CONFIG_ID, GROUPIFIER_COMPETITION_CONFIG_SPEC_URL: "groupifier.CompetitionConfig", "https://groupifier.jonatanklosko.com/wcif-extensions/CompetitionConfig.json", "Groupifier";
+        GROUPIFIER_ACTIVITY_CONFIG_ID, GROUPIFIER_ACTIVITY_CONFIG_SPEC_URL: "groupifier.ActivityConfig", "https://groupifier.jonatanklosko.com/wcif-extensions/ActivityConfig.json", "Groupifier";
+        GROUPIFIER_ROOM_CONFIG_ID, GROUPIFIER_ROOM_CONFIG_SPEC_URL: "groupifier.RoomConfig", "https://groupifier.jonatanklosko.com/wcif-extensions/RoomConfig.json", "Groupifier";
+        DELEGATE_DASHBOARD_GROUPS_ID, DELEGATE_DASHBOARD_GROUPS_SPEC_URL: "undefined.groups", "https://github.com/coder13/delegateDashboard/blob/main/public/wcif-extensions/groups.json", "Delegate Dashboard";
+    }
+    wcif_rs {
+        ENCRYPTED_SCRAMBLE_SETS_ID, ENCRYPTED_SCRAMBLE_SETS_SPEC_URL: "EncryptedScrambleSets";
+        CHECK_IN_ID, CHECK_IN_SPEC_URL: "CheckIn";
+        ID_VERIFICATION_ID, ID_VERIFICATION_SPEC_URL: "IdVerification";
+        PAYMENTS_ID, PAYMENTS_SPEC_URL: "Payments";
+        RESULT_VERIFICATION_ID, RESULT_VERIFICATION_SPEC_URL: "ResultVerification";
+        ATTEMPT_PROVENANCE_ID, ATTEMPT_PROVENANCE_SPEC_URL: "AttemptProvenance";
+        EXTRA_ATTEMPTS_ID, EXTRA_ATTEMPTS_SPEC_URL: "ExtraAttempts";
+        PENALTIES_ID, PENALTIES_SPEC_URL: "Penalties";
+        ATTEMPT_CLOCKS_ID, ATTEMPT_CLOCKS_SPEC_URL: "AttemptClocks";
+        RESULTS_SIGNATURE_ID, RESULTS_SIGNATURE_SPEC_URL: "ResultsSignature";
+        WAITING_LIST_ID, WAITING_LIST_SPEC_URL: "WaitingList";
+        INCIDENT_LOG_ID, INCIDENT_LOG_SPEC_URL: "IncidentLog";
+        UNOFFICIAL_EVENTS_ID, UNOFFICIAL_EVENTS_SPEC_URL: "UnofficialEvents";
+        UNOFFICIAL_RESULTS_ID, UNOFFICIAL_RESULTS_SPEC_URL: "UnofficialResults";
+        TEAMS_ID, TEAMS_SPEC_URL: "Teams";
+    }
+}
+
+pub fn find_known_extension(id: &str) -> Option<&'static KnownExtension> {
+    KNOWN_EXTENSIONS.iter().find(|e| e.id == id)
+}
+
+// Extensions with the same id and spec url found in a document, with the tool that wrote them
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionUsage {
+    pub id: String,
+    pub spec_url: String,
+    // The tool of a known extension, otherwise the id up to the first dot
+    pub tool: String,
+    pub known: bool,
+    pub version: Option<String>,
+    // Number of places (competition, persons, events, rounds, venues, rooms and activities) the extension is found in
+    pub count: usize,
+}
+
+fn is_version(s: &str) -> bool {
+    let s = s.strip_prefix(['v', 'V']).unwrap_or(s);
+    !s.is_empty() && s.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+// Version encoded in a spec url, as a "version" or "v" query parameter, a path segment ("/v2/", "/1.0.0/") or a
+// suffix of the file name ("Config-1.2.json", "Config@v2.json")
+pub fn spec_url_version(spec_url: &str) -> Option<String> {
+    let (path, query) = spec_url.split_once('?').unwrap_or((spec_url, ""));
+    if let Some((_, version)) = query.split('&')
+        .filter_map(|p| p.split_once('='))
+        .find(|(key, value)| (*key == "version" || *key == "v") && !value.is_empty()) {
+        return Some(version.to_string());
+    }
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest.split_once('/').map_or("", |(_, path)| path));
+    let mut segments: Vec<&str> = path.split('/').collect();
+    let file_name = segments.pop().unwrap_or_default();
+    let stem = file_name.strip_suffix(".json").unwrap_or(file_name);
+    stem.rsplit(['-', '_', '@']).next()
+        .filter(|suffix| *suffix != stem && is_version(suffix))
+        .or_else(|| segments.into_iter().rev().find(|s| is_version(s)))
+        .map(|v| v.strip_prefix(['v', 'V']).unwrap_or(v).to_string())
+}
+
+impl Extension {
+    pub fn id(&self) -> &str {
+        match self {
+            #[cfg(feature = "groupifier")]
+            Extension::GroupifierCompetitionConfig(_) => GROUPIFIER_COMPETITION_CONFIG_ID,
+            #[cfg(feature = "groupifier")]
+            Extension::GroupifierActivityConfig(_) => GROUPIFIER_ACTIVITY_CONFIG_ID,
+            #[cfg(feature = "groupifier")]
+            Extension::GroupifierRoomConfig(_) => GROUPIFIER_ROOM_CONFIG_ID,
+            #[cfg(feature = "delegate_dashboard")]
+            Extension::DelegateDashboardGroups(_) => DELEGATE_DASHBOARD_GROUPS_ID,
+            #[cfg(feature = "scramble_encryption")]
+            Extension::EncryptedScrambleSets(_) => ENCRYPTED_SCRAMBLE_SETS_ID,
+            #[cfg(feature = "check_in")]
+            Extension::CheckIn(_) => CHECK_IN_ID,
+            #[cfg(feature = "id_verification")]
+            Extension::IdVerification(_) => ID_VERIFICATION_ID,
+            #[cfg(feature = "payments")]
+            Extension::Payments(_) => PAYMENTS_ID,
+            #[cfg(feature = "result_verification")]
+            Extension::ResultVerification(_) => RESULT_VERIFICATION_ID,
+            #[cfg(feature = "attempt_provenance")]
+            Extension::AttemptProvenance(_) => ATTEMPT_PROVENANCE_ID,
+            #[cfg(feature = "extra_attempts")]
+            Extension::ExtraAttempts(_) => EXTRA_ATTEMPTS_ID,
+            #[cfg(feature = "penalties")]
+            Extension::Penalties(_) => PENALTIES_ID,
+            #[cfg(feature = "replication")]
+            Extension::AttemptClocks(_) => ATTEMPT_CLOCKS_ID,
+            #[cfg(feature = "signing")]
+            Extension::ResultsSignature(_) => RESULTS_SIGNATURE_ID,
+            #[cfg(feature = "waiting_list")]
+            Extension::WaitingList(_) => WAITING_LIST_ID,
+            #[cfg(feature = "incidents")]
+            Extension::IncidentLog(_) => INCIDENT_LOG_ID,
+            #[cfg(feature = "unofficial_events")]
+            Extension::UnofficialEvents(_) => UNOFFICIAL_EVENTS_ID,
+            #[cfg(feature = "unofficial_events")]
+            Extension::UnofficialResults(_) => UNOFFICIAL_RESULTS_ID,
+            #[cfg(feature = "teams")]
+            Extension::Teams(_) => TEAMS_ID,
+            Extension::Unknown(x) => &x.id,
+        }
+    }
+}
+
+// Id and spec url of any extension, whether parsed by this crate or not
+fn id_and_spec_url(extension: &Extension) -> Option<(String, String)> {
+    let value = serde_json::to_value(extension).ok()?;
+    Some((value.get("id")?.as_str()?.to_string(), value.get("specUrl")?.as_str()?.to_string()))
+}
+
+impl Competition {
+    fn all_extensions(&self) -> Vec<&Extension> {
+        let mut extensions: Vec<&Extension> = self.extensions.iter().collect();
+        extensions.extend(self.persons.iter().flat_map(|p| p.extensions.iter()));
+        for event in &self.events {
+            extensions.extend(event.extensions.iter());
+            extensions.extend(event.rounds.iter().flat_map(|r| r.extensions.iter()));
+        }
+        for venue in &self.schedule.venues {
+            extensions.extend(venue.extensions.iter());
+            for room in &venue.rooms {
+                extensions.extend(room.extensions.iter());
+                extensions.extend(room.activities.iter().flat_map(Activity::all_activities).flat_map(|a| a.extensions.iter()));
+            }
+        }
+        extensions
+    }
+}
+
+// Which tools wrote extensions into the document, and in which versions as far as their spec urls tell
+pub fn detect_extension_versions(competition: &Competition) -> Vec<ExtensionUsage> {
+    let mut usages: BTreeMap<(String, String), ExtensionUsage> = BTreeMap::new();
+    for (id, spec_url) in competition.all_extensions().into_iter().filter_map(id_and_spec_url) {
+        usages.entry((id.clone(), spec_url.clone()))
+            .or_insert_with(|| {
+                let known = find_known_extension(&id);
+                ExtensionUsage {
+                    tool: known.map_or_else(|| id.split('.').next().unwrap_or_default().to_string(), |k| k.tool.to_string()),
+                    known: known.is_some(),
+                    version: spec_url_version(&spec_url),
+                    id,
+                    spec_url,
+                    count: 0,
+                }
+            })
+            .count += 1;
+    }
+    usages.into_values().collect()
+}
+
+// Data of an extension parsed by this crate, stored in its variant of `Extension`
+pub trait ExtensionData: Sized {
+    fn from_extension(extension: &Extension) -> Option<&Self>;
+    fn from_extension_mut(extension: &mut Extension) -> Option<&mut Self>;
+    fn into_extension(self) -> Extension;
+}
+
+// Uniform access to the extensions of the competition and the entities in it, e.g. `round.ext::<ExtraAttempts>()`
+pub trait HasExtensions {
+    fn extensions(&self) -> &Vec<Extension>;
+    fn extensions_mut(&mut self) -> &mut Vec<Extension>;
+
+    fn ext<T: ExtensionData>(&self) -> Option<&T> {
+        self.extensions().iter().find_map(T::from_extension)
+    }
+
+    fn ext_mut<T: ExtensionData>(&mut self) -> Option<&mut T> {
+        self.extensions_mut().iter_mut().find_map(T::from_extension_mut)
+    }
+
+    // Replaces an extension of the same type
+    fn push_ext<T: ExtensionData>(&mut self, data: T) {
+        self.extensions_mut().retain(|e| T::from_extension(e).is_none());
+        self.extensions_mut().push(data.into_extension());
+    }
+}
+
+macro_rules! has_extensions {
+    ($($entity:ty),*) => {
+        $(impl HasExtensions for $entity {
+            fn extensions(&self) -> &Vec<Extension> {
+                &self.extensions
+            }
+
+            fn extensions_mut(&mut self) -> &mut Vec<Extension> {
+                &mut self.extensions
+            }
+        })*
+    };
+}
+
+has_extensions!(Competition, Person, Event, Round, Venue, Room, Activity);
+
+macro_rules! extension_data {
+    ($feature:literal, $data:ty, $variant:ident, $wrapper:path, $spec_url:expr) => {
+        #[cfg(feature = $feature)]
+        impl ExtensionData for $data {
+            fn from_extension(extension: &Extension) -> Option<&Self> {
+                match extension {
+                    Extension::$variant(x) => Some(&x.data),
+                    _ => None,
+                }
+            }
+
+            fn from_extension_mut(extension: &mut Extension) -> Option<&mut Self> {
+                match extension {
+                    Extension::$variant(x) => Some(&mut x.data),
+                    _ => None,
+                }
+            }
+
+            fn into_extension(self) -> Extension {
+                use $wrapper as Wrapper;
+                Extension::$variant(Wrapper { id: Default::default(), spec_url: $spec_url, data: self })
+            }
+        }
+    };
+}
+
+extension_data!("groupifier", crate::groupifier::CompetitionConfig, GroupifierCompetitionConfig, crate::groupifier::CompetitionConfigExtension, crate::groupifier::COMPETITION_CONFIG_SPEC_URL.to_string());
+extension_data!("groupifier", crate::groupifier::ActivityConfig, GroupifierActivityConfig, crate::groupifier::ActivityConfigExtension, crate::groupifier::ACTIVITY_CONFIG_SPEC_URL.to_string());
+extension_data!("groupifier", crate::groupifier::RoomConfig, GroupifierRoomConfig, crate::groupifier::RoomConfigExtension, crate::groupifier::ROOM_CONFIG_SPEC_URL.to_string());
+extension_data!("delegate_dashboard", crate::delegate_dashboard::GroupsConfig, DelegateDashboardGroups, crate::delegate_dashboard::GroupsExtension, crate::delegate_dashboard::SPEC_URL.to_string());
+extension_data!("scramble_encryption", crate::scramble_encryption::EncryptedScrambleSets, EncryptedScrambleSets, crate::scramble_encryption::EncryptedScrambleSetsExtension, crate::scramble_encryption::SPEC_URL.to_string());
+extension_data!("check_in", crate::check_in::CheckIn, CheckIn, crate::check_in::CheckInExtension, crate::check_in::SPEC_URL.to_string());
+extension_data!("id_verification", crate::id_verification::IdVerification, IdVerification, crate::id_verification::IdVerificationExtension, crate::id_verification::SPEC_URL.to_string());
+extension_data!("payments", crate::payments::Payments, Payments, crate::payments::PaymentsExtension, crate::payments::SPEC_URL.to_string());
+extension_data!("result_verification", crate::verification::ResultVerification, ResultVerification, crate::verification::ResultVerificationExtension, crate::verification::SPEC_URL.to_string());
+extension_data!("attempt_provenance", crate::provenance::AttemptProvenanceLog, AttemptProvenance, crate::provenance::AttemptProvenanceExtension, crate::provenance::SPEC_URL.to_string());
+extension_data!("extra_attempts", crate::extra_attempts::ExtraAttempts, ExtraAttempts, crate::extra_attempts::ExtraAttemptsExtension, crate::extra_attempts::SPEC_URL.to_string());
+extension_data!("penalties", crate::penalties::PenaltyLog, Penalties, crate::penalties::PenaltiesExtension, crate::penalties::SPEC_URL.to_string());
+extension_data!("replication", crate::replication::AttemptClocks, AttemptClocks, crate::replication::AttemptClocksExtension, crate::replication::SPEC_URL.to_string());
+extension_data!("signing", crate::signing::ResultsSignature, ResultsSignature, crate::signing::ResultsSignatureExtension, crate::signing::SPEC_URL.to_string());
+extension_data!("waiting_list", crate::waiting_list::WaitingList, WaitingList, crate::waiting_list::WaitingListExtension, crate::waiting_list::SPEC_URL.to_string());
+extension_data!("incidents", crate::incidents::IncidentLog, IncidentLog, crate::incidents::IncidentLogExtension, crate::incidents::SPEC_URL.to_string());
+extension_data!("unofficial_events", crate::unofficial::UnofficialEvents, UnofficialEvents, crate::unofficial::UnofficialEventsExtension, crate::unofficial::EVENTS_SPEC_URL.to_string());
+extension_data!("unofficial_events", crate::unofficial::UnofficialResults, UnofficialResults, crate::unofficial::UnofficialResultsExtension, crate::unofficial::RESULTS_SPEC_URL.to_string());
+extension_data!("teams", crate::teams::Teams, Teams, crate::teams::TeamsExtension, crate::teams::SPEC_URL.to_string());
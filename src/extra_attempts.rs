@@ -0,0 +1,133 @@
+use std::fmt::{Display, Formatter};
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::types::{Attempt, AttemptResult, DateTime, Extension, PersonId, Round};
+
+pub const EXTENSION_ID: &str = crate::extensions::EXTRA_ATTEMPTS_ID;
+pub const SPEC_URL: &str = crate::extensions::EXTRA_ATTEMPTS_SPEC_URL;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtraAttemptError {
+    NoResult { person_id: PersonId },
+    // Only attempts that were already entered can be replaced
+    InvalidAttempt { person_id: PersonId, attempt_index: usize },
+    NotGranted { person_id: PersonId, attempt_index: usize },
+}
+
+impl Display for ExtraAttemptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoResult { person_id } => write!(f, "Person {person_id} has no result in this round"),
+            Self::InvalidAttempt { person_id, attempt_index } => write!(f, "Person {person_id} has no attempt {}", attempt_index + 1),
+            Self::NotGranted { person_id, attempt_index } => write!(f, "Person {person_id} was not granted an extra attempt for attempt {}", attempt_index + 1),
+        }
+    }
+}
+
+impl std::error::Error for ExtraAttemptError {}
+
+// The attempts of a result only hold the final values, so the attempts that were replaced by extra attempts are
+// kept on the round
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraAttemptsExtension {
+    pub id: MustBe!("wcif-rs.ExtraAttempts"),
+    pub spec_url: String,
+    pub data: ExtraAttempts,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraAttempts {
+    // In the order they were granted, an extra attempt can be replaced again
+    pub replaced: Vec<ReplacedAttempt>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplacedAttempt {
+    pub person_id: PersonId,
+    // Zero based
+    pub attempt_index: usize,
+    pub original: Attempt,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
+    pub granted_at: DateTime,
+    pub granted_by: Option<PersonId>,
+    // The incident that led to the extra attempt, see the incidents feature
+    pub incident_id: Option<u32>,
+}
+
+impl Round {
+    pub fn extra_attempts(&self) -> Option<&ExtraAttempts> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::ExtraAttempts(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    fn extra_attempts_mut(&mut self) -> &mut ExtraAttempts {
+        if self.extra_attempts().is_none() {
+            self.extensions.push(Extension::ExtraAttempts(ExtraAttemptsExtension {
+                id: Default::default(),
+                spec_url: SPEC_URL.to_string(),
+                data: ExtraAttempts::default(),
+            }));
+        }
+        self.extensions.iter_mut().find_map(|e| match e {
+            Extension::ExtraAttempts(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("extra attempts extension was just added")
+    }
+
+    // Replaces an attempt by an extra attempt that is still to be done. The attempt is skipped until the extra
+    // attempt is entered, so it counts towards neither best nor average in the meantime
+    pub fn grant_extra_attempt(&mut self, person_id: PersonId, attempt_index: usize, now: DateTime, granted_by: Option<PersonId>, incident_id: Option<u32>) -> Result<(), ExtraAttemptError> {
+        let result = self.results.iter_mut()
+            .find(|r| r.person_id == person_id)
+            .ok_or(ExtraAttemptError::NoResult { person_id })?;
+        let attempt = result.attempts.get_mut(attempt_index)
+            .filter(|a| a.result != AttemptResult::Skipped)
+            .ok_or(ExtraAttemptError::InvalidAttempt { person_id, attempt_index })?;
+        let original = std::mem::replace(attempt, Attempt { result: AttemptResult::Skipped, reconstruction: None });
+        self.extra_attempts_mut().replaced.push(ReplacedAttempt { person_id, attempt_index, original, granted_at: now, granted_by, incident_id });
+        trace_change!(person_id, attempt_index, "granted extra attempt");
+        self.update_results();
+        Ok(())
+    }
+
+    // Enters the result of a granted extra attempt in place of the replaced attempt
+    pub fn enter_extra_attempt(&mut self, person_id: PersonId, attempt_index: usize, attempt: Attempt) -> Result<(), ExtraAttemptError> {
+        if !self.pending_extra_attempts().contains(&(person_id, attempt_index)) {
+            return Err(ExtraAttemptError::NotGranted { person_id, attempt_index });
+        }
+        let result = self.results.iter_mut()
+            .find(|r| r.person_id == person_id)
+            .ok_or(ExtraAttemptError::NoResult { person_id })?;
+        result.attempts[attempt_index] = attempt;
+        self.update_results();
+        Ok(())
+    }
+
+    // Granted extra attempts without a result yet, as person and attempt index
+    pub fn pending_extra_attempts(&self) -> Vec<(PersonId, usize)> {
+        let mut pending: Vec<(PersonId, usize)> = self.extra_attempts().iter()
+            .flat_map(|e| e.replaced.iter())
+            .map(|r| (r.person_id, r.attempt_index))
+            .filter(|(person_id, index)| self.results.iter()
+                .find(|r| r.person_id == *person_id)
+                .and_then(|r| r.attempts.get(*index))
+                .is_some_and(|a| a.result == AttemptResult::Skipped))
+            .collect();
+        pending.sort();
+        pending.dedup();
+        pending
+    }
+
+    // The attempts an attempt replaced, oldest first
+    pub fn replaced_attempts(&self, person_id: PersonId, attempt_index: usize) -> Vec<&ReplacedAttempt> {
+        self.extra_attempts().iter()
+            .flat_map(|e| e.replaced.iter())
+            .filter(|r| r.person_id == person_id && r.attempt_index == attempt_index)
+            .collect()
+    }
+}
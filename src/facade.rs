@@ -0,0 +1,139 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use crate::compliance::{parse, ParseError, ParseReport, Profile};
+use crate::diff::Change;
+use crate::serialize::SerializeOptions;
+use crate::types::Competition;
+
+// One-call operations for command line tools, returning reports that can be printed as they are
+
+#[derive(Debug)]
+pub enum FacadeError {
+    Io { path: PathBuf, error: std::io::Error },
+    Parse { path: PathBuf, error: ParseError },
+    Json(serde_json::Error),
+}
+
+impl Display for FacadeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "{}: {error}", path.display()),
+            Self::Parse { path, error } => write!(f, "{}: {error}", path.display()),
+            Self::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for FacadeError {}
+
+fn read(path: &Path) -> Result<String, FacadeError> {
+    let io_error = |error| FacadeError::Io { path: path.to_path_buf(), error };
+    let bytes = std::fs::read(path).map_err(io_error)?;
+    #[cfg(feature = "compression")]
+    let bytes = crate::compression::decompress(&bytes).map_err(io_error)?;
+    String::from_utf8(bytes).map_err(|e| io_error(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+}
+
+fn load(path: &Path, profile: Profile) -> Result<(Competition, ParseReport), FacadeError> {
+    parse(&read(path)?, profile).map_err(|error| FacadeError::Parse { path: path.to_path_buf(), error })
+}
+
+// Reads a document leniently, compressed ones too with the compression feature
+pub fn load_file(path: impl AsRef<Path>) -> Result<Competition, FacadeError> {
+    load(path.as_ref(), Profile::Lenient).map(|(competition, _)| competition)
+}
+
+// Deviations from the specification and validation errors of a document
+pub fn validate_and_report(path: impl AsRef<Path>) -> Result<ParseReport, FacadeError> {
+    load(path.as_ref(), Profile::Lenient).map(|(_, report)| report)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffReport {
+    pub changes: Vec<Change>,
+}
+
+impl Display for DiffReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "No differences");
+        }
+        let lines: Vec<String> = self.changes.iter()
+            .map(|change| match change {
+                Change::Added { path, value } => format!("+ {path}: {value}"),
+                Change::Removed { path, value } => format!("- {path}: {value}"),
+                Change::Changed { path, old, new } => format!("~ {path}: {old} -> {new}"),
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub fn diff_files(old: impl AsRef<Path>, new: impl AsRef<Path>) -> Result<DiffReport, FacadeError> {
+    let changes = load_file(old)?.diff(&load_file(new)?).map_err(FacadeError::Json)?;
+    Ok(DiffReport { changes })
+}
+
+// Writes the public version of a document, as served by the WCA website, and returns it. Private fields, private
+// extensions and scramble sets are left out
+pub fn strip_private(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<String, FacadeError> {
+    let json = load_file(input)?.to_json_with(&SerializeOptions::public()).map_err(FacadeError::Json)?;
+    std::fs::write(output.as_ref(), &json).map_err(|error| FacadeError::Io { path: output.as_ref().to_path_buf(), error })?;
+    Ok(json)
+}
+
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CsvExport {
+    // One row per activity, see `export::schedule_table`
+    Schedule,
+    // One row per result of every round, with formatted attempts
+    Results,
+}
+
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+fn results_csv(competition: &Competition) -> String {
+    use crate::export::schedule_table::escape_csv;
+    use crate::results::format_result;
+    use crate::types::ResultType;
+    let max_attempts = competition.events.iter()
+        .flat_map(|e| e.rounds.iter())
+        .map(|r| r.format.expected_solve_count() as usize)
+        .max()
+        .unwrap_or_default();
+    let mut csv = String::from("Round,Ranking,Person,Name");
+    for i in 1..=max_attempts {
+        csv.push_str(&format!(",Attempt {i}"));
+    }
+    csv.push_str(",Best,Average\n");
+    for event in &competition.events {
+        for round in &event.rounds {
+            for result in &round.results {
+                let mut row = vec![
+                    round.id.to_string(),
+                    result.ranking.map(|r| r.to_string()).unwrap_or_default(),
+                    result.person_id.to_string(),
+                    competition.find_person(result.person_id).map(|p| p.name.clone()).unwrap_or_default(),
+                ];
+                row.extend((0..max_attempts).map(|i| result.attempts.get(i)
+                    .map(|a| format_result(&event.id, &ResultType::Single, &a.result))
+                    .unwrap_or_default()));
+                row.push(format_result(&event.id, &ResultType::Single, &result.best));
+                row.push(format_result(&event.id, &ResultType::Average, &result.average));
+                let row: Vec<String> = row.iter().map(|c| escape_csv(c)).collect();
+                csv.push_str(&row.join(","));
+                csv.push('\n');
+            }
+        }
+    }
+    csv
+}
+
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub fn export_csv(path: impl AsRef<Path>, export: CsvExport) -> Result<String, FacadeError> {
+    let competition = load_file(path)?;
+    Ok(match export {
+        CsvExport::Schedule => competition.schedule_table().to_csv(),
+        CsvExport::Results => results_csv(&competition),
+    })
+}
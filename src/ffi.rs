@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use crate::types::Competition;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WcifStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    ParseError = 3,
+    SerializeError = 4,
+    Panic = 5,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn fail(status: WcifStatus, message: impl ToString) -> WcifStatus {
+    let message = CString::new(message.to_string().replace('\0', "")).ok();
+    LAST_ERROR.with(|e| *e.borrow_mut() = message);
+    status
+}
+
+// Runs the body of an entry point. Clears the last error first, so it only ever describes the latest call, and
+// turns panics into an error status as unwinding into the caller is undefined behavior
+fn guard(f: impl FnOnce() -> WcifStatus) -> WcifStatus {
+    LAST_ERROR.with(|e| *e.borrow_mut() = None);
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        let message = panic.downcast_ref::<&str>().map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        fail(WcifStatus::Panic, format!("panic: {message}"))
+    })
+}
+
+fn write_string(value: String, out: *mut *mut c_char) -> WcifStatus {
+    match CString::new(value) {
+        Ok(s) => {
+            unsafe { *out = s.into_raw() };
+            WcifStatus::Ok
+        },
+        Err(e) => fail(WcifStatus::SerializeError, e),
+    }
+}
+
+/// Returns the message of the error of the last call on this thread, or null if it succeeded.
+/// The pointer stays valid until the next call into this library on the same thread.
+#[no_mangle]
+pub extern "C" fn wcif_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map(|s| s.as_ptr()).unwrap_or(ptr::null()))
+}
+
+/// Parses a WCIF document and stores a handle to it in `out`.
+///
+/// # Safety
+/// `json` must be a valid null terminated string and `out` a valid pointer.
+/// The handle must be released with `wcif_competition_free`.
+#[no_mangle]
+pub unsafe extern "C" fn wcif_competition_parse(json: *const c_char, out: *mut *mut Competition) -> WcifStatus {
+    guard(|| {
+        if json.is_null() || out.is_null() {
+            return fail(WcifStatus::NullPointer, "null pointer argument");
+        }
+        let json = match CStr::from_ptr(json).to_str() {
+            Ok(s) => s,
+            Err(e) => return fail(WcifStatus::InvalidUtf8, e),
+        };
+        match serde_json::from_str::<Competition>(json) {
+            Ok(competition) => {
+                *out = Box::into_raw(Box::new(competition));
+                WcifStatus::Ok
+            },
+            Err(e) => fail(WcifStatus::ParseError, e),
+        }
+    })
+}
+
+/// Serializes the competition to a JSON string stored in `out`.
+///
+/// # Safety
+/// `competition` must be a handle returned by `wcif_competition_parse` and `out` a valid pointer.
+/// The string must be released with `wcif_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn wcif_competition_serialize(competition: *const Competition, out: *mut *mut c_char) -> WcifStatus {
+    guard(|| {
+        if competition.is_null() || out.is_null() {
+            return fail(WcifStatus::NullPointer, "null pointer argument");
+        }
+        match serde_json::to_string(&*competition) {
+            Ok(json) => write_string(json, out),
+            Err(e) => fail(WcifStatus::SerializeError, e),
+        }
+    })
+}
+
+/// Validates the competition and stores a JSON array of error messages in `out`.
+///
+/// # Safety
+/// `competition` must be a handle returned by `wcif_competition_parse` and `out` a valid pointer.
+/// The string must be released with `wcif_string_free`.
+#[no_mangle]
+pub unsafe extern "C" fn wcif_competition_validate(competition: *const Competition, out: *mut *mut c_char) -> WcifStatus {
+    guard(|| {
+        if competition.is_null() || out.is_null() {
+            return fail(WcifStatus::NullPointer, "null pointer argument");
+        }
+        let errors: Vec<String> = (*competition).validate().iter()
+            .map(|e| e.to_string())
+            .collect();
+        match serde_json::to_string(&errors) {
+            Ok(json) => write_string(json, out),
+            Err(e) => fail(WcifStatus::SerializeError, e),
+        }
+    })
+}
+
+/// # Safety
+/// `competition` must be null or a handle returned by `wcif_competition_parse` that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wcif_competition_free(competition: *mut Competition) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if !competition.is_null() {
+            drop(Box::from_raw(competition));
+        }
+    }));
+}
+
+/// # Safety
+/// `s` must be null or a string returned by this library that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wcif_string_free(s: *mut c_char) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        if !s.is_null() {
+            drop(CString::from_raw(s));
+        }
+    }));
+}
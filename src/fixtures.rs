@@ -0,0 +1,97 @@
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use flate2::read::GzDecoder;
+use crate::types::Competition;
+
+// Synthetic, gzip compressed documents for testing code built on this crate. They are not exports of real
+// competitions: they were generated and then shaped after the documents the listed tools write, with placeholder
+// names, WCA ids, user ids, emails and birthdates. They include the private fields, so they parse with and without
+// the private_properties feature
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Fixture {
+    pub name: &'static str,
+    pub description: &'static str,
+    // The tool whose output the document is modelled on
+    pub modelled_on: &'static str,
+    pub persons: usize,
+    pub compressed: &'static [u8],
+}
+
+pub const FIXTURES: [Fixture; 4] = [
+    Fixture {
+        name: "website-registration",
+        description: "Small competition during registration, three events without groups, assignments or results",
+        modelled_on: "WCA website",
+        persons: 24,
+        compressed: include_bytes!("../fixtures/synthetic/website-registration.json.gz"),
+    },
+    Fixture {
+        name: "groupifier",
+        description: "Medium competition with groups, staff and stations by seed, including Groupifier's competition, room and activity configs",
+        modelled_on: "Groupifier",
+        persons: 80,
+        compressed: include_bytes!("../fixtures/synthetic/groupifier.json.gz"),
+    },
+    Fixture {
+        name: "delegate-dashboard",
+        description: "Medium competition with two rooms, groups and sequential stations, including Delegate Dashboard's groups extension on round activities",
+        modelled_on: "Delegate Dashboard",
+        persons: 50,
+        compressed: include_bytes!("../fixtures/synthetic/delegate-dashboard.json.gz"),
+    },
+    Fixture {
+        name: "championship-results",
+        description: "Large three day competition with 13 events in three rooms, results and scramble sets for every round",
+        modelled_on: "WCA Live",
+        persons: 220,
+        compressed: include_bytes!("../fixtures/synthetic/championship-results.json.gz"),
+    },
+];
+
+#[derive(Debug)]
+pub enum FixtureError {
+    Unknown(String),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl Display for FixtureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unknown(name) => write!(f, "No fixture named {name}"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for FixtureError {}
+
+pub fn find(name: &str) -> Option<&'static Fixture> {
+    FIXTURES.iter().find(|f| f.name == name)
+}
+
+pub fn names() -> impl Iterator<Item = &'static str> {
+    FIXTURES.iter().map(|f| f.name)
+}
+
+impl Fixture {
+    pub fn json(&self) -> Result<String, FixtureError> {
+        let mut json = String::new();
+        GzDecoder::new(self.compressed).read_to_string(&mut json).map_err(FixtureError::Io)?;
+        Ok(json)
+    }
+
+    pub fn load(&self) -> Result<Competition, FixtureError> {
+        serde_json::from_str(&self.json()?).map_err(FixtureError::Json)
+    }
+}
+
+// The raw document of a fixture, e.g. for testing parsers or the compliance checks
+pub fn load_json(name: &str) -> Result<String, FixtureError> {
+    find(name).ok_or_else(|| FixtureError::Unknown(name.to_string()))?.json()
+}
+
+pub fn load(name: &str) -> Result<Competition, FixtureError> {
+    find(name).ok_or_else(|| FixtureError::Unknown(name.to_string()))?.load()
+}
@@ -0,0 +1,117 @@
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+use crate::types::{Activity, ActivityCode, ActivityId, AssignmentCode, AttemptIdType, Competition, EventId, GroupIdType, PersonId, RoomId, RoundId, Scramble, ScrambleSetId};
+
+// Everything needed to print the scramble sheets of one fewest moves attempt in one room
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FmcHandout {
+    pub round_id: RoundId,
+    pub room_id: RoomId,
+    pub activity_id: ActivityId,
+    pub group: Option<GroupIdType>,
+    pub attempt: AttemptIdType,
+    pub scramble_set_id: ScrambleSetId,
+    pub scramble: Scramble,
+    pub competitors: Vec<PersonId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FmcHandoutError {
+    MissingScrambleSet { activity_id: ActivityId },
+    UnknownScrambleSet { activity_id: ActivityId, scramble_set_id: ScrambleSetId },
+    MissingScramble { scramble_set_id: ScrambleSetId, attempt: AttemptIdType },
+    UnknownRound { activity_id: ActivityId },
+}
+
+impl Display for FmcHandoutError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingScrambleSet { activity_id } => write!(f, "Activity {activity_id} has no scramble set"),
+            Self::UnknownScrambleSet { activity_id, scramble_set_id } => write!(f, "Activity {activity_id} references scramble set {scramble_set_id} which is not part of its round"),
+            Self::MissingScramble { scramble_set_id, attempt } => write!(f, "Scramble set {scramble_set_id} has no scramble for attempt {attempt}"),
+            Self::UnknownRound { activity_id } => write!(f, "Activity {activity_id} belongs to a round that does not exist"),
+        }
+    }
+}
+
+impl Competition {
+    // Creates one handout per fewest moves attempt and room. Attempt activities (e.g. 333fm-r1-a2) use the
+    // scramble of their attempt, round or group activities without attempt produce a handout for every attempt.
+    // Scramble sets are inherited from parent activities and must belong to the round of the activity.
+    pub fn fmc_handouts(&self) -> Result<Vec<FmcHandout>, Vec<FmcHandoutError>> {
+        let mut handouts = vec![];
+        let mut errors = vec![];
+        for venue in &self.schedule.venues {
+            for room in &venue.rooms {
+                for activity in &room.activities {
+                    self.collect_fmc_handouts(room.id, activity, &[], None, &mut handouts, &mut errors);
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(handouts)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn collect_fmc_handouts<'a>(&'a self, room_id: RoomId, activity: &'a Activity, ancestors: &[&'a Activity], scramble_set_id: Option<ScrambleSetId>, handouts: &mut Vec<FmcHandout>, errors: &mut Vec<FmcHandoutError>) {
+        let ActivityCode::Official(code) = &activity.activity_code else {
+            return;
+        };
+        if code.event != EventId::FewestMoves333 {
+            return;
+        }
+        let scramble_set_id = activity.scramble_set_id.or(scramble_set_id);
+        if !activity.child_activities.is_empty() {
+            let mut ancestors = ancestors.to_vec();
+            ancestors.push(activity);
+            for child in &activity.child_activities {
+                self.collect_fmc_handouts(room_id, child, &ancestors, scramble_set_id, handouts, errors);
+            }
+            return;
+        }
+        let Some(round) = code.round.and_then(|r| self.events.iter()
+            .filter(|e| e.id == code.event)
+            .flat_map(|e| e.rounds.iter())
+            .find(|round| round.id.round == r)) else {
+            errors.push(FmcHandoutError::UnknownRound { activity_id: activity.id });
+            return;
+        };
+        let Some(scramble_set_id) = scramble_set_id else {
+            errors.push(FmcHandoutError::MissingScrambleSet { activity_id: activity.id });
+            return;
+        };
+        let Some(scramble_set) = round.scramble_sets.iter().find(|s| s.id == scramble_set_id) else {
+            errors.push(FmcHandoutError::UnknownScrambleSet { activity_id: activity.id, scramble_set_id });
+            return;
+        };
+        let mut related: Vec<ActivityId> = ancestors.iter().map(|a| a.id).collect();
+        related.push(activity.id);
+        let competitors: Vec<PersonId> = self.persons.iter()
+            .filter(|p| p.assignments.iter().any(|a| a.assignment_code == AssignmentCode::Competitor && related.contains(&a.activity_id)))
+            .filter_map(|p| p.registrant_id)
+            .collect();
+        let attempts = match code.attempt {
+            Some(attempt) => vec![attempt],
+            None => (1..=round.format.expected_solve_count()).collect(),
+        };
+        for attempt in attempts {
+            let Some(scramble) = (attempt as usize).checked_sub(1).and_then(|i| scramble_set.scrambles.get(i)) else {
+                errors.push(FmcHandoutError::MissingScramble { scramble_set_id, attempt });
+                continue;
+            };
+            handouts.push(FmcHandout {
+                round_id: round.id.clone(),
+                room_id,
+                activity_id: activity.id,
+                group: code.group,
+                attempt,
+                scramble_set_id,
+                scramble: scramble.clone(),
+                competitors: competitors.clone(),
+            });
+        }
+    }
+}
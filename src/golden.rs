@@ -0,0 +1,119 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use serde_json::Value;
+use crate::diff::{diff_values, Change};
+use crate::serialize::SerializeOptions;
+use crate::types::Competition;
+
+// Golden files store the pretty printed output of a competition, so changes to it show up in version control and
+// in the output of tools serializing competitions after a crate upgrade
+
+#[derive(Debug)]
+pub enum GoldenError {
+    Io { path: PathBuf, error: std::io::Error },
+    Json(serde_json::Error),
+}
+
+impl Display for GoldenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "{}: {error}", path.display()),
+            Self::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for GoldenError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GoldenReport {
+    pub path: PathBuf,
+    // The golden file did not exist and was created
+    pub written: bool,
+    pub bytes_equal: bool,
+    // From the golden file to the current output
+    pub changes: Vec<Change>,
+}
+
+impl GoldenReport {
+    // Whether the output is byte for byte the same. Changes of formatting or field order without structural
+    // changes don't match either
+    pub fn is_match(&self) -> bool {
+        self.bytes_equal
+    }
+}
+
+impl Display for GoldenReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let path = self.path.display();
+        if self.written {
+            return write!(f, "Wrote golden file {path}");
+        }
+        if self.bytes_equal {
+            return write!(f, "Output matches golden file {path}");
+        }
+        if self.changes.is_empty() {
+            return write!(f, "Output differs from golden file {path} in formatting or field order only");
+        }
+        write!(f, "Output differs from golden file {path}:")?;
+        for change in &self.changes {
+            match change {
+                Change::Added { path, value } => write!(f, "\nadded {path}: {value}")?,
+                Change::Removed { path, value } => write!(f, "\nremoved {path}: {value}")?,
+                Change::Changed { path, old, new } => write!(f, "\nchanged {path}: {old} -> {new}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Serialized directly from the competition, so fields keep the order of the types instead of being sorted
+fn golden_output(competition: &Competition, options: &SerializeOptions) -> Result<String, GoldenError> {
+    let mut output = serde_json::to_string_pretty(&competition.view_with(options)).map_err(GoldenError::Json)?;
+    output.push('\n');
+    Ok(output)
+}
+
+fn write_golden(path: &Path, output: &str) -> Result<(), GoldenError> {
+    let io_error = |error| GoldenError::Io { path: path.to_path_buf(), error };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    std::fs::write(path, output).map_err(io_error)
+}
+
+// Compares the serialized competition with the golden file, creating the file if it doesn't exist
+pub fn check_golden(competition: &Competition, path: impl AsRef<Path>, options: &SerializeOptions) -> Result<GoldenReport, GoldenError> {
+    let path = path.as_ref();
+    let output = golden_output(competition, options)?;
+    let golden = match std::fs::read_to_string(path) {
+        Ok(golden) => golden,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            write_golden(path, &output)?;
+            return Ok(GoldenReport { path: path.to_path_buf(), written: true, bytes_equal: true, changes: vec![] });
+        },
+        Err(error) => return Err(GoldenError::Io { path: path.to_path_buf(), error }),
+    };
+    let changes = if golden == output {
+        vec![]
+    } else {
+        let golden: Value = serde_json::from_str(&golden).map_err(GoldenError::Json)?;
+        let output: Value = serde_json::from_str(&output).map_err(GoldenError::Json)?;
+        diff_values(&golden, &output)
+    };
+    Ok(GoldenReport { path: path.to_path_buf(), written: false, bytes_equal: golden == output, changes })
+}
+
+// Overwrites the golden file with the current output, after a change was reviewed
+pub fn update_golden(competition: &Competition, path: impl AsRef<Path>, options: &SerializeOptions) -> Result<(), GoldenError> {
+    write_golden(path.as_ref(), &golden_output(competition, options)?)
+}
+
+// For tests, panics with the differences if the output doesn't match the golden file
+pub fn assert_golden(competition: &Competition, path: impl AsRef<Path>, options: &SerializeOptions) {
+    match check_golden(competition, path, options) {
+        Ok(report) if report.is_match() => {},
+        Ok(report) => panic!("{report}"),
+        Err(e) => panic!("{e}"),
+    }
+}
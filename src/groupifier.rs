@@ -4,6 +4,10 @@ use serde_with::serde_as;
 use serde_with::NoneAsEmptyString;
 use crate::types::WCAUserId;
 
+pub const ACTIVITY_CONFIG_SPEC_URL: &str = crate::extensions::GROUPIFIER_ACTIVITY_CONFIG_SPEC_URL;
+pub const ROOM_CONFIG_SPEC_URL: &str = crate::extensions::GROUPIFIER_ROOM_CONFIG_SPEC_URL;
+pub const COMPETITION_CONFIG_SPEC_URL: &str = crate::extensions::GROUPIFIER_COMPETITION_CONFIG_SPEC_URL;
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ActivityConfigExtension {
@@ -86,3 +90,29 @@ pub struct RoomConfigExtension {
 pub struct RoomConfig {
     pub stations: u32,
 }
+
+impl ScorecardPaperSize {
+    pub fn scorecards_per_page(&self) -> usize {
+        match self {
+            Self::A4 | Self::Letter => 4,
+            Self::A6 => 1,
+        }
+    }
+}
+
+// Arranges scorecards in the order they are printed, page by page. Empty slots on the last page(s) are None.
+// Natural fills each page before moving to the next one. Stacked places the scorecards such that cutting all
+// pages into quarters and putting the stacks of each quarter on top of each other yields the original order
+pub fn scorecard_print_order<'a, T>(scorecards: &'a [T], paper_size: &ScorecardPaperSize, order: &ScorecardOrder) -> Vec<Option<&'a T>> {
+    let per_page = paper_size.scorecards_per_page();
+    let pages = scorecards.len().div_ceil(per_page);
+    (0..pages * per_page)
+        .map(|slot| {
+            let index = match order {
+                ScorecardOrder::Natural => slot,
+                ScorecardOrder::Stacked => (slot % per_page) * pages + slot / per_page,
+            };
+            scorecards.get(index)
+        })
+        .collect()
+}
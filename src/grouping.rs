@@ -0,0 +1,385 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use crate::groupifier::{ActivityConfig, CompetitionConfig, CompetitorsSortingRule, RoomConfig};
+use crate::types::{Activity, ActivityCode, ActivityId, Assignment, AssignmentCode, Competition, DateTime, EventId, Extension, GroupIdType, Person, PersonId, RegistrationStatus, Room, RoomId, RoundId, StaffAssignment, WCAUserId};
+use crate::wca_id::transliterate;
+
+fn name_key(person: &Person) -> String {
+    transliterate(person.parsed_name().latin()).to_lowercase()
+}
+
+fn by_seed(event_id: &EventId) -> impl Fn(&&Person, &&Person) -> Ordering + '_ {
+    move |a, b| a.seed(event_id).cmp(&b.seed(event_id)).then_with(|| name_key(a).cmp(&name_key(b)))
+}
+
+impl CompetitorsSortingRule {
+    // Orders the competitors of an event the way Groupifier does before splitting them into groups,
+    // from the best seed to the worst. Groups are filled from the end, so the best competitors end up in the last group.
+    // - Ranks: by personal best in the event, competitors without one are ordered by name
+    // - Balanced: by personal best in the event, competitors without one are ordered by their 3x3x3 Cube personal best,
+    //   so newcomers to an event are spread according to their general speed
+    // - Symmetric: by 3x3x3 Cube personal best in every event, so competitors end up in the same group number everywhere
+    // - NameOptimised: by name, so competitors can easily find themselves in printed group lists
+    pub fn sort(&self, event_id: &EventId, competitors: &mut [&Person]) {
+        match self {
+            Self::Ranks => competitors.sort_by(by_seed(event_id)),
+            Self::Balanced => competitors.sort_by(|a, b| {
+                let seed = |p: &Person| match p.seed(event_id) {
+                    (u64::MAX, u64::MAX) => (1, p.seed(&EventId::Cube333)),
+                    seed => (0, seed),
+                };
+                seed(a).cmp(&seed(b)).then_with(|| name_key(a).cmp(&name_key(b)))
+            }),
+            Self::Symmetric => competitors.sort_by(by_seed(&EventId::Cube333)),
+            Self::NameOptimised => competitors.sort_by(|a, b| name_key(a).cmp(&name_key(b)).then_with(|| a.registrant_id.cmp(&b.registrant_id))),
+        }
+    }
+}
+
+impl Competition {
+    // Competitors of a round: persons with results if there are any, otherwise accepted registrations for
+    // first rounds and the persons advancing from the previous round for subsequent rounds
+    pub fn round_competitors(&self, round_id: &RoundId) -> Vec<&Person> {
+        let Some(round) = self.find_round(round_id) else {
+            return vec![];
+        };
+        if !round.results.is_empty() {
+            return round.results.iter()
+                .filter_map(|r| self.find_person(r.person_id))
+                .collect();
+        }
+        if round_id.round <= 1 {
+            return self.persons.iter()
+                .filter(|p| p.registration.as_ref().is_some_and(|r| {
+                    r.status == RegistrationStatus::Accepted && r.is_competing && r.event_ids.contains(&round_id.event)
+                }))
+                .collect();
+        }
+        let previous = RoundId { event: round_id.event.clone(), round: round_id.round - 1 };
+        let Some(advancing) = self.find_round(&previous).and_then(|r| r.advancing_persons()) else {
+            return vec![];
+        };
+        self.persons.iter()
+            .filter(|p| p.registrant_id.is_some_and(|id| advancing.contains(&id)))
+            .collect()
+    }
+
+    pub fn sorted_round_competitors(&self, round_id: &RoundId, rule: &CompetitorsSortingRule) -> Vec<&Person> {
+        let mut competitors = self.round_competitors(round_id);
+        rule.sort(&round_id.event, &mut competitors);
+        competitors
+    }
+}
+
+// Decides which groups featured competitors (e.g. well known cubers streamed on a stage) are placed in.
+// Groups are indexed in the order they take place
+pub trait FeaturedCompetitorsPolicy {
+    // Returns the group index of each featured competitor, in the order of `featured`
+    fn place(&self, featured: &[&Person], group_count: usize) -> Vec<usize>;
+}
+
+// Spreads featured competitors evenly across groups, starting with the last one.
+// If `later_groups` is set, only the second half of the groups is used
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpreadFeatured {
+    pub later_groups: bool,
+}
+
+impl FeaturedCompetitorsPolicy for SpreadFeatured {
+    fn place(&self, featured: &[&Person], group_count: usize) -> Vec<usize> {
+        let first = if self.later_groups { group_count / 2 } else { 0 };
+        let available = group_count - first;
+        (0..featured.len())
+            .map(|i| group_count - 1 - i % available)
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GroupingError {
+    UnknownRound { round_id: RoundId },
+    NoGroupActivities { round_id: RoundId },
+    // Competitors who are unavailable during every group of the round
+    Unavailable { round_id: RoundId, person_ids: Vec<PersonId> },
+}
+
+impl Display for GroupingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownRound { round_id } => write!(f, "Round {round_id} does not exist"),
+            Self::NoGroupActivities { round_id } => write!(f, "Round {round_id} has no group activities"),
+            Self::Unavailable { round_id, person_ids } => write!(f, "Persons {person_ids:?} are unavailable during every group of round {round_id}"),
+        }
+    }
+}
+
+// Times during which persons can neither compete nor staff, e.g. because they arrive late
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Availability {
+    pub unavailable: HashMap<PersonId, Vec<(DateTime, DateTime)>>,
+}
+
+impl Availability {
+    pub fn add_unavailable(&mut self, person_id: PersonId, start_time: DateTime, end_time: DateTime) {
+        self.unavailable.entry(person_id).or_default().push((start_time, end_time));
+    }
+
+    pub fn is_available(&self, person_id: PersonId, start_time: DateTime, end_time: DateTime) -> bool {
+        self.unavailable.get(&person_id)
+            .is_none_or(|windows| windows.iter().all(|(start, end)| *end <= start_time || end_time <= *start))
+    }
+}
+
+// A group activity of a round in one room
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupSlot {
+    pub activity_id: ActivityId,
+    pub room_id: RoomId,
+    pub group: GroupIdType,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+}
+
+impl Activity {
+    pub fn groupifier_config(&self) -> Option<&ActivityConfig> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::GroupifierActivityConfig(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+}
+
+impl Competition {
+    // Group activities (children of the round activities) of a round, ordered by start time
+    pub fn group_slots(&self, round_id: &RoundId) -> Vec<GroupSlot> {
+        let mut slots: Vec<GroupSlot> = self.schedule.activities_with_rooms().into_iter()
+            .filter_map(|(_, room, activity)| match &activity.activity_code {
+                ActivityCode::Official(code) if code.event == round_id.event && code.round == Some(round_id.round) && code.attempt.is_none() => {
+                    code.group.map(|group| GroupSlot {
+                        activity_id: activity.id,
+                        room_id: room.id,
+                        group,
+                        start_time: activity.start_time,
+                        end_time: activity.end_time,
+                    })
+                },
+                _ => None,
+            })
+            .collect();
+        slots.sort_by_key(|s| (s.start_time, s.group, s.room_id));
+        slots
+    }
+
+    // Featured competitors configured in Groupifier's activity config of any round activity
+    pub fn featured_competitors(&self, round_id: &RoundId) -> Vec<WCAUserId> {
+        let mut featured: Vec<WCAUserId> = self.schedule.all_activities().into_iter()
+            .filter(|a| matches!(&a.activity_code, ActivityCode::Official(code) if code.event == round_id.event && code.round == Some(round_id.round) && code.group.is_none()))
+            .filter_map(|a| a.groupifier_config())
+            .flat_map(|c| c.featured_competitors_wca_user_ids.iter().copied())
+            .collect();
+        featured.sort();
+        featured.dedup();
+        featured
+    }
+
+    // Splits the competitors of a round into its group activities, replacing existing competitor assignments of
+    // those activities. Groups get (almost) equal sizes, the best seeded competitors are placed in the last groups.
+    // Returns the competitors of each group
+    pub fn generate_groups(&mut self, round_id: &RoundId, rule: &CompetitorsSortingRule, policy: &dyn FeaturedCompetitorsPolicy) -> Result<Vec<(GroupSlot, Vec<PersonId>)>, GroupingError> {
+        self.generate_groups_with_availability(round_id, rule, policy, &Availability::default())
+    }
+
+    // Same as `generate_groups`, competitors placed in a group they are unavailable for are swapped with a competitor
+    // of the closest group they are available for. Fails without changing any assignments if a competitor is
+    // unavailable during every group
+    pub fn generate_groups_with_availability(&mut self, round_id: &RoundId, rule: &CompetitorsSortingRule, policy: &dyn FeaturedCompetitorsPolicy, availability: &Availability) -> Result<Vec<(GroupSlot, Vec<PersonId>)>, GroupingError> {
+        if self.find_round(round_id).is_none() {
+            return Err(GroupingError::UnknownRound { round_id: round_id.clone() });
+        }
+        let slots = self.group_slots(round_id);
+        if slots.is_empty() {
+            return Err(GroupingError::NoGroupActivities { round_id: round_id.clone() });
+        }
+        let featured_ids = self.featured_competitors(round_id);
+        let competitors = self.sorted_round_competitors(round_id, rule);
+        let (featured, others): (Vec<&Person>, Vec<&Person>) = competitors.into_iter()
+            .partition(|p| featured_ids.contains(&p.wca_user_id));
+        let total = featured.len() + others.len();
+        let mut groups: Vec<Vec<PersonId>> = vec![vec![]; slots.len()];
+        for (person, group) in featured.iter().zip(policy.place(&featured, slots.len())) {
+            groups[group.min(slots.len() - 1)].extend(person.registrant_id);
+        }
+        // Later groups get the remainder, so the slowest competitors fill the first groups
+        let sizes: Vec<usize> = (0..slots.len())
+            .map(|i| total / slots.len() + usize::from(i >= slots.len() - total % slots.len()))
+            .collect();
+        let mut others = others.into_iter().rev().filter_map(|p| p.registrant_id);
+        for (group, size) in groups.iter_mut().zip(sizes) {
+            let missing = size.saturating_sub(group.len());
+            group.extend(others.by_ref().take(missing));
+        }
+        // Competitors left over because featured competitors overfilled groups go into the last one
+        groups.last_mut().expect("at least one group").extend(others);
+        place_available(&mut groups, &slots, availability)
+            .map_err(|person_ids| GroupingError::Unavailable { round_id: round_id.clone(), person_ids })?;
+
+        let slot_ids: Vec<ActivityId> = slots.iter().map(|s| s.activity_id).collect();
+        for person in self.persons.iter_mut() {
+            person.assignments.retain(|a| !(a.assignment_code == AssignmentCode::Competitor && slot_ids.contains(&a.activity_id)));
+        }
+        for (slot, group) in slots.iter().zip(&groups) {
+            for person_id in group {
+                if let Some(person) = self.find_person_mut(*person_id) {
+                    person.assignments.push(Assignment {
+                        activity_id: slot.activity_id,
+                        assignment_code: AssignmentCode::Competitor,
+                        station_number: None,
+                    });
+                }
+            }
+        }
+        Ok(slots.into_iter().zip(groups).collect())
+    }
+}
+
+// Moves competitors out of groups they are unavailable for, into the closest group they are available for.
+// A competitor of that group who is available for the original group takes their place, so sizes stay the same
+// whenever possible. Returns the competitors no group works for
+fn place_available(groups: &mut [Vec<PersonId>], slots: &[GroupSlot], availability: &Availability) -> Result<(), Vec<PersonId>> {
+    let fits = |person_id: PersonId, group: usize| availability.is_available(person_id, slots[group].start_time, slots[group].end_time);
+    let mut unsatisfiable = vec![];
+    for group in 0..groups.len() {
+        let conflicting: Vec<PersonId> = groups[group].iter().copied().filter(|id| !fits(*id, group)).collect();
+        for person_id in conflicting {
+            let mut targets: Vec<usize> = (0..groups.len()).filter(|g| fits(person_id, *g)).collect();
+            targets.sort_by_key(|g| g.abs_diff(group));
+            let Some(&target) = targets.first() else {
+                unsatisfiable.push(person_id);
+                continue;
+            };
+            groups[group].retain(|id| *id != person_id);
+            if let Some(position) = groups[target].iter().position(|id| fits(*id, group)) {
+                let swapped = groups[target].remove(position);
+                groups[group].push(swapped);
+            }
+            groups[target].push(person_id);
+        }
+    }
+    if unsatisfiable.is_empty() {
+        Ok(())
+    } else {
+        unsatisfiable.sort();
+        Err(unsatisfiable)
+    }
+}
+
+impl Room {
+    pub fn groupifier_config(&self) -> Option<&RoomConfig> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::GroupifierRoomConfig(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+}
+
+impl Competition {
+    pub fn groupifier_config(&self) -> Option<&CompetitionConfig> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::GroupifierCompetitionConfig(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    fn is_busy(&self, person: &Person, start_time: DateTime, end_time: DateTime) -> bool {
+        person.assignments.iter()
+            .filter_map(|a| self.schedule.find_activity(a.activity_id))
+            .any(|a| a.start_time < end_time && start_time < a.end_time)
+    }
+
+    // Assigns one judge per station to every group of a round if Groupifier's activity config asks for it,
+    // replacing existing judge assignments of those groups. Judges are picked among the competitors of the round
+    // who are not busy during the group (e.g. competing in it). Competitors of the groups right before or after it
+    // are only picked if there is nobody else, then judges with fewer judging assignments in the round are preferred.
+    // At least half of the judges of the faster half of the groups have competed before. Returns the judges of each group
+    pub fn generate_judges(&mut self, round_id: &RoundId) -> Result<Vec<(GroupSlot, Vec<PersonId>)>, GroupingError> {
+        self.generate_judges_with_availability(round_id, &Availability::default())
+    }
+
+    // Same as `generate_judges`, persons are never picked as judges for groups they are unavailable for
+    pub fn generate_judges_with_availability(&mut self, round_id: &RoundId, availability: &Availability) -> Result<Vec<(GroupSlot, Vec<PersonId>)>, GroupingError> {
+        if self.find_round(round_id).is_none() {
+            return Err(GroupingError::UnknownRound { round_id: round_id.clone() });
+        }
+        let assign_judges = self.schedule.all_activities().into_iter()
+            .filter(|a| matches!(&a.activity_code, ActivityCode::Official(code) if code.event == round_id.event && code.round == Some(round_id.round) && code.group.is_none()))
+            .filter_map(|a| a.groupifier_config())
+            .any(|c| c.assign_judges);
+        if !assign_judges {
+            return Ok(vec![]);
+        }
+        let slots = self.group_slots(round_id);
+        if slots.is_empty() {
+            return Err(GroupingError::NoGroupActivities { round_id: round_id.clone() });
+        }
+        let slot_ids: Vec<ActivityId> = slots.iter().map(|s| s.activity_id).collect();
+        for person in self.persons.iter_mut() {
+            person.assignments.retain(|a| !(a.assignment_code == AssignmentCode::Staff(StaffAssignment::Judge) && slot_ids.contains(&a.activity_id)));
+        }
+        let no_tasks_for_newcomers = self.groupifier_config().is_some_and(|c| c.no_tasks_for_newcomers);
+        let competes_in = |person: &Person, activity_id: ActivityId| person.assignments.iter()
+            .any(|a| a.activity_id == activity_id && a.assignment_code == AssignmentCode::Competitor);
+        let candidates: Vec<PersonId> = self.round_competitors(round_id).into_iter()
+            .filter(|p| !no_tasks_for_newcomers || p.wca_id.is_some())
+            .filter_map(|p| p.registrant_id)
+            .collect();
+
+        let mut judged: HashMap<PersonId, usize> = HashMap::new();
+        let mut result = vec![];
+        for (index, slot) in slots.iter().enumerate() {
+            let neighbours: Vec<ActivityId> = slots[index.saturating_sub(1)..slots.len().min(index + 2)].iter()
+                .map(|s| s.activity_id)
+                .collect();
+            let group_size = self.persons.iter().filter(|p| competes_in(p, slot.activity_id)).count();
+            let stations = self.schedule.venues.iter()
+                .flat_map(|v| v.rooms.iter())
+                .find(|r| r.id == slot.room_id)
+                .and_then(|r| r.groupifier_config())
+                .map_or(group_size, |c| (c.stations as usize).min(group_size));
+            let mut available: Vec<&Person> = candidates.iter()
+                .filter_map(|id| self.find_person(*id))
+                .filter(|p| !self.is_busy(p, slot.start_time, slot.end_time))
+                .filter(|p| p.registrant_id.is_some_and(|id| availability.is_available(id, slot.start_time, slot.end_time)))
+                .collect();
+            available.sort_by_key(|p| (
+                neighbours.iter().any(|a| competes_in(p, *a)),
+                judged.get(&p.registrant_id.unwrap_or_default()).copied().unwrap_or_default(),
+                p.wca_id.is_none(),
+            ));
+            let mut judges: Vec<PersonId> = vec![];
+            if index >= slots.len() / 2 {
+                judges.extend(available.iter()
+                    .filter(|p| p.wca_id.is_some())
+                    .take(stations.div_ceil(2))
+                    .filter_map(|p| p.registrant_id));
+            }
+            judges.extend(available.iter()
+                .filter_map(|p| p.registrant_id)
+                .filter(|id| !judges.contains(id))
+                .take(stations - judges.len())
+                .collect::<Vec<_>>());
+            for person_id in &judges {
+                *judged.entry(*person_id).or_default() += 1;
+                if let Some(person) = self.find_person_mut(*person_id) {
+                    person.assignments.push(Assignment {
+                        activity_id: slot.activity_id,
+                        assignment_code: AssignmentCode::Staff(StaffAssignment::Judge),
+                        station_number: None,
+                    });
+                }
+            }
+            result.push((slot.clone(), judges));
+        }
+        Ok(result)
+    }
+}
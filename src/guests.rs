@@ -0,0 +1,108 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+use crate::types::{ActivityId, Competition, Date, Person, PersonId, RegistrationStatus};
+
+// Expected attendance of a competition day, for venue capacity planning
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayAttendance {
+    pub date: Date,
+    pub persons: u32,
+    pub guests: u32,
+}
+
+impl DayAttendance {
+    pub fn total(&self) -> u32 {
+        self.persons + self.guests
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GuestLimit {
+    pub per_registration: Option<u32>,
+    pub total: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GuestLimitViolation {
+    PerRegistration { person_id: Option<PersonId>, limit: u32, guests: u32 },
+    Total { limit: u32, guests: u32 },
+}
+
+impl Display for GuestLimitViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PerRegistration { person_id: Some(person_id), limit, guests } => write!(f, "Person {person_id} registered {guests} guests, only {limit} are allowed"),
+            Self::PerRegistration { person_id: None, limit, guests } => write!(f, "A person registered {guests} guests, only {limit} are allowed"),
+            Self::Total { limit, guests } => write!(f, "{guests} guests are registered in total, only {limit} are allowed"),
+        }
+    }
+}
+
+fn accepted_guests(person: &Person) -> Option<u32> {
+    person.registration.as_ref()
+        .filter(|r| r.status == RegistrationStatus::Accepted)
+        .map(|r| r.guests)
+}
+
+impl Competition {
+    // Guests of all accepted registrations
+    pub fn total_guests(&self) -> u32 {
+        self.persons.iter().filter_map(accepted_guests).sum()
+    }
+
+    // Estimates the attendance per day. Persons (and their guests) are expected on the days they have assignments on,
+    // or on all days of the competition if they have no assignments yet
+    pub fn attendance_per_day(&self) -> Vec<DayAttendance> {
+        let all_days: BTreeSet<Date> = (0..self.schedule.number_of_days as u64)
+            .filter_map(|i| self.schedule.start_date.checked_add_days(chrono::Days::new(i)))
+            .collect();
+        let activity_days: HashMap<ActivityId, Date> = self.schedule.activities_with_rooms().into_iter()
+            .map(|(venue, _, activity)| (activity.id, venue.local_date(&activity.start_time)))
+            .collect();
+        let mut days: BTreeMap<Date, DayAttendance> = all_days.iter()
+            .map(|date| (*date, DayAttendance { date: *date, persons: 0, guests: 0 }))
+            .collect();
+        for person in &self.persons {
+            let Some(guests) = accepted_guests(person) else {
+                continue;
+            };
+            let mut person_days: BTreeSet<Date> = person.assignments.iter()
+                .filter_map(|a| activity_days.get(&a.activity_id).copied())
+                .collect();
+            if person_days.is_empty() {
+                person_days = all_days.clone();
+            }
+            for date in person_days {
+                let day = days.entry(date).or_insert(DayAttendance { date, persons: 0, guests: 0 });
+                day.persons += 1;
+                day.guests += guests;
+            }
+        }
+        days.into_values().collect()
+    }
+
+    pub fn validate_guests(&self, limit: &GuestLimit) -> Vec<GuestLimitViolation> {
+        let mut violations = vec![];
+        if let Some(per_registration) = limit.per_registration {
+            for person in &self.persons {
+                match accepted_guests(person) {
+                    Some(guests) if guests > per_registration => violations.push(GuestLimitViolation::PerRegistration {
+                        person_id: person.registrant_id,
+                        limit: per_registration,
+                        guests,
+                    }),
+                    _ => {},
+                }
+            }
+        }
+        let guests = self.total_guests();
+        match limit.total {
+            Some(total) if guests > total => violations.push(GuestLimitViolation::Total { limit: total, guests }),
+            _ => {},
+        }
+        violations
+    }
+}
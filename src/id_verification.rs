@@ -0,0 +1,69 @@
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::types::{Competition, DateTime, Extension, Person, PersonId};
+
+pub const EXTENSION_ID: &str = crate::extensions::ID_VERIFICATION_ID;
+pub const SPEC_URL: &str = crate::extensions::ID_VERIFICATION_SPEC_URL;
+
+// Stored on a newcomer once a delegate checked their name and birthdate against an identity document
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdVerificationExtension {
+    pub id: MustBe!("wcif-rs.IdVerification"),
+    pub spec_url: String,
+    pub data: IdVerification,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdVerification {
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
+    pub verified_at: DateTime,
+    // Registrant id of the person who checked the document
+    pub verified_by: PersonId,
+}
+
+impl Person {
+    pub fn id_verification(&self) -> Option<&IdVerification> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::IdVerification(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    pub fn is_id_verified(&self) -> bool {
+        self.id_verification().is_some()
+    }
+
+    // Replaces a previous verification
+    pub fn verify_id(&mut self, verified_by: PersonId, now: DateTime) {
+        self.undo_id_verification();
+        self.extensions.push(Extension::IdVerification(IdVerificationExtension {
+            id: Default::default(),
+            spec_url: SPEC_URL.to_string(),
+            data: IdVerification { verified_at: now, verified_by },
+        }));
+    }
+
+    pub fn undo_id_verification(&mut self) {
+        self.extensions.retain(|e| !matches!(e, Extension::IdVerification(_)));
+    }
+}
+
+impl Competition {
+    // Returns false if there is no such person
+    pub fn verify_id(&mut self, person_id: PersonId, verified_by: PersonId, now: DateTime) -> bool {
+        let Some(person) = self.find_person_mut(person_id) else {
+            return false;
+        };
+        person.verify_id(verified_by, now);
+        true
+    }
+
+    // Accepted newcomers whose identity wasn't checked yet, ordered by name
+    pub fn newcomers_not_verified(&self) -> Vec<&Person> {
+        self.newcomers().into_iter()
+            .filter(|p| !p.is_id_verified())
+            .collect()
+    }
+}
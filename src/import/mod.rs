@@ -0,0 +1,2 @@
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod timers;
@@ -0,0 +1,226 @@
+use std::fmt::{Display, Formatter};
+use serde_json::Value;
+use crate::results::round_timed_result;
+use crate::types::{Attempt, AttemptResult, DateTime, PersonId, Round, RoundResult};
+
+#[derive(Debug)]
+pub enum TimerImportError {
+    Json(serde_json::Error),
+    UnknownSession { session: String },
+    InvalidSolve { index: usize },
+}
+
+impl Display for TimerImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "{e}"),
+            Self::UnknownSession { session } => write!(f, "Session {session} does not exist"),
+            Self::InvalidSolve { index } => write!(f, "Solve {index} is invalid"),
+        }
+    }
+}
+
+impl std::error::Error for TimerImportError {}
+
+// A solve recorded by a timer. Only timed events can be imported
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimerEntry {
+    // As shown on the timer, truncated to centiseconds
+    pub centiseconds: u32,
+    pub plus_twos: u32,
+    pub dnf: bool,
+    pub scramble: Option<String>,
+    pub comment: Option<String>,
+    pub timestamp: Option<DateTime>,
+}
+
+impl TimerEntry {
+    pub fn new(centiseconds: u32) -> Self {
+        Self { centiseconds, plus_twos: 0, dnf: false, scramble: None, comment: None, timestamp: None }
+    }
+
+    pub fn result(&self) -> AttemptResult {
+        if self.dnf {
+            AttemptResult::DNF
+        } else {
+            AttemptResult::Success(round_timed_result(self.centiseconds + 200 * self.plus_twos))
+        }
+    }
+}
+
+// Sessions of a csTimer export as key (e.g. "session1") and name
+pub fn cstimer_sessions(json: &str) -> Result<Vec<(String, String)>, TimerImportError> {
+    let export: Value = serde_json::from_str(json).map_err(TimerImportError::Json)?;
+    Ok(cstimer_session_names(&export))
+}
+
+fn cstimer_session_names(export: &Value) -> Vec<(String, String)> {
+    // Depending on the version, the session data is an object or a string containing one
+    let session_data = match export.pointer("/properties/sessionData") {
+        Some(Value::String(s)) => serde_json::from_str(s).unwrap_or_default(),
+        Some(data) => data.clone(),
+        None => Value::Null,
+    };
+    let mut sessions: Vec<(String, String)> = export.as_object().iter()
+        .flat_map(|o| o.keys())
+        .filter_map(|key| {
+            let number = key.strip_prefix("session")?;
+            let name = match session_data.get(number).and_then(|s| s.get("name")) {
+                Some(Value::String(name)) => name.clone(),
+                Some(name) => name.to_string(),
+                None => number.to_string(),
+            };
+            Some((key.clone(), name))
+        })
+        .collect();
+    sessions.sort_by_key(|(key, _)| key["session".len()..].parse::<u32>().unwrap_or(u32::MAX));
+    sessions
+}
+
+// Solves of a session of a csTimer export, selected by key or name. Solves are stored as
+// [[penalty, milliseconds, ...], scramble, comment, timestamp], with a penalty of 2000 for +2 and -1 for DNF
+pub fn parse_cstimer(json: &str, session: &str) -> Result<Vec<TimerEntry>, TimerImportError> {
+    let export: Value = serde_json::from_str(json).map_err(TimerImportError::Json)?;
+    let key = cstimer_session_names(&export).into_iter()
+        .find(|(key, name)| key == session || name == session)
+        .map(|(key, _)| key)
+        .ok_or_else(|| TimerImportError::UnknownSession { session: session.to_string() })?;
+    let solves = match &export[&key] {
+        Value::String(s) => serde_json::from_str(s).map_err(TimerImportError::Json)?,
+        solves => solves.clone(),
+    };
+    solves.as_array().into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, solve)| {
+            let penalty = solve.pointer("/0/0").and_then(Value::as_i64).ok_or(TimerImportError::InvalidSolve { index })?;
+            let milliseconds = solve.pointer("/0/1").and_then(Value::as_u64).ok_or(TimerImportError::InvalidSolve { index })?;
+            let text = |i: usize| solve.get(i).and_then(Value::as_str).filter(|s| !s.is_empty()).map(str::to_string);
+            Ok(TimerEntry {
+                centiseconds: (milliseconds / 10) as u32,
+                plus_twos: if penalty > 0 { penalty as u32 / 2000 } else { 0 },
+                dnf: penalty < 0,
+                scramble: text(1),
+                comment: text(2),
+                timestamp: solve.get(3).and_then(Value::as_i64).and_then(|t| DateTime::from_timestamp(t, 0)),
+            })
+        })
+        .collect()
+}
+
+// Solves in the data a Stackmat timer sends over its serial port. Each packet is a status character, the time as
+// MSSHH (or MSSHHT for Gen4 timers), a checksum of 64 plus the sum of the digits and a line break. A solve is
+// recorded when the timer stops, packets with wrong checksums are ignored
+pub fn parse_stackmat_log(log: &[u8]) -> Vec<TimerEntry> {
+    let mut entries = vec![];
+    let mut armed = true;
+    let mut last: Option<u32> = None;
+    for line in log.split(|b| *b == b'\n') {
+        let packet = line.trim_ascii_end();
+        let Some((status, centiseconds)) = parse_stackmat_packet(packet) else {
+            continue;
+        };
+        match status {
+            b'S' if centiseconds > 0 && (armed || last != Some(centiseconds)) => {
+                entries.push(TimerEntry::new(centiseconds));
+                armed = false;
+                last = Some(centiseconds);
+            },
+            b'S' => {},
+            // Running or reset
+            b' ' | b'I' => armed = true,
+            _ => {},
+        }
+    }
+    entries
+}
+
+fn parse_stackmat_packet(packet: &[u8]) -> Option<(u8, u32)> {
+    let (&status, rest) = packet.split_first()?;
+    let (&checksum, digits) = rest.split_last()?;
+    if !matches!(digits.len(), 5 | 6) || !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let digits: Vec<u32> = digits.iter().map(|d| (d - b'0') as u32).collect();
+    if checksum as u32 != 64 + digits.iter().sum::<u32>() {
+        return None;
+    }
+    Some((status, digits[0] * 6000 + (digits[1] * 10 + digits[2]) * 100 + digits[3] * 10 + digits[4]))
+}
+
+impl Round {
+    // Enters timer entries as the next attempts of a person, until they have no attempts left (including missing
+    // the cutoff). Results reaching a non-cumulative time limit are DNF. Returns the number of entries used
+    pub fn import_timer_entries(&mut self, person_id: PersonId, entries: &[TimerEntry]) -> usize {
+        let time_limit = self.time_limit.as_ref()
+            .filter(|t| t.cumulative_round_ids.is_empty())
+            .map(|t| t.centiseconds);
+        let mut imported = 0;
+        for entry in entries {
+            let Some(index) = self.next_attempt_index(person_id) else {
+                break;
+            };
+            let result = match entry.result() {
+                AttemptResult::Success(value) if time_limit.is_some_and(|limit| value >= limit) => AttemptResult::DNF,
+                result => result,
+            };
+            if !self.results.iter().any(|r| r.person_id == person_id) {
+                self.results.push(RoundResult {
+                    person_id,
+                    ranking: None,
+                    attempts: vec![],
+                    best: AttemptResult::Skipped,
+                    average: AttemptResult::Skipped,
+                });
+            }
+            let round_result = self.results.iter_mut().find(|r| r.person_id == person_id).expect("result was just added");
+            let attempt = Attempt { result, reconstruction: None };
+            match round_result.attempts.get_mut(index) {
+                Some(skipped) => *skipped = attempt,
+                None => round_result.attempts.push(attempt),
+            }
+            imported += 1;
+        }
+        if imported > 0 {
+            self.update_results();
+        }
+        imported
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(status: u8, digits: &str) -> Vec<u8> {
+        let checksum = 64 + digits.bytes().map(|d| (d - b'0') as u32).sum::<u32>();
+        let mut packet = vec![status];
+        packet.extend_from_slice(digits.as_bytes());
+        packet.extend_from_slice(&[checksum as u8, b'\r', b'\n']);
+        packet
+    }
+
+    fn times(packets: &[(u8, &str)]) -> Vec<u32> {
+        let log: Vec<u8> = packets.iter().flat_map(|(status, digits)| packet(*status, digits)).collect();
+        parse_stackmat_log(&log).iter().map(|e| e.centiseconds).collect()
+    }
+
+    #[test]
+    fn repeated_stopped_packets_are_one_solve() {
+        assert_eq!(times(&[(b'I', "00000"), (b' ', "00500"), (b'S', "01234"), (b'S', "01234")]), [1234]);
+    }
+
+    #[test]
+    fn running_packet_separates_equal_solves() {
+        let packets = [(b'S', "01234"), (b'S', "01234"), (b' ', "00005"), (b' ', "01000"), (b'S', "01234")];
+        assert_eq!(times(&packets), [1234, 1234]);
+    }
+
+    #[test]
+    fn packets_with_wrong_checksums_are_ignored() {
+        let mut log = packet(b'S', "01234");
+        log[6] += 1;
+        log.extend(packet(b'S', "10000"));
+        assert_eq!(parse_stackmat_log(&log).iter().map(|e| e.centiseconds).collect::<Vec<_>>(), [6000]);
+    }
+}
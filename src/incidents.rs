@@ -0,0 +1,213 @@
+use std::fmt::{Display, Formatter, Write};
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::export::escape_markdown_cell;
+use crate::types::{Competition, DateTime, Extension, PersonId, RoundId};
+
+pub const EXTENSION_ID: &str = crate::extensions::INCIDENT_LOG_ID;
+pub const SPEC_URL: &str = crate::extensions::INCIDENT_LOG_SPEC_URL;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IncidentError {
+    UnknownRound { round_id: RoundId },
+    UnknownPerson { person_id: PersonId },
+    // Attempt numbers start at 1 and can't exceed the number of attempts of the round's format
+    InvalidAttempt { round_id: RoundId, attempt_number: u32 },
+    UnknownIncident { incident_id: u32 },
+}
+
+impl Display for IncidentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownRound { round_id } => write!(f, "Round {round_id} does not exist"),
+            Self::UnknownPerson { person_id } => write!(f, "Person {person_id} does not exist"),
+            Self::InvalidAttempt { round_id, attempt_number } => write!(f, "Round {round_id} has no attempt {attempt_number}"),
+            Self::UnknownIncident { incident_id } => write!(f, "Incident {incident_id} does not exist"),
+        }
+    }
+}
+
+impl std::error::Error for IncidentError {}
+
+// Incidents handled by the delegates during the competition, kept for the delegate report
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncidentLogExtension {
+    pub id: MustBe!("wcif-rs.IncidentLog"),
+    pub spec_url: String,
+    pub data: IncidentLog,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncidentLog {
+    pub incidents: Vec<Incident>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Incident {
+    pub id: u32,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
+    pub reported_at: DateTime,
+    pub person_id: Option<PersonId>,
+    pub round_id: Option<RoundId>,
+    // Starting at 1, only set together with the round
+    pub attempt_number: Option<u32>,
+    // Regulations and guidelines involved, e.g. "A6e" or "10e+"
+    pub regulations: Vec<String>,
+    pub description: String,
+    pub resolution: Option<Resolution>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resolution {
+    pub action: IncidentAction,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IncidentAction {
+    ExtraAttempt,
+    TimePenalty,
+    Dnf,
+    Disqualification,
+    NoAction,
+    Other,
+}
+
+impl IncidentAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ExtraAttempt => "Extra attempt",
+            Self::TimePenalty => "Time penalty",
+            Self::Dnf => "DNF",
+            Self::Disqualification => "Disqualification",
+            Self::NoAction => "No action",
+            Self::Other => "Other",
+        }
+    }
+}
+
+impl Competition {
+    pub fn incident_log(&self) -> Option<&IncidentLog> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::IncidentLog(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    pub fn incident_log_mut(&mut self) -> &mut IncidentLog {
+        if self.incident_log().is_none() {
+            self.extensions.push(Extension::IncidentLog(IncidentLogExtension {
+                id: Default::default(),
+                spec_url: SPEC_URL.to_string(),
+                data: IncidentLog::default(),
+            }));
+        }
+        self.extensions.iter_mut().find_map(|e| match e {
+            Extension::IncidentLog(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("incident log extension was just added")
+    }
+
+    pub fn incidents(&self) -> &[Incident] {
+        self.incident_log().map(|l| l.incidents.as_slice()).unwrap_or_default()
+    }
+
+    // Logs an incident not tied to an attempt and returns its id
+    pub fn log_incident(&mut self, now: DateTime, person_id: Option<PersonId>, regulations: Vec<String>, description: String) -> Result<u32, IncidentError> {
+        if let Some(person_id) = person_id.filter(|id| self.find_person(*id).is_none()) {
+            return Err(IncidentError::UnknownPerson { person_id });
+        }
+        Ok(self.push_incident(now, person_id, None, None, regulations, description))
+    }
+
+    // Logs an incident of an attempt and returns its id. The attempt doesn't have to be entered yet
+    pub fn log_attempt_incident(&mut self, now: DateTime, person_id: PersonId, round_id: &RoundId, attempt_number: u32, regulations: Vec<String>, description: String) -> Result<u32, IncidentError> {
+        let Some(round) = self.find_round(round_id) else {
+            return Err(IncidentError::UnknownRound { round_id: round_id.clone() });
+        };
+        if attempt_number == 0 || attempt_number > round.format.expected_solve_count() as u32 {
+            return Err(IncidentError::InvalidAttempt { round_id: round_id.clone(), attempt_number });
+        }
+        if self.find_person(person_id).is_none() {
+            return Err(IncidentError::UnknownPerson { person_id });
+        }
+        Ok(self.push_incident(now, Some(person_id), Some(round_id.clone()), Some(attempt_number), regulations, description))
+    }
+
+    fn push_incident(&mut self, now: DateTime, person_id: Option<PersonId>, round_id: Option<RoundId>, attempt_number: Option<u32>, regulations: Vec<String>, description: String) -> u32 {
+        let log = self.incident_log_mut();
+        let id = log.incidents.iter().map(|i| i.id + 1).max().unwrap_or(1);
+        log.incidents.push(Incident { id, reported_at: now, person_id, round_id, attempt_number, regulations, description, resolution: None });
+        trace_change!(incident_id = id, "logged incident");
+        id
+    }
+
+    // Replaces a previous resolution
+    pub fn resolve_incident(&mut self, incident_id: u32, resolution: Resolution) -> Result<(), IncidentError> {
+        let incident = self.incident_log_mut().incidents.iter_mut()
+            .find(|i| i.id == incident_id)
+            .ok_or(IncidentError::UnknownIncident { incident_id })?;
+        incident.resolution = Some(resolution);
+        Ok(())
+    }
+
+    pub fn incidents_for_attempt(&self, person_id: PersonId, round_id: &RoundId, attempt_number: u32) -> Vec<&Incident> {
+        self.incidents().iter()
+            .filter(|i| i.person_id == Some(person_id) && i.round_id.as_ref() == Some(round_id) && i.attempt_number == Some(attempt_number))
+            .collect()
+    }
+
+    pub fn unresolved_incidents(&self) -> Vec<&Incident> {
+        self.incidents().iter()
+            .filter(|i| i.resolution.is_none())
+            .collect()
+    }
+
+    // Markdown table of all incidents in the order they were reported, for the delegate report. Times are local to
+    // the first venue
+    pub fn incident_report(&self) -> String {
+        let mut md = String::new();
+        let _ = self.write_incident_report(&mut md);
+        md
+    }
+
+    fn write_incident_report(&self, md: &mut String) -> std::fmt::Result {
+        let mut incidents: Vec<&Incident> = self.incidents().iter().collect();
+        incidents.sort_by_key(|i| (i.reported_at, i.id));
+        writeln!(md, "## Incidents")?;
+        writeln!(md)?;
+        if incidents.is_empty() {
+            writeln!(md, "No incidents.")?;
+            return Ok(());
+        }
+        writeln!(md, "| Time | Competitor | Round | Attempt | Regulations | Description | Resolution |")?;
+        writeln!(md, "|---|---|---|---|---|---|---|")?;
+        let venue = self.schedule.venues.first();
+        for incident in incidents {
+            let time = match venue {
+                Some(venue) => venue.local_time(&incident.reported_at),
+                None => incident.reported_at.naive_utc(),
+            };
+            let resolution = match &incident.resolution {
+                Some(r) if r.description.is_empty() => r.action.name().to_string(),
+                Some(r) => format!("{}: {}", r.action.name(), r.description),
+                None => "Unresolved".to_string(),
+            };
+            writeln!(md, "| {} | {} | {} | {} | {} | {} | {} |",
+                time.format("%a %H:%M"),
+                incident.person_id.and_then(|id| self.find_person(id)).map(|p| p.name.as_str()).unwrap_or_default(),
+                incident.round_id.as_ref().map(|r| format!("{} Round {}", r.event.get_name(), r.round)).unwrap_or_default(),
+                incident.attempt_number.map(|a| a.to_string()).unwrap_or_default(),
+                incident.regulations.join(", "),
+                escape_markdown_cell(&incident.description),
+                escape_markdown_cell(&resolution),
+            )?;
+        }
+        Ok(())
+    }
+}
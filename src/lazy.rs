@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use crate::types::{AdvancementCondition, Competition, CompetitionId, Cutoff, Event, EventId, Extension, FormatVersion, Person, Qualification, RegistrationInfo, Round, RoundFormat, RoundId, RoundResult, Schedule, ScrambleSet, Series, TimeLimit};
+
+// Mirrors of Competition, Event and Round that keep the results of rounds as raw JSON. Persons are
+// either decoded or kept as raw JSON as well
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LazyDocument<'a, P> {
+    format_version: FormatVersion,
+    id: CompetitionId,
+    name: String,
+    short_name: String,
+    series: Option<Series>,
+    persons: Vec<P>,
+    #[serde(borrow)]
+    events: Vec<LazyEvent<'a>>,
+    schedule: Schedule,
+    registration_info: Option<RegistrationInfo>,
+    competitor_limit: Option<u32>,
+    #[serde(default)]
+    extensions: Vec<Extension>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LazyEvent<'a> {
+    id: EventId,
+    #[serde(borrow)]
+    rounds: Vec<LazyRound<'a>>,
+    competitor_limit: Option<u32>,
+    qualification: Option<Qualification>,
+    #[serde(default)]
+    extensions: Vec<Extension>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LazyRound<'a> {
+    id: RoundId,
+    format: RoundFormat,
+    time_limit: Option<TimeLimit>,
+    cutoff: Option<Cutoff>,
+    advancement_condition: Option<AdvancementCondition>,
+    #[serde(default, borrow)]
+    results: Option<&'a RawValue>,
+    scramble_set_count: u32,
+    #[serde(default)]
+    scramble_sets: Vec<ScrambleSet>,
+    #[serde(default)]
+    extensions: Vec<Extension>,
+}
+
+impl<'a, P> LazyDocument<'a, P> {
+    // The competition without persons and results, the persons and the raw results of all rounds
+    pub(crate) fn split(self) -> (Competition, Vec<P>, Vec<(RoundId, &'a RawValue)>) {
+        let mut raw_results = vec![];
+        let events = self.events.into_iter()
+            .map(|event| Event {
+                id: event.id,
+                rounds: event.rounds.into_iter()
+                    .map(|round| {
+                        if let Some(results) = round.results {
+                            raw_results.push((round.id.clone(), results));
+                        }
+                        Round {
+                            id: round.id,
+                            format: round.format,
+                            time_limit: round.time_limit,
+                            cutoff: round.cutoff,
+                            advancement_condition: round.advancement_condition,
+                            results: vec![],
+                            scramble_set_count: round.scramble_set_count,
+                            scramble_sets: round.scramble_sets,
+                            extensions: round.extensions,
+                        }
+                    })
+                    .collect(),
+                competitor_limit: event.competitor_limit,
+                qualification: event.qualification,
+                extensions: event.extensions,
+            })
+            .collect();
+        let competition = Competition {
+            format_version: self.format_version,
+            id: self.id,
+            name: self.name,
+            short_name: self.short_name,
+            series: self.series,
+            persons: vec![],
+            events,
+            schedule: self.schedule,
+            registration_info: self.registration_info,
+            competitor_limit: self.competitor_limit,
+            extensions: self.extensions,
+        };
+        (competition, self.persons, raw_results)
+    }
+}
+
+// A competition whose round results are kept as slices of the source document and only decoded when accessed,
+// for tools that parse many documents but never look at results. The source can be a memory mapped file
+#[derive(Clone, Debug)]
+pub struct LazyCompetition<'a> {
+    // Rounds have no results until they are decoded
+    competition: Competition,
+    raw_results: HashMap<RoundId, &'a RawValue>,
+}
+
+impl<'a> LazyCompetition<'a> {
+    pub fn parse(json: &'a str) -> Result<Self, serde_json::Error> {
+        let document: LazyDocument<'a, Person> = serde_json::from_str(json)?;
+        let (mut competition, persons, raw_results) = document.split();
+        competition.persons = persons;
+        Ok(Self { competition, raw_results: raw_results.into_iter().collect() })
+    }
+
+    // The competition with the results of all rounds that have not been accessed yet missing
+    pub fn competition(&self) -> &Competition {
+        &self.competition
+    }
+
+    pub fn is_decoded(&self, round_id: &RoundId) -> bool {
+        !self.raw_results.contains_key(round_id)
+    }
+
+    // Decodes the results of a round on first access. Returns None if there is no such round
+    pub fn results(&mut self, round_id: &RoundId) -> Option<Result<&[RoundResult], serde_json::Error>> {
+        if let Some(raw) = self.raw_results.get(round_id) {
+            let results = match serde_json::from_str(raw.get()) {
+                Ok(results) => results,
+                Err(e) => return Some(Err(e)),
+            };
+            self.raw_results.remove(round_id);
+            self.competition.find_round_mut(round_id)?.results = results;
+        }
+        self.competition.find_round(round_id).map(|r| Ok(r.results.as_slice()))
+    }
+
+    // Decodes the results of all rounds that have not been accessed yet
+    pub fn into_competition(mut self) -> Result<Competition, serde_json::Error> {
+        for (round_id, raw) in self.raw_results {
+            if let Some(round) = self.competition.find_round_mut(&round_id) {
+                round.results = serde_json::from_str(raw.get())?;
+            }
+        }
+        Ok(self.competition)
+    }
+}
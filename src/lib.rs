@@ -1,5 +1,135 @@
+// Emits a tracing event when the tracing feature is enabled and expands to nothing otherwise
+macro_rules! trace_change {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+pub use compliance::{parse, Profile};
+
 pub mod types;
+pub mod datetime;
+pub mod compliance;
+pub mod extensions;
+pub mod visit;
+pub mod query;
+pub mod facade;
+pub mod serialize;
+pub mod wca_id;
+pub mod names;
+pub mod diff;
+pub mod export;
+pub mod import;
+pub mod roundtrip;
+pub mod golden;
+pub mod projection;
+pub mod registration_sync;
+pub mod assignments;
+pub mod newcomers;
+pub mod staff;
+pub mod schedule;
+#[cfg(feature = "private_properties")]
+pub mod guests;
+#[cfg(feature = "private_properties")]
+pub mod private_data;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+pub mod validation;
+#[cfg(feature = "parse_activity_code")]
+pub mod repair;
+#[cfg(feature = "parse_activity_code")]
+pub mod scrambles;
+#[cfg(feature = "parse_activity_code")]
+pub mod stations;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+pub mod regions;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+pub mod records;
+#[cfg(feature = "country_data")]
+pub mod countries;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod results;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod summary;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod statistics;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod scoring;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+pub mod competition_set;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod fmc;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_puzzle_type"))]
+pub mod activity_names;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_puzzle_type"))]
+pub mod code_inference;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_puzzle_type"))]
+pub mod expand;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod rankings;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod standings;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod cursor;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod live;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod editing;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod presets;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod suggest;
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod wca_live;
+#[cfg(feature = "simulate")]
+pub mod simulate;
+#[cfg(feature = "scramble_encryption")]
+pub mod scramble_encryption;
+#[cfg(feature = "check_in")]
+pub mod check_in;
+#[cfg(feature = "id_verification")]
+pub mod id_verification;
+#[cfg(feature = "payments")]
+pub mod payments;
+#[cfg(feature = "result_verification")]
+pub mod verification;
+#[cfg(feature = "attempt_provenance")]
+pub mod provenance;
+#[cfg(feature = "extra_attempts")]
+pub mod extra_attempts;
+#[cfg(feature = "penalties")]
+pub mod penalties;
+#[cfg(feature = "replication")]
+pub mod replication;
+#[cfg(feature = "signing")]
+pub mod signing;
+#[cfg(feature = "waiting_list")]
+pub mod waiting_list;
+#[cfg(feature = "incidents")]
+pub mod incidents;
+#[cfg(feature = "unofficial_events")]
+pub mod unofficial;
+#[cfg(feature = "teams")]
+pub mod teams;
+#[cfg(feature = "lazy_results")]
+pub mod lazy;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "compression")]
+pub mod compression;
+#[cfg(feature = "store")]
+pub mod store;
+#[cfg(feature = "synthetic_fixtures")]
+pub mod fixtures;
+#[cfg(feature = "reconstruction")]
+pub mod reconstruction;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 #[cfg(feature = "groupifier")]
 pub mod groupifier;
+#[cfg(all(feature = "groupifier", feature = "parse_activity_code", feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+pub mod grouping;
 #[cfg(feature = "delegate_dashboard")]
 pub mod delegate_dashboard;
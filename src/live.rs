@@ -0,0 +1,102 @@
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+use crate::types::{Attempt, AttemptResult, Competition, PersonId, RoundId, RoundResult};
+
+// A single attempt entered during a round, together with the rankings of the round after the change.
+// Shared by live results servers and clients to stream results without sending the whole document
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WcifDelta {
+    pub round_id: RoundId,
+    pub person_id: PersonId,
+    // Zero based
+    pub attempt_index: usize,
+    pub value: AttemptResult,
+    pub rankings: Vec<RankingEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankingEntry {
+    pub person_id: PersonId,
+    pub ranking: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeltaError {
+    UnknownRound { round_id: RoundId },
+    UnknownResult { round_id: RoundId, person_id: PersonId },
+    AttemptOutOfRange { round_id: RoundId, attempt_index: usize },
+}
+
+impl Display for DeltaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownRound { round_id } => write!(f, "Round {round_id} does not exist"),
+            Self::UnknownResult { round_id, person_id } => write!(f, "Person {person_id} has no result in round {round_id}"),
+            Self::AttemptOutOfRange { round_id, attempt_index } => write!(f, "Round {round_id} has no attempt {}", attempt_index + 1),
+        }
+    }
+}
+
+impl std::error::Error for DeltaError {}
+
+impl WcifDelta {
+    // Creates the delta for an attempt that has already been updated in the competition
+    pub fn capture(competition: &Competition, round_id: &RoundId, person_id: PersonId, attempt_index: usize) -> Result<Self, DeltaError> {
+        let round = competition.find_round(round_id).ok_or_else(|| DeltaError::UnknownRound { round_id: round_id.clone() })?;
+        let result = round.results.iter()
+            .find(|r| r.person_id == person_id)
+            .ok_or_else(|| DeltaError::UnknownResult { round_id: round_id.clone(), person_id })?;
+        let value = result.attempts.get(attempt_index)
+            .map(|a| a.result)
+            .ok_or_else(|| DeltaError::AttemptOutOfRange { round_id: round_id.clone(), attempt_index })?;
+        Ok(WcifDelta {
+            round_id: round_id.clone(),
+            person_id,
+            attempt_index,
+            value,
+            rankings: round.results.iter()
+                .map(|r| RankingEntry { person_id: r.person_id, ranking: r.ranking })
+                .collect(),
+        })
+    }
+
+    // Sets the attempt, recomputes best and average of the result and takes over the ranking snapshot.
+    // Results that don't exist yet are created, missing attempts before the index are filled with skipped attempts
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(round_id = %self.round_id, person_id = self.person_id, attempt_index = self.attempt_index)))]
+    pub fn apply(&self, competition: &mut Competition) -> Result<(), DeltaError> {
+        let round = competition.find_round_mut(&self.round_id).ok_or_else(|| DeltaError::UnknownRound { round_id: self.round_id.clone() })?;
+        if self.attempt_index >= round.format.expected_solve_count() as usize {
+            return Err(DeltaError::AttemptOutOfRange { round_id: self.round_id.clone(), attempt_index: self.attempt_index });
+        }
+        let index = match round.results.iter().position(|r| r.person_id == self.person_id) {
+            Some(index) => index,
+            None => {
+                round.results.push(RoundResult {
+                    person_id: self.person_id,
+                    ranking: None,
+                    attempts: vec![],
+                    best: AttemptResult::Skipped,
+                    average: AttemptResult::Skipped,
+                });
+                round.results.len() - 1
+            },
+        };
+        let event_id = round.id.event.clone();
+        let format = round.format.clone();
+        let result = &mut round.results[index];
+        while result.attempts.len() <= self.attempt_index {
+            result.attempts.push(Attempt { result: AttemptResult::Skipped, reconstruction: None });
+        }
+        result.attempts[self.attempt_index].result = self.value;
+        result.update_best_and_average(&event_id, &format);
+        trace_change!(value = self.value.to_raw(), "applied attempt");
+        for result in round.results.iter_mut() {
+            if let Some(entry) = self.rankings.iter().find(|e| e.person_id == result.person_id) {
+                result.ranking = entry.ranking;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt::{Display, Formatter};
+use crate::types::Person;
+
+// A person name of the form "Latin Name (Local Name)" as used by the WCA for names in non-latin scripts
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PersonName {
+    latin: String,
+    local: Option<String>,
+}
+
+impl PersonName {
+    pub fn new(latin: String, local: Option<String>) -> Self {
+        PersonName { latin, local }
+    }
+
+    pub fn parse(name: &str) -> Self {
+        let name = name.trim();
+        if let Some(without_close) = name.strip_suffix(')') {
+            if let Some((latin, local)) = without_close.split_once('(') {
+                let local = local.trim();
+                return PersonName {
+                    latin: latin.trim().to_string(),
+                    local: (!local.is_empty()).then(|| local.to_string()),
+                };
+            }
+        }
+        PersonName {
+            latin: name.to_string(),
+            local: None,
+        }
+    }
+
+    pub fn latin(&self) -> &str {
+        &self.latin
+    }
+
+    pub fn local(&self) -> Option<&str> {
+        self.local.as_deref()
+    }
+
+    // Formats the name with the local name first if requested and available, e.g. "Local Name (Latin Name)"
+    pub fn format(&self, local_names_first: bool) -> String {
+        match (&self.local, local_names_first) {
+            (Some(local), true) => format!("{local} ({})", self.latin),
+            (Some(local), false) => format!("{} ({local})", self.latin),
+            (None, _) => self.latin.clone(),
+        }
+    }
+
+    #[cfg(feature = "groupifier")]
+    pub fn format_for(&self, config: &crate::groupifier::CompetitionConfig) -> String {
+        self.format(config.local_names_first)
+    }
+}
+
+impl Display for PersonName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.format(false))
+    }
+}
+
+impl Person {
+    pub fn parsed_name(&self) -> PersonName {
+        PersonName::parse(&self.name)
+    }
+}
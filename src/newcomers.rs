@@ -0,0 +1,47 @@
+use chrono::Datelike;
+use crate::assignments::ScheduledAssignment;
+use crate::types::{AssignmentCode, Competition, EventId, Person, RegistrationStatus};
+
+// A newcomer with what the newcomer briefing and the ID check need
+#[derive(Clone, Debug, PartialEq)]
+pub struct Newcomer<'a> {
+    pub person: &'a Person,
+    pub event_ids: &'a [EventId],
+    // Earliest activity the person competes in, None if groups haven't been assigned yet
+    pub first_group: Option<ScheduledAssignment<'a>>,
+}
+
+impl Person {
+    // A person competing for the first time: without a WCA ID, or with one from the year of the competition
+    // (assigned when the results of an earlier competition of the same year were posted)
+    pub fn is_newcomer(&self, competition_year: i32) -> bool {
+        self.wca_id.as_ref().is_none_or(|id| id.year as i32 == competition_year)
+    }
+}
+
+impl Competition {
+    // Accepted competitors who are newcomers, ordered by name
+    pub fn newcomers(&self) -> Vec<&Person> {
+        let year = self.schedule.start_date.year();
+        let mut newcomers: Vec<&Person> = self.persons.iter()
+            .filter(|p| p.registration.as_ref().is_some_and(|r| r.status == RegistrationStatus::Accepted && r.is_competing))
+            .filter(|p| p.is_newcomer(year))
+            .collect();
+        newcomers.sort_by(|a, b| a.name.cmp(&b.name));
+        newcomers
+    }
+
+    pub fn newcomer_list(&self) -> Vec<Newcomer<'_>> {
+        self.newcomers().into_iter()
+            .filter_map(|person| {
+                let first_group = person.registrant_id
+                    .and_then(|id| self.assignments_for(id).into_iter().find(|a| *a.assignment_code == AssignmentCode::Competitor));
+                Some(Newcomer {
+                    person,
+                    event_ids: &person.registration.as_ref()?.event_ids,
+                    first_group,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,47 @@
+use rayon::prelude::*;
+use serde_json::value::RawValue;
+use crate::lazy::LazyDocument;
+use crate::types::{Competition, Person, RoundResult};
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+use crate::validation::{validate_event_progression, ValidationError, DOCUMENT_CHECKS};
+
+// Parses a document, decoding persons and the results of all rounds on the rayon thread pool. Only the
+// structure of the document is scanned sequentially
+pub fn parse_parallel(json: &str) -> Result<Competition, serde_json::Error> {
+    let document: LazyDocument<&RawValue> = serde_json::from_str(json)?;
+    let (mut competition, persons, raw_results) = document.split();
+    let (persons, results) = rayon::join(
+        || persons.par_iter()
+            .map(|p| serde_json::from_str::<Person>(p.get()))
+            .collect::<Result<Vec<Person>, serde_json::Error>>(),
+        || raw_results.par_iter()
+            .map(|(round_id, raw)| serde_json::from_str::<Vec<RoundResult>>(raw.get()).map(|r| (round_id, r)))
+            .collect::<Result<Vec<_>, serde_json::Error>>(),
+    );
+    competition.persons = persons?;
+    for (round_id, results) in results? {
+        if let Some(round) = competition.find_round_mut(round_id) {
+            round.results = results;
+        }
+    }
+    Ok(competition)
+}
+
+#[cfg(all(feature = "parse_activity_code", feature = "parse_attempt_result"))]
+impl Competition {
+    // Same as `validate`, with the round progression of every event and the other checks of the document
+    // run in parallel to each other
+    pub fn validate_parallel(&self) -> Vec<ValidationError> {
+        let (progression, mut document) = rayon::join(
+            || self.events.par_iter()
+                .flat_map_iter(validate_event_progression)
+                .collect::<Vec<ValidationError>>(),
+            || DOCUMENT_CHECKS.par_iter()
+                .flat_map_iter(|check| check(self))
+                .collect::<Vec<ValidationError>>(),
+        );
+        let mut errors = progression;
+        errors.append(&mut document);
+        errors
+    }
+}
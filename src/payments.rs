@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::types::{Competition, CurrencyCode, DateTime, Extension, Person, RegistrationStatus};
+
+pub const EXTENSION_ID: &str = crate::extensions::PAYMENTS_ID;
+pub const SPEC_URL: &str = crate::extensions::PAYMENTS_SPEC_URL;
+
+// Payments of a person's registration. The base fee is part of the registration info, this tracks what was actually paid
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentsExtension {
+    pub id: MustBe!("wcif-rs.Payments"),
+    pub spec_url: String,
+    pub data: Payments,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payments {
+    pub payments: Vec<Payment>,
+}
+
+// Amounts are in the lowest denomination of the currency, like the base entry fee
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payment {
+    pub payment_id: String,
+    pub amount: u64,
+    pub currency_code: CurrencyCode,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
+    pub paid_at: DateTime,
+    #[serde(default)]
+    pub refunds: Vec<Refund>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Refund {
+    pub refund_id: String,
+    pub amount: u64,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
+    pub refunded_at: DateTime,
+}
+
+impl Payment {
+    pub fn refunded(&self) -> u64 {
+        self.refunds.iter().map(|r| r.amount).sum()
+    }
+
+    pub fn net_amount(&self) -> u64 {
+        self.amount.saturating_sub(self.refunded())
+    }
+}
+
+impl Payments {
+    // Net amount paid per currency
+    pub fn total(&self) -> BTreeMap<CurrencyCode, u64> {
+        let mut total = BTreeMap::new();
+        for payment in &self.payments {
+            *total.entry(payment.currency_code.clone()).or_default() += payment.net_amount();
+        }
+        total
+    }
+}
+
+impl Person {
+    pub fn payments(&self) -> Option<&Payments> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::Payments(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    pub fn payments_mut(&mut self) -> &mut Payments {
+        if self.payments().is_none() {
+            self.extensions.push(Extension::Payments(PaymentsExtension {
+                id: Default::default(),
+                spec_url: SPEC_URL.to_string(),
+                data: Payments::default(),
+            }));
+        }
+        self.extensions.iter_mut().find_map(|e| match e {
+            Extension::Payments(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("payments extension was just added")
+    }
+
+    pub fn amount_paid(&self, currency_code: &CurrencyCode) -> u64 {
+        self.payments()
+            .and_then(|p| p.total().get(currency_code).copied())
+            .unwrap_or_default()
+    }
+}
+
+impl Competition {
+    // Net amount collected per currency over all persons
+    pub fn total_collected(&self) -> BTreeMap<CurrencyCode, u64> {
+        let mut total = BTreeMap::new();
+        for payments in self.persons.iter().filter_map(|p| p.payments()) {
+            for (currency_code, amount) in payments.total() {
+                *total.entry(currency_code).or_default() += amount;
+            }
+        }
+        total
+    }
+
+    // Accepted persons who paid less than the base entry fee in the competition's currency.
+    // Empty if the document has no registration info
+    pub fn outstanding_payments(&self) -> Vec<(&Person, u64)> {
+        let Some(registration_info) = &self.registration_info else {
+            return vec![];
+        };
+        let fee = registration_info.base_entry_fee;
+        let currency_code = &registration_info.currency_code;
+        self.persons.iter()
+            .filter(|p| p.registration.as_ref().is_some_and(|r| r.status == RegistrationStatus::Accepted && r.is_competing))
+            .map(|p| (p, fee.saturating_sub(p.amount_paid(currency_code))))
+            .filter(|(_, outstanding)| *outstanding > 0)
+            .collect()
+    }
+}
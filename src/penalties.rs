@@ -0,0 +1,180 @@
+use std::fmt::{Display, Formatter};
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::results::round_timed_result;
+use crate::types::{Attempt, AttemptResult, Extension, PersonId, Round, RoundId, TimeLimit};
+
+pub const EXTENSION_ID: &str = crate::extensions::PENALTIES_ID;
+pub const SPEC_URL: &str = crate::extensions::PENALTIES_SPEC_URL;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PenaltyError {
+    // Persons are part of a round once they have a result in it, e.g. after advancing to it
+    UnknownPerson { round_id: RoundId, person_id: PersonId },
+    // Attempt indices are zero based and can't reach the number of attempts of the round's format
+    InvalidAttempt { round_id: RoundId, attempt_index: usize },
+}
+
+impl Display for PenaltyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownPerson { round_id, person_id } => write!(f, "Person {person_id} is not part of round {round_id}"),
+            Self::InvalidAttempt { round_id, attempt_index } => write!(f, "Round {round_id} has no attempt {}", attempt_index + 1),
+        }
+    }
+}
+
+impl std::error::Error for PenaltyError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PenaltyKind {
+    PlusTwo,
+    Dnf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PenaltyCause {
+    // Inspection over 15 seconds is +2, over 17 seconds DNF (A3d1)
+    Inspection,
+    // Puzzle misaligned by at most one move is +2, more is DNF (10f)
+    Misalignment,
+    // Timer started or stopped incorrectly (A4b, A6b, A6c)
+    Timer,
+    // Time limit reached (A1a4), added by the builder
+    TimeLimit,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Penalty {
+    pub kind: PenaltyKind,
+    pub cause: PenaltyCause,
+}
+
+// Builds the result of a timed attempt from the time shown on the timer and the penalties given by the judge
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttemptBuilder {
+    pub raw_centiseconds: u32,
+    pub penalties: Vec<Penalty>,
+}
+
+impl AttemptBuilder {
+    pub fn new(raw_centiseconds: u32) -> Self {
+        Self { raw_centiseconds, penalties: vec![] }
+    }
+
+    pub fn plus_two(mut self, cause: PenaltyCause) -> Self {
+        self.penalties.push(Penalty { kind: PenaltyKind::PlusTwo, cause });
+        self
+    }
+
+    pub fn dnf(mut self, cause: PenaltyCause) -> Self {
+        self.penalties.push(Penalty { kind: PenaltyKind::Dnf, cause });
+        self
+    }
+
+    // Adds a DNF if the result including penalties reaches the time limit of the round. Cumulative time limits
+    // depend on other attempts and are not checked
+    pub fn time_limit(self, time_limit: &TimeLimit) -> Self {
+        match self.build() {
+            AttemptResult::Success(centiseconds) if time_limit.cumulative_round_ids.is_empty() && centiseconds >= time_limit.centiseconds => self.dnf(PenaltyCause::TimeLimit),
+            _ => self,
+        }
+    }
+
+    pub fn plus_twos(&self) -> u32 {
+        self.penalties.iter().filter(|p| p.kind == PenaltyKind::PlusTwo).count() as u32
+    }
+
+    // Any DNF penalty makes the attempt a DNF. Otherwise two seconds are added per +2, results over 10 minutes are
+    // rounded to the nearest second (9f1)
+    pub fn build(&self) -> AttemptResult {
+        if self.penalties.iter().any(|p| p.kind == PenaltyKind::Dnf) {
+            return AttemptResult::DNF;
+        }
+        AttemptResult::Success(round_timed_result(self.raw_centiseconds + 200 * self.plus_twos()))
+    }
+
+    pub fn build_attempt(&self) -> Attempt {
+        Attempt { result: self.build(), reconstruction: None }
+    }
+}
+
+// Attempts can't have extensions, so the penalties of all attempts of a round are stored on the round
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PenaltiesExtension {
+    pub id: MustBe!("wcif-rs.Penalties"),
+    pub spec_url: String,
+    pub data: PenaltyLog,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PenaltyLog {
+    pub entries: Vec<PenaltyEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PenaltyEntry {
+    pub person_id: PersonId,
+    // Zero based
+    pub attempt_index: usize,
+    #[serde(flatten)]
+    pub attempt: AttemptBuilder,
+}
+
+impl Round {
+    pub fn penalty_log(&self) -> Option<&PenaltyLog> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::Penalties(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    fn penalty_log_mut(&mut self) -> &mut PenaltyLog {
+        if self.penalty_log().is_none() {
+            self.extensions.push(Extension::Penalties(PenaltiesExtension {
+                id: Default::default(),
+                spec_url: SPEC_URL.to_string(),
+                data: PenaltyLog::default(),
+            }));
+        }
+        self.extensions.iter_mut().find_map(|e| match e {
+            Extension::Penalties(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("penalties extension was just added")
+    }
+
+    // Sets an attempt to the built result and keeps the breakdown, replacing the one of a previous entry of the
+    // attempt. Earlier attempts that weren't entered yet are skipped
+    pub fn enter_attempt(&mut self, person_id: PersonId, attempt_index: usize, attempt: AttemptBuilder) -> Result<(), PenaltyError> {
+        if attempt_index >= self.format.expected_solve_count() as usize {
+            return Err(PenaltyError::InvalidAttempt { round_id: self.id.clone(), attempt_index });
+        }
+        let Some(result) = self.results.iter_mut().find(|r| r.person_id == person_id) else {
+            return Err(PenaltyError::UnknownPerson { round_id: self.id.clone(), person_id });
+        };
+        if result.attempts.len() <= attempt_index {
+            result.attempts.resize(attempt_index + 1, Attempt { result: AttemptResult::Skipped, reconstruction: None });
+        }
+        result.attempts[attempt_index] = attempt.build_attempt();
+        let log = self.penalty_log_mut();
+        log.entries.retain(|e| e.person_id != person_id || e.attempt_index != attempt_index);
+        log.entries.push(PenaltyEntry { person_id, attempt_index, attempt });
+        self.update_results();
+        Ok(())
+    }
+
+    // The time and penalties an attempt was built from, None if it was entered without a breakdown
+    pub fn attempt_penalties(&self, person_id: PersonId, attempt_index: usize) -> Option<&AttemptBuilder> {
+        self.penalty_log()?.entries.iter()
+            .find(|e| e.person_id == person_id && e.attempt_index == attempt_index)
+            .map(|e| &e.attempt)
+    }
+}
@@ -0,0 +1,93 @@
+use crate::editing::{EditError, RoundSpec};
+use crate::types::{AdvancementCondition, AttemptResult, Competition, Cutoff, Event, EventId};
+
+// Number of rounds and how competitors advance between them. The final round has no advancement condition
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoundsPreset {
+    pub name: &'static str,
+    pub advancement_conditions: &'static [AdvancementCondition],
+}
+
+pub const ONE_ROUND: RoundsPreset = RoundsPreset {
+    name: "1 round",
+    advancement_conditions: &[],
+};
+
+pub const TWO_ROUNDS_TOP_8: RoundsPreset = RoundsPreset {
+    name: "2 rounds, top 8",
+    advancement_conditions: &[AdvancementCondition::Ranking { level: 8 }],
+};
+
+pub const TWO_ROUNDS_TOP_12: RoundsPreset = RoundsPreset {
+    name: "2 rounds, top 12",
+    advancement_conditions: &[AdvancementCondition::Ranking { level: 12 }],
+};
+
+pub const TWO_ROUNDS_TOP_16: RoundsPreset = RoundsPreset {
+    name: "2 rounds, top 16",
+    advancement_conditions: &[AdvancementCondition::Ranking { level: 16 }],
+};
+
+pub const THREE_ROUNDS_TOP_75_PERCENT_TOP_12: RoundsPreset = RoundsPreset {
+    name: "3 rounds, top 75%, top 12",
+    advancement_conditions: &[AdvancementCondition::Percent { level: 75 }, AdvancementCondition::Ranking { level: 12 }],
+};
+
+pub const THREE_ROUNDS_TOP_75_PERCENT_TOP_16: RoundsPreset = RoundsPreset {
+    name: "3 rounds, top 75%, top 16",
+    advancement_conditions: &[AdvancementCondition::Percent { level: 75 }, AdvancementCondition::Ranking { level: 16 }],
+};
+
+pub const FOUR_ROUNDS_TOP_75_PERCENT_TOP_50_PERCENT_TOP_16: RoundsPreset = RoundsPreset {
+    name: "4 rounds, top 75%, top 50%, top 16",
+    advancement_conditions: &[AdvancementCondition::Percent { level: 75 }, AdvancementCondition::Percent { level: 50 }, AdvancementCondition::Ranking { level: 16 }],
+};
+
+pub const PRESETS: [RoundsPreset; 7] = [
+    ONE_ROUND,
+    TWO_ROUNDS_TOP_8,
+    TWO_ROUNDS_TOP_12,
+    TWO_ROUNDS_TOP_16,
+    THREE_ROUNDS_TOP_75_PERCENT_TOP_12,
+    THREE_ROUNDS_TOP_75_PERCENT_TOP_16,
+    FOUR_ROUNDS_TOP_75_PERCENT_TOP_50_PERCENT_TOP_16,
+];
+
+// Cutoffs commonly used for the first round of slower events, None for events that usually have none
+pub fn standard_cutoff(event_id: &EventId) -> Option<Cutoff> {
+    let (number_of_attempts, centiseconds) = match event_id {
+        EventId::Cube444 => (2, 12000),
+        EventId::Cube555 | EventId::Megaminx => (2, 18000),
+        EventId::Cube666 => (1, 30000),
+        EventId::Cube777 => (1, 42000),
+        EventId::Square1 => (2, 6000),
+        EventId::Clock => (2, 3000),
+        _ => return None,
+    };
+    Some(Cutoff { number_of_attempts, attempt_result: AttemptResult::Success(centiseconds) })
+}
+
+impl RoundsPreset {
+    pub fn round_count(&self) -> usize {
+        self.advancement_conditions.len() + 1
+    }
+
+    // Specs for all rounds of an event with the default format and time limit of the event, see
+    // `RoundSpec::default_for`. With `cutoff` the first round gets the standard cutoff of the event
+    pub fn round_specs(&self, event_id: &EventId, cutoff: bool) -> Vec<RoundSpec> {
+        (0..self.round_count())
+            .map(|i| RoundSpec {
+                cutoff: if cutoff && i == 0 { standard_cutoff(event_id) } else { None },
+                advancement_condition: self.advancement_conditions.get(i).cloned(),
+                ..RoundSpec::default_for(event_id)
+            })
+            .collect()
+    }
+}
+
+impl Competition {
+    pub fn add_event_from_preset(&mut self, event_id: EventId, preset: &RoundsPreset, cutoff: bool) -> Result<&mut Event, EditError> {
+        let rounds = preset.round_specs(&event_id, cutoff);
+        self.add_event(event_id, rounds)
+    }
+}
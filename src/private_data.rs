@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::types::{Competition, Date, WCAUserId};
+
+// Private fields of a person exported separately from the public WCIF, e.g. from a registration spreadsheet.
+// Fields that are missing are left unchanged when merged
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivatePersonData {
+    #[serde(default)]
+    pub birthdate: Option<Date>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub guests: Option<u32>,
+    #[serde(default)]
+    pub comments: Option<String>,
+    #[serde(default)]
+    pub administrative_notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateData {
+    pub persons: HashMap<WCAUserId, PrivatePersonData>,
+}
+
+impl Competition {
+    // Completes a public WCIF with private data keyed by WCA user id. Registration details are only applied to
+    // persons with a registration. Returns the user ids of the data that didn't match any person, sorted
+    pub fn merge_private_data(&mut self, data: &PrivateData) -> Vec<WCAUserId> {
+        for person in &mut self.persons {
+            let Some(private) = data.persons.get(&person.wca_user_id) else {
+                continue;
+            };
+            trace_change!(wca_user_id = person.wca_user_id, "merged private data");
+            if let Some(birthdate) = private.birthdate {
+                person.birthdate = birthdate;
+            }
+            if let Some(email) = &private.email {
+                person.email = email.clone();
+            }
+            let Some(registration) = person.registration.as_mut() else {
+                continue;
+            };
+            if let Some(guests) = private.guests {
+                registration.guests = guests;
+            }
+            if let Some(comments) = &private.comments {
+                registration.comments = comments.clone();
+            }
+            if let Some(administrative_notes) = &private.administrative_notes {
+                registration.administrative_notes = administrative_notes.clone();
+            }
+        }
+        let mut unmatched: Vec<WCAUserId> = data.persons.keys()
+            .filter(|id| !self.persons.iter().any(|p| &p.wca_user_id == *id))
+            .copied()
+            .collect();
+        unmatched.sort();
+        unmatched
+    }
+
+    pub fn from_public(mut competition: Competition, data: &PrivateData) -> Competition {
+        competition.merge_private_data(data);
+        competition
+    }
+}
@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{Activity, ActivityCode, ActivityId, AdvancementCondition, AssignmentCode, AttemptResult, Competition, CompetitionId, CountryCode, Cutoff, Date, DateTime, EventId, Person, PersonId, RegistrationStatus, Room, RoomId, Round, RoundFormat, RoundId, Schedule, TimeLimit, Venue, VenueId, WCAId};
+
+// Lightweight views of a competition for competitor facing APIs. Scrambles, extensions, attempts and private data are left out
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompetitionProjection {
+    pub id: CompetitionId,
+    pub name: String,
+    pub short_name: String,
+    pub competitors: Vec<CompetitorProjection>,
+    pub rounds: Vec<RoundProjection>,
+    pub schedule: ScheduleProjection,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompetitorProjection {
+    pub registrant_id: Option<PersonId>,
+    pub name: String,
+    pub wca_id: Option<WCAId>,
+    pub country_iso2: CountryCode,
+    pub avatar_thumb_url: Option<String>,
+    pub event_ids: Vec<EventId>,
+    pub assignments: Vec<AssignmentProjection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentProjection {
+    pub activity_id: ActivityId,
+    pub assignment_code: AssignmentCode,
+    pub station_number: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundProjection {
+    pub id: RoundId,
+    pub format: RoundFormat,
+    pub time_limit: Option<TimeLimit>,
+    pub cutoff: Option<Cutoff>,
+    pub advancement_condition: Option<AdvancementCondition>,
+    pub results: Vec<ResultProjection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultProjection {
+    pub person_id: PersonId,
+    pub ranking: Option<u64>,
+    pub best: AttemptResult,
+    pub average: AttemptResult,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleProjection {
+    pub start_date: Date,
+    pub number_of_days: u8,
+    pub venues: Vec<VenueProjection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VenueProjection {
+    pub id: VenueId,
+    pub name: String,
+    pub timezone: String,
+    pub rooms: Vec<RoomProjection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomProjection {
+    pub id: RoomId,
+    pub name: String,
+    pub color: String,
+    pub activities: Vec<ActivityProjection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityProjection {
+    pub id: ActivityId,
+    pub name: String,
+    pub activity_code: ActivityCode,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub child_activities: Vec<ActivityProjection>,
+}
+
+impl From<&Person> for CompetitorProjection {
+    fn from(person: &Person) -> Self {
+        CompetitorProjection {
+            registrant_id: person.registrant_id,
+            name: person.name.clone(),
+            wca_id: person.wca_id.clone(),
+            country_iso2: person.country_iso2.clone(),
+            avatar_thumb_url: person.avatar.as_ref().map(|a| a.thumb_url.clone()),
+            event_ids: person.registration.as_ref().map(|r| r.event_ids.clone()).unwrap_or_default(),
+            assignments: person.assignments.iter()
+                .map(|a| AssignmentProjection {
+                    activity_id: a.activity_id,
+                    assignment_code: a.assignment_code.clone(),
+                    station_number: a.station_number,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&Round> for RoundProjection {
+    fn from(round: &Round) -> Self {
+        RoundProjection {
+            id: round.id.clone(),
+            format: round.format.clone(),
+            time_limit: round.time_limit.clone(),
+            cutoff: round.cutoff.clone(),
+            advancement_condition: round.advancement_condition.clone(),
+            results: round.results.iter()
+                .map(|r| ResultProjection { person_id: r.person_id, ranking: r.ranking, best: r.best, average: r.average })
+                .collect(),
+        }
+    }
+}
+
+impl From<&Activity> for ActivityProjection {
+    fn from(activity: &Activity) -> Self {
+        ActivityProjection {
+            id: activity.id,
+            name: activity.name.clone(),
+            activity_code: activity.activity_code.clone(),
+            start_time: activity.start_time,
+            end_time: activity.end_time,
+            child_activities: activity.child_activities.iter().map(ActivityProjection::from).collect(),
+        }
+    }
+}
+
+impl From<&Room> for RoomProjection {
+    fn from(room: &Room) -> Self {
+        RoomProjection {
+            id: room.id,
+            name: room.name.clone(),
+            color: room.color.clone(),
+            activities: room.activities.iter().map(ActivityProjection::from).collect(),
+        }
+    }
+}
+
+impl From<&Venue> for VenueProjection {
+    fn from(venue: &Venue) -> Self {
+        VenueProjection {
+            id: venue.id,
+            name: venue.name.clone(),
+            timezone: venue.timezone.clone(),
+            rooms: venue.rooms.iter().map(RoomProjection::from).collect(),
+        }
+    }
+}
+
+impl From<&Schedule> for ScheduleProjection {
+    fn from(schedule: &Schedule) -> Self {
+        ScheduleProjection {
+            start_date: schedule.start_date,
+            number_of_days: schedule.number_of_days,
+            venues: schedule.venues.iter().map(VenueProjection::from).collect(),
+        }
+    }
+}
+
+impl From<&Competition> for ScheduleProjection {
+    fn from(competition: &Competition) -> Self {
+        ScheduleProjection::from(&competition.schedule)
+    }
+}
+
+// Only persons with an accepted registration are included
+impl From<&Competition> for Vec<CompetitorProjection> {
+    fn from(competition: &Competition) -> Self {
+        competition.persons.iter()
+            .filter(|p| p.registration.as_ref().is_some_and(|r| r.status == RegistrationStatus::Accepted))
+            .map(CompetitorProjection::from)
+            .collect()
+    }
+}
+
+impl From<&Competition> for Vec<RoundProjection> {
+    fn from(competition: &Competition) -> Self {
+        competition.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .map(RoundProjection::from)
+            .collect()
+    }
+}
+
+impl From<&Competition> for CompetitionProjection {
+    fn from(competition: &Competition) -> Self {
+        CompetitionProjection {
+            id: competition.id.clone(),
+            name: competition.name.clone(),
+            short_name: competition.short_name.clone(),
+            competitors: competition.into(),
+            rounds: competition.into(),
+            schedule: competition.into(),
+        }
+    }
+}
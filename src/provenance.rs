@@ -0,0 +1,71 @@
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::types::{DateTime, Extension, PersonId, Round};
+
+pub const EXTENSION_ID: &str = crate::extensions::ATTEMPT_PROVENANCE_ID;
+pub const SPEC_URL: &str = crate::extensions::ATTEMPT_PROVENANCE_SPEC_URL;
+
+// Attempts can't have extensions, so the audit trail of all attempts of a round is stored on the round
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttemptProvenanceExtension {
+    pub id: MustBe!("wcif-rs.AttemptProvenance"),
+    pub spec_url: String,
+    pub data: AttemptProvenanceLog,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttemptProvenanceLog {
+    // In the order the attempts were entered, later entries of the same attempt supersede earlier ones
+    pub entries: Vec<AttemptProvenance>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttemptProvenance {
+    pub person_id: PersonId,
+    // Zero based
+    pub attempt_index: usize,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
+    pub entered_at: DateTime,
+    pub entered_by: Option<PersonId>,
+    pub device_id: Option<String>,
+}
+
+impl Round {
+    pub fn attempt_provenance_log(&self) -> Option<&AttemptProvenanceLog> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::AttemptProvenance(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    // Appends an entry to the audit trail of the round
+    pub fn record_attempt_provenance(&mut self, provenance: AttemptProvenance) {
+        if self.attempt_provenance_log().is_none() {
+            self.extensions.push(Extension::AttemptProvenance(AttemptProvenanceExtension {
+                id: Default::default(),
+                spec_url: SPEC_URL.to_string(),
+                data: AttemptProvenanceLog::default(),
+            }));
+        }
+        self.extensions.iter_mut().find_map(|e| match e {
+            Extension::AttemptProvenance(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("provenance extension was just added").entries.push(provenance);
+    }
+
+    // All entries of an attempt, oldest first
+    pub fn attempt_history(&self, person_id: PersonId, attempt_index: usize) -> Vec<&AttemptProvenance> {
+        self.attempt_provenance_log().iter()
+            .flat_map(|l| l.entries.iter())
+            .filter(|e| e.person_id == person_id && e.attempt_index == attempt_index)
+            .collect()
+    }
+
+    // The entry of the current value of an attempt
+    pub fn attempt_provenance(&self, person_id: PersonId, attempt_index: usize) -> Option<&AttemptProvenance> {
+        self.attempt_history(person_id, attempt_index).pop()
+    }
+}
@@ -0,0 +1,267 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use serde::Serialize;
+use serde_json::Value;
+use crate::extensions::HasExtensions;
+use crate::types::{Activity, Assignment, Attempt, Competition, Event, Extension, Person, PersonalBest, Room, Round, RoundResult, Venue};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueryError {
+    Syntax { position: usize, message: String },
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax { position, message } => write!(f, "Invalid query at {position}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+fn syntax(position: usize, message: &str) -> QueryError {
+    QueryError::Syntax { position, message: message.to_string() }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+const OPERATORS: [(&str, Operator); 6] = [
+    ("!=", Operator::Ne),
+    ("<=", Operator::Le),
+    (">=", Operator::Ge),
+    ("=", Operator::Eq),
+    ("<", Operator::Lt),
+    (">", Operator::Gt),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Filter {
+    // 1-based, like XPath
+    Position(usize),
+    Compare { key: String, operator: Operator, value: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Step {
+    name: String,
+    filters: Vec<Filter>,
+}
+
+// A part of the competition found by a query. Fields without a type of their own are returned as JSON values
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryNode<'a> {
+    Competition(&'a Competition),
+    Person(&'a Person),
+    Assignment(&'a Assignment),
+    PersonalBest(&'a PersonalBest),
+    Event(&'a Event),
+    Round(&'a Round),
+    Result(&'a RoundResult),
+    Attempt(&'a Attempt),
+    Venue(&'a Venue),
+    Room(&'a Room),
+    Activity(&'a Activity),
+    Extension(&'a Extension),
+    Value(Value),
+}
+
+impl Step {
+    // Filters the children selected from one node, so positions count within each parent
+    fn apply<'a>(&self, mut nodes: Vec<QueryNode<'a>>) -> Vec<QueryNode<'a>> {
+        for filter in &self.filters {
+            nodes = match filter {
+                Filter::Position(position) => nodes.into_iter().skip(position - 1).take(1).collect(),
+                Filter::Compare { key, operator, value } => nodes.into_iter()
+                    .filter(|n| n.matches(key, *operator, value))
+                    .collect(),
+            };
+        }
+        nodes
+    }
+}
+
+fn parse_filter(filter: &str) -> Option<Filter> {
+    let filter = filter.trim();
+    if let Ok(position) = filter.parse::<usize>() {
+        return (position > 0).then_some(Filter::Position(position));
+    }
+    // The leftmost operator outside quotes, so operators inside the value don't split the filter
+    let mut quote = None;
+    let (position, token, operator) = filter.char_indices().find_map(|(i, c)| {
+        match quote {
+            Some(q) if q == c => quote = None,
+            None if c == '"' || c == '\'' => quote = Some(c),
+            _ => {},
+        }
+        if quote.is_some() {
+            return None;
+        }
+        OPERATORS.iter().find(|(token, _)| filter[i..].starts_with(token)).map(|(token, operator)| (i, *token, operator))
+    })?;
+    let (key, value) = (&filter[..position], &filter[position + token.len()..]);
+    let value = value.trim();
+    let value = ['"', '\''].iter()
+        .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)))
+        .unwrap_or(value);
+    let key = key.trim();
+    (!key.is_empty()).then(|| Filter::Compare { key: key.to_string(), operator: *operator, value: value.to_string() })
+}
+
+// Steps separated by dots, each a field name followed by any number of filters in brackets
+fn parse_query(query: &str) -> Result<Vec<Step>, QueryError> {
+    let mut steps = vec![];
+    let mut chars = query.char_indices().peekable();
+    loop {
+        let position = chars.peek().map_or(query.len(), |(i, _)| *i);
+        let mut name = String::new();
+        while let Some((_, c)) = chars.peek().filter(|(_, c)| c.is_alphanumeric() || *c == '_') {
+            name.push(*c);
+            chars.next();
+        }
+        if name.is_empty() {
+            return Err(syntax(position, "expected a field name"));
+        }
+        let mut filters = vec![];
+        while let Some((start, _)) = chars.peek().filter(|(_, c)| *c == '[').copied() {
+            chars.next();
+            let mut filter = String::new();
+            let mut quote = None;
+            loop {
+                match chars.next() {
+                    None => return Err(syntax(start, "unclosed filter")),
+                    Some((_, ']')) if quote.is_none() => break,
+                    Some((_, c)) => {
+                        match quote {
+                            Some(q) if q == c => quote = None,
+                            None if c == '"' || c == '\'' => quote = Some(c),
+                            _ => {},
+                        }
+                        filter.push(c);
+                    },
+                }
+            }
+            filters.push(parse_filter(&filter).ok_or_else(|| syntax(start, "invalid filter"))?);
+        }
+        steps.push(Step { name, filters });
+        match chars.next() {
+            None => return Ok(steps),
+            Some((_, '.')) => {},
+            Some((i, _)) => return Err(syntax(i, "expected . or [")),
+        }
+    }
+}
+
+fn compare(field: &Value, operator: Operator, value: &str) -> bool {
+    let ordering = match field {
+        Value::Number(n) => n.as_f64().zip(value.parse::<f64>().ok()).and_then(|(a, b)| a.partial_cmp(&b)),
+        Value::String(s) => Some(s.as_str().cmp(value)),
+        Value::Bool(b) => value.parse::<bool>().ok().map(|v| b.cmp(&v)),
+        _ => None,
+    };
+    ordering.is_some_and(|o| match operator {
+        Operator::Eq => o == Ordering::Equal,
+        Operator::Ne => o != Ordering::Equal,
+        Operator::Lt => o == Ordering::Less,
+        Operator::Le => o != Ordering::Greater,
+        Operator::Gt => o == Ordering::Greater,
+        Operator::Ge => o != Ordering::Less,
+    })
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+fn extension_nodes<T: HasExtensions>(entity: &T) -> Vec<QueryNode<'_>> {
+    entity.extensions().iter().map(QueryNode::Extension).collect()
+}
+
+impl<'a> QueryNode<'a> {
+    // The node as it is serialized, filters compare against its fields
+    pub fn to_value(&self) -> Value {
+        match self {
+            Self::Competition(x) => to_value(x),
+            Self::Person(x) => to_value(x),
+            Self::Assignment(x) => to_value(x),
+            Self::PersonalBest(x) => to_value(x),
+            Self::Event(x) => to_value(x),
+            Self::Round(x) => to_value(x),
+            Self::Result(x) => to_value(x),
+            Self::Attempt(x) => to_value(x),
+            Self::Venue(x) => to_value(x),
+            Self::Room(x) => to_value(x),
+            Self::Activity(x) => to_value(x),
+            Self::Extension(x) => to_value(x),
+            Self::Value(x) => x.clone(),
+        }
+    }
+
+    fn children(&self, name: &str) -> Option<Vec<QueryNode<'a>>> {
+        Some(match (self, name) {
+            (Self::Competition(c), "persons") => c.persons.iter().map(Self::Person).collect(),
+            (Self::Competition(c), "events") => c.events.iter().map(Self::Event).collect(),
+            (Self::Competition(c), "venues") => c.schedule.venues.iter().map(Self::Venue).collect(),
+            // All activities of the schedule, including nested ones
+            (Self::Competition(c), "activities") => c.schedule.all_activities().into_iter().map(Self::Activity).collect(),
+            (Self::Person(p), "assignments") => p.assignments.iter().map(Self::Assignment).collect(),
+            (Self::Person(p), "personalBests") => p.personal_bests.iter().map(Self::PersonalBest).collect(),
+            (Self::Event(e), "rounds") => e.rounds.iter().map(Self::Round).collect(),
+            (Self::Round(r), "results") => r.results.iter().map(Self::Result).collect(),
+            (Self::Result(r), "attempts") => r.attempts.iter().map(Self::Attempt).collect(),
+            (Self::Venue(v), "rooms") => v.rooms.iter().map(Self::Room).collect(),
+            (Self::Room(r), "activities") => r.activities.iter().map(Self::Activity).collect(),
+            (Self::Activity(a), "childActivities") => a.child_activities.iter().map(Self::Activity).collect(),
+            (Self::Competition(c), "extensions") => extension_nodes(*c),
+            (Self::Person(p), "extensions") => extension_nodes(*p),
+            (Self::Event(e), "extensions") => extension_nodes(*e),
+            (Self::Round(r), "extensions") => extension_nodes(*r),
+            (Self::Venue(v), "extensions") => extension_nodes(*v),
+            (Self::Room(r), "extensions") => extension_nodes(*r),
+            (Self::Activity(a), "extensions") => extension_nodes(*a),
+            _ => return None,
+        })
+    }
+
+    // Typed children if the name is a collection of the node, otherwise the field as JSON with arrays flattened
+    fn select(&self, name: &str) -> Vec<QueryNode<'a>> {
+        if let Some(children) = self.children(name) {
+            return children;
+        }
+        match self.to_value().get(name) {
+            None | Some(Value::Null) => vec![],
+            Some(Value::Array(values)) => values.iter().cloned().map(Self::Value).collect(),
+            Some(value) => vec![Self::Value(value.clone())],
+        }
+    }
+
+    // Keys may be nested with dots, e.g. "registration.status"
+    fn matches(&self, key: &str, operator: Operator, value: &str) -> bool {
+        let node = self.to_value();
+        key.split('.')
+            .try_fold(&node, |v, k| v.get(k))
+            .is_some_and(|field| compare(field, operator, value))
+    }
+}
+
+impl Competition {
+    // Selects parts of the competition with a path of field names and filters, e.g.
+    // "events[id=333].rounds[2].results[ranking<=3]" or "persons[registration.status=accepted].name".
+    // Field names are as in the JSON document. Filters are 1-based positions among the
+    // children of each node, or comparisons of a field with a value, numerically if both are numbers
+    pub fn query(&self, query: &str) -> Result<Vec<QueryNode<'_>>, QueryError> {
+        let mut nodes = vec![QueryNode::Competition(self)];
+        for step in parse_query(query)? {
+            nodes = nodes.iter().flat_map(|n| step.apply(n.select(&step.name))).collect();
+        }
+        Ok(nodes)
+    }
+}
@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+use crate::types::{AttemptResult, Competition, Gender, PersonId, RoundId};
+#[cfg(feature = "private_properties")]
+use crate::types::{Date, Person};
+
+// An age range used for side rankings, e.g. "Under 12" or "Over 40". Bounds are inclusive
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgeCategory {
+    pub name: String,
+    pub min_age: Option<u32>,
+    pub max_age: Option<u32>,
+}
+
+impl AgeCategory {
+    pub fn contains(&self, age: u32) -> bool {
+        self.min_age.is_none_or(|min| age >= min) && self.max_age.is_none_or(|max| age <= max)
+    }
+
+    pub fn over(age: u32) -> Self {
+        Self { name: format!("Over {age}"), min_age: Some(age), max_age: None }
+    }
+
+    // The senior categories of the WCA, each one includes all older ones
+    pub fn senior_categories() -> Vec<Self> {
+        [40, 50, 60].into_iter().map(Self::over).collect()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RankingFilter {
+    pub gender: Option<Gender>,
+    // Ages are computed on the first day of the competition
+    #[cfg(feature = "private_properties")]
+    pub age_category: Option<AgeCategory>,
+}
+
+// A result ranked within a side ranking. Deliberately only contains public data, so side rankings
+// based on birthdates can be published without leaking them
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedResult {
+    pub ranking: u64,
+    pub person_id: PersonId,
+    pub name: String,
+    pub best: AttemptResult,
+    pub average: AttemptResult,
+}
+
+#[cfg(feature = "private_properties")]
+impl Person {
+    pub fn age_on(&self, date: Date) -> u32 {
+        date.years_since(self.birthdate).unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "private_properties")]
+impl Competition {
+    pub fn age_of(&self, person: &Person) -> u32 {
+        person.age_on(self.schedule.start_date)
+    }
+
+    // Oldest senior category of a person, None if they are too young for any or unknown
+    pub fn senior_category(&self, person_id: PersonId) -> Option<AgeCategory> {
+        let age = self.age_of(self.find_person(person_id)?);
+        AgeCategory::senior_categories().into_iter().rev().find(|c| c.contains(age))
+    }
+
+    // Rankings of a round within every senior category that has results, youngest category first
+    pub fn senior_rankings(&self, round_id: &RoundId) -> Vec<(AgeCategory, Vec<RankedResult>)> {
+        AgeCategory::senior_categories().into_iter()
+            .map(|category| {
+                let filter = RankingFilter { age_category: Some(category.clone()), ..Default::default() };
+                let ranking = self.filtered_ranking(round_id, &filter);
+                (category, ranking)
+            })
+            .filter(|(_, ranking)| !ranking.is_empty())
+            .collect()
+    }
+}
+
+impl Competition {
+    // Ranks the results of a round among the competitors matching the filter. Ties share a ranking,
+    // results without a successful or failed attempt are not ranked
+    pub fn filtered_ranking(&self, round_id: &RoundId, filter: &RankingFilter) -> Vec<RankedResult> {
+        let Some(round) = self.find_round(round_id) else {
+            return vec![];
+        };
+        let mut results: Vec<_> = round.results.iter()
+            .filter(|r| r.best != AttemptResult::Skipped)
+            .filter_map(|r| self.find_person(r.person_id).map(|p| (r, p)))
+            .filter(|(_, p)| filter.gender.as_ref().is_none_or(|g| &p.gender == g))
+            .collect();
+        #[cfg(feature = "private_properties")]
+        if let Some(category) = &filter.age_category {
+            results.retain(|(_, p)| category.contains(self.age_of(p)));
+        }
+        results.sort_by(|(a, _), (b, _)| round.compare_results(a, b));
+        let mut ranked: Vec<RankedResult> = vec![];
+        for (position, (result, person)) in results.iter().enumerate() {
+            let ranking = match (ranked.last(), position.checked_sub(1).map(|p| results[p].0)) {
+                (Some(last), Some(previous)) if round.compare_results(previous, result) == Ordering::Equal => last.ranking,
+                _ => position as u64 + 1,
+            };
+            ranked.push(RankedResult {
+                ranking,
+                person_id: result.person_id,
+                name: person.name.clone(),
+                best: result.best,
+                average: result.average,
+            });
+        }
+        ranked
+    }
+}
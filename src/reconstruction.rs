@@ -0,0 +1,272 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::types::{Attempt, EventId, PuzzleType};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+// Which part of the cube a move turns
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MoveKind {
+    // A single outer layer, e.g. R
+    Face,
+    // An outer layer together with the middle layer, e.g. Rw or r
+    Wide,
+    // The middle layer only, e.g. M
+    Slice,
+    // The whole cube, e.g. x
+    Rotation,
+}
+
+// A move on a 3x3x3 cube in WCA notation
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Move {
+    pub notation: String,
+    pub kind: MoveKind,
+    axis: Axis,
+    // Layers (-1, 0, 1 along the axis) turned by the move
+    layers: Vec<i8>,
+    // Number of clockwise quarter turns as seen from the positive side of the axis, 1 to 3
+    quarter_turns: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReconstructionError {
+    InvalidMove(String),
+    UnbalancedParentheses,
+    UnsupportedEvent(EventId),
+}
+
+impl Display for ReconstructionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidMove(m) => write!(f, "Invalid move {m}"),
+            Self::UnbalancedParentheses => write!(f, "Unbalanced parentheses"),
+            Self::UnsupportedEvent(e) => write!(f, "Reconstructions of {e} are not supported"),
+        }
+    }
+}
+
+impl std::error::Error for ReconstructionError {}
+
+impl FromStr for Move {
+    type Err = ReconstructionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ReconstructionError::InvalidMove(s.to_string());
+        // "2'" is common in reconstructions and equivalent to "2"
+        let (base, amount) = if let Some(base) = s.strip_suffix("2'") {
+            (base, 2)
+        } else if let Some(base) = s.strip_suffix('\'') {
+            (base, 3)
+        } else if let Some(base) = s.strip_suffix('2') {
+            (base, 2)
+        } else {
+            (s, 1)
+        };
+        let (face, wide) = match base.strip_suffix('w') {
+            Some(face) => (face, true),
+            None => (base, false),
+        };
+        // (axis, outer layer or 2 for rotations, whether clockwise on that face is clockwise seen from the positive side of the axis)
+        let (axis, layer, positive) = match face {
+            "R" | "r" | "x" => (Axis::X, 1, true),
+            "L" | "l" => (Axis::X, -1, false),
+            "U" | "u" | "y" => (Axis::Y, 1, true),
+            "D" | "d" => (Axis::Y, -1, false),
+            "F" | "f" | "z" => (Axis::Z, 1, true),
+            "B" | "b" => (Axis::Z, -1, false),
+            "M" => (Axis::X, 0, false),
+            "E" => (Axis::Y, 0, false),
+            "S" => (Axis::Z, 0, true),
+            _ => return Err(invalid()),
+        };
+        let (kind, layers) = match face {
+            "x" | "y" | "z" if !wide => (MoveKind::Rotation, vec![-1, 0, 1]),
+            "M" | "E" | "S" if !wide => (MoveKind::Slice, vec![0]),
+            "R" | "L" | "U" | "D" | "F" | "B" if !wide => (MoveKind::Face, vec![layer]),
+            "R" | "L" | "U" | "D" | "F" | "B" => (MoveKind::Wide, vec![layer, 0]),
+            "r" | "l" | "u" | "d" | "f" | "b" if !wide => (MoveKind::Wide, vec![layer, 0]),
+            _ => return Err(invalid()),
+        };
+        Ok(Move {
+            notation: s.to_string(),
+            kind,
+            axis,
+            layers,
+            quarter_turns: if positive { amount } else { 4 - amount },
+        })
+    }
+}
+
+impl Move {
+    pub fn is_half_turn(&self) -> bool {
+        self.quarter_turns == 2
+    }
+
+    pub fn inverse(&self) -> Move {
+        let notation = match (self.is_half_turn(), self.notation.strip_suffix('\'')) {
+            (true, _) => self.notation.clone(),
+            (false, Some(base)) => base.to_string(),
+            (false, None) => format!("{}'", self.notation),
+        };
+        Move { notation, quarter_turns: 4 - self.quarter_turns, ..self.clone() }
+    }
+}
+
+// Splits a reconstruction into its moves. Comments starting with // are ignored. Moves in parentheses are
+// interpreted as moves on the inverse scramble (NISS) and appended to the solution inverted and in reverse order
+pub fn parse_moves(s: &str) -> Result<Vec<Move>, ReconstructionError> {
+    let mut normal = vec![];
+    let mut inverse = vec![];
+    let mut in_parentheses = false;
+    for line in s.lines() {
+        let line = line.split("//").next().unwrap_or_default();
+        for token in line.replace('(', " ( ").replace(')', " ) ").split_whitespace() {
+            match token {
+                "(" if !in_parentheses => in_parentheses = true,
+                ")" if in_parentheses => in_parentheses = false,
+                "(" | ")" => return Err(ReconstructionError::UnbalancedParentheses),
+                token if in_parentheses => inverse.push(Move::from_str(token)?),
+                token => normal.push(Move::from_str(token)?),
+            }
+        }
+    }
+    if in_parentheses {
+        return Err(ReconstructionError::UnbalancedParentheses);
+    }
+    normal.extend(inverse.iter().rev().map(Move::inverse));
+    Ok(normal)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MoveCounts {
+    // Half turn metric as used for fewest moves: rotations count 0, slices 2, everything else 1
+    pub htm: usize,
+    // Quarter turn metric: like HTM, but half turns count twice
+    pub qtm: usize,
+    // Slice turn metric: like HTM, but slices count 1
+    pub stm: usize,
+}
+
+pub fn count_moves(moves: &[Move]) -> MoveCounts {
+    let mut counts = MoveCounts { htm: 0, qtm: 0, stm: 0 };
+    for m in moves {
+        let (htm, stm) = match m.kind {
+            MoveKind::Rotation => (0, 0),
+            MoveKind::Slice => (2, 1),
+            MoveKind::Face | MoveKind::Wide => (1, 1),
+        };
+        counts.htm += htm;
+        counts.stm += stm;
+        counts.qtm += htm * if m.is_half_turn() { 2 } else { 1 };
+    }
+    counts
+}
+
+// A 3x3x3 cube as 54 stickers, each with its position, the direction it faces and its color
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Cube {
+    stickers: Vec<([i8; 3], [i8; 3], u8)>,
+}
+
+const NORMALS: [[i8; 3]; 6] = [[1, 0, 0], [-1, 0, 0], [0, 1, 0], [0, -1, 0], [0, 0, 1], [0, 0, -1]];
+
+impl Cube {
+    fn solved() -> Self {
+        let mut stickers = vec![];
+        for (color, normal) in NORMALS.iter().enumerate() {
+            let axis = normal.iter().position(|c| *c != 0).expect("normal has a non zero component");
+            for a in -1..=1 {
+                for b in -1..=1 {
+                    let mut position = [0; 3];
+                    let others: Vec<usize> = (0..3).filter(|i| *i != axis).collect();
+                    position[others[0]] = a;
+                    position[others[1]] = b;
+                    position[axis] = normal[axis];
+                    stickers.push((position, *normal, color as u8));
+                }
+            }
+        }
+        Cube { stickers }
+    }
+
+    // Clockwise quarter turn seen from the positive side of the axis
+    fn rotate(v: [i8; 3], axis: Axis) -> [i8; 3] {
+        let [x, y, z] = v;
+        match axis {
+            Axis::X => [x, z, -y],
+            Axis::Y => [-z, y, x],
+            Axis::Z => [y, -x, z],
+        }
+    }
+
+    fn apply(&mut self, m: &Move) {
+        let index = match m.axis {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        };
+        for (position, normal, _) in self.stickers.iter_mut() {
+            if !m.layers.contains(&position[index]) {
+                continue;
+            }
+            for _ in 0..m.quarter_turns {
+                *position = Self::rotate(*position, m.axis);
+                *normal = Self::rotate(*normal, m.axis);
+            }
+        }
+    }
+
+    // Every face has a single color, regardless of the orientation of the cube
+    fn is_solved(&self) -> bool {
+        NORMALS.iter().all(|normal| {
+            let mut colors = self.stickers.iter().filter(|(_, n, _)| n == normal).map(|(_, _, c)| c);
+            let first = colors.next();
+            colors.all(|c| Some(c) == first)
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReconstructionReport {
+    pub moves: Vec<Move>,
+    pub counts: MoveCounts,
+    pub solves: bool,
+}
+
+fn is_supported(event_id: &EventId) -> bool {
+    event_id.get_puzzle_type() == PuzzleType::Cube333 && *event_id != EventId::MultiBlind333
+}
+
+// Parses the reconstruction and checks whether it solves the scramble. Only single 3x3x3 Cube scrambles are supported
+pub fn verify_reconstruction(event_id: &EventId, scramble: &str, reconstruction: &str) -> Result<ReconstructionReport, ReconstructionError> {
+    if !is_supported(event_id) {
+        return Err(ReconstructionError::UnsupportedEvent(event_id.clone()));
+    }
+    let scramble = parse_moves(scramble)?;
+    let moves = parse_moves(reconstruction)?;
+    let mut cube = Cube::solved();
+    for m in scramble.iter().chain(moves.iter()) {
+        cube.apply(m);
+    }
+    Ok(ReconstructionReport {
+        counts: count_moves(&moves),
+        solves: cube.is_solved(),
+        moves,
+    })
+}
+
+impl Attempt {
+    pub fn parse_reconstruction(&self) -> Option<Result<Vec<Move>, ReconstructionError>> {
+        self.reconstruction.as_deref().map(parse_moves)
+    }
+
+    pub fn verify_reconstruction(&self, event_id: &EventId, scramble: &str) -> Option<Result<ReconstructionReport, ReconstructionError>> {
+        self.reconstruction.as_deref().map(|r| verify_reconstruction(event_id, scramble, r))
+    }
+}
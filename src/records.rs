@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::regions::{ContinentId, RegionalResult};
+use crate::types::{AttemptResult, Competition, CountryCode, EventId, PersonId, ResultType, RoundId};
+
+#[derive(Debug)]
+pub enum RecordsError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl Display for RecordsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Json(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for RecordsError {}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EventRecords {
+    pub single: Option<AttemptResult>,
+    pub average: Option<AttemptResult>,
+}
+
+impl EventRecords {
+    pub fn get(&self, result_type: &ResultType) -> Option<AttemptResult> {
+        match result_type {
+            ResultType::Single => self.single,
+            ResultType::Average => self.average,
+        }
+    }
+}
+
+// Records at some point in time, in the shape of the WCA's records API. Events are keyed by event id, national
+// records by the WCA id of the country (e.g. "USA") or its ISO 3166-1 alpha-2 code
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordsSnapshot {
+    #[serde(default)]
+    pub world_records: HashMap<String, EventRecords>,
+    #[serde(default)]
+    pub continental_records: HashMap<ContinentId, HashMap<String, EventRecords>>,
+    #[serde(default)]
+    pub national_records: HashMap<String, HashMap<String, EventRecords>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordKind {
+    World,
+    Continental(ContinentId),
+    National(CountryCode),
+}
+
+impl RecordKind {
+    // The abbreviation shown next to results, e.g. "WR", "ER" or "NR"
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Self::World => "WR",
+            Self::Continental(continent_id) => match continent_id.as_str() {
+                "_Africa" => "AfR",
+                "_Asia" => "AsR",
+                "_Europe" => "ER",
+                "_North America" => "NAR",
+                "_Oceania" => "OcR",
+                "_South America" => "SAR",
+                _ => "CR",
+            },
+            Self::National(_) => "NR",
+        }
+    }
+}
+
+// A result of the competition that sets or ties a record
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordTag {
+    pub round_id: RoundId,
+    pub person_id: PersonId,
+    pub result_type: ResultType,
+    pub result: AttemptResult,
+    pub kind: RecordKind,
+}
+
+impl RecordsSnapshot {
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, serde_json::Error> {
+        serde_json::from_reader(reader)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordsError> {
+        let file = File::open(path).map_err(RecordsError::Io)?;
+        Self::from_reader(BufReader::new(file)).map_err(RecordsError::Json)
+    }
+
+    pub fn world(&self, event_id: &EventId, result_type: &ResultType) -> Option<AttemptResult> {
+        self.world_records.get(&event_id.to_string())?.get(result_type)
+    }
+
+    pub fn continental(&self, continent_id: &str, event_id: &EventId, result_type: &ResultType) -> Option<AttemptResult> {
+        self.continental_records.get(continent_id)?.get(&event_id.to_string())?.get(result_type)
+    }
+
+    pub fn national(&self, country_iso2: &str, event_id: &EventId, result_type: &ResultType) -> Option<AttemptResult> {
+        let records = self.national_records.get(country_iso2);
+        #[cfg(feature = "country_data")]
+        let records = records.or_else(|| self.national_records.get(crate::countries::find_country(country_iso2)?.id));
+        records?.get(&event_id.to_string())?.get(result_type)
+    }
+}
+
+// A result is a record if no record exists yet or it is at least as good as the current one
+fn is_record(result: AttemptResult, record: Option<AttemptResult>) -> bool {
+    result.is_success() && record.is_none_or(|record| result <= record)
+}
+
+impl Competition {
+    // Tags results that set or tie a world, continental or national record, only the most significant one per
+    // result. Rounds are processed in order, so a record set in an earlier round raises the bar for later ones.
+    // Results in the same round are compared against the records before the round. Events the snapshot has no world
+    // records for are skipped, missing continental and national records mean that none has been set yet
+    pub fn record_tags(&self, records: &RecordsSnapshot, continents: &HashMap<CountryCode, ContinentId>) -> Vec<RecordTag> {
+        let mut world: HashMap<(String, ResultType), AttemptResult> = HashMap::new();
+        let mut continental: HashMap<(ContinentId, String, ResultType), AttemptResult> = HashMap::new();
+        let mut national: HashMap<(CountryCode, String, ResultType), AttemptResult> = HashMap::new();
+        let results = self.regional_results(continents);
+        let mut tags = vec![];
+        for round_results in results.chunk_by(|a, b| a.round_id == b.round_id) {
+            let round_id = &round_results[0].round_id;
+            let event = round_id.event.to_string();
+            if !records.world_records.contains_key(&event) {
+                continue;
+            }
+            for result_type in [ResultType::Single, ResultType::Average] {
+                let world_record = better(world.get(&(event.clone(), result_type.clone())).copied(),
+                    records.world(&round_id.event, &result_type));
+                let continental_record = |continent_id: &ContinentId| better(continental.get(&(continent_id.clone(), event.clone(), result_type.clone())).copied(),
+                    records.continental(continent_id, &round_id.event, &result_type));
+                let national_record = |country: &CountryCode| better(national.get(&(country.clone(), event.clone(), result_type.clone())).copied(),
+                    records.national(country, &round_id.event, &result_type));
+                let round_tags: Vec<RecordTag> = round_results.iter()
+                    .filter_map(|r| {
+                        let result = *r.get_result(&result_type);
+                        let kind = if is_record(result, world_record) {
+                            RecordKind::World
+                        } else if let Some(continent_id) = r.continent_id.as_ref().filter(|c| is_record(result, continental_record(c))) {
+                            RecordKind::Continental(continent_id.clone())
+                        } else if is_record(result, national_record(&r.country_iso2)) {
+                            RecordKind::National(r.country_iso2.clone())
+                        } else {
+                            return None;
+                        };
+                        Some(RecordTag { round_id: round_id.clone(), person_id: r.person_id, result_type: result_type.clone(), result, kind })
+                    })
+                    .collect();
+                for r in round_results.iter().filter(|r| r.get_result(&result_type).is_success()) {
+                    update_record(&mut world, (event.clone(), result_type.clone()), r, &result_type);
+                    if let Some(continent_id) = &r.continent_id {
+                        update_record(&mut continental, (continent_id.clone(), event.clone(), result_type.clone()), r, &result_type);
+                    }
+                    update_record(&mut national, (r.country_iso2.clone(), event.clone(), result_type.clone()), r, &result_type);
+                }
+                tags.extend(round_tags);
+            }
+        }
+        tags
+    }
+}
+
+fn better(a: Option<AttemptResult>, b: Option<AttemptResult>) -> Option<AttemptResult> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+// Keeps the best result of a region during the competition
+fn update_record<K: std::hash::Hash + Eq>(records: &mut HashMap<K, AttemptResult>, key: K, result: &RegionalResult, result_type: &ResultType) {
+    let result = *result.get_result(result_type);
+    records.entry(key)
+        .and_modify(|record| *record = (*record).min(result))
+        .or_insert(result);
+}
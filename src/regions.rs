@@ -0,0 +1,83 @@
+use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use crate::types::{AttemptResult, Competition, CountryCode, EventId, PersonId, ResultType, RoundId};
+
+// Continent ids as used by the WCA, e.g. "_Europe" or "_North America"
+pub type ContinentId = String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Region {
+    Country,
+    Continent,
+}
+
+// A round result labeled with the competitor's country and continent, the regions its records count for
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionalResult {
+    pub round_id: RoundId,
+    pub person_id: PersonId,
+    pub country_iso2: CountryCode,
+    pub continent_id: Option<ContinentId>,
+    pub best: AttemptResult,
+    pub average: AttemptResult,
+}
+
+impl RegionalResult {
+    pub fn get_result(&self, result_type: &ResultType) -> &AttemptResult {
+        match result_type {
+            ResultType::Single => &self.best,
+            ResultType::Average => &self.average,
+        }
+    }
+
+    pub fn region(&self, region: &Region) -> Option<&str> {
+        match region {
+            Region::Country => Some(&self.country_iso2),
+            Region::Continent => self.continent_id.as_deref(),
+        }
+    }
+}
+
+impl Competition {
+    // Labels every result with the country of the competitor and the continent it belongs to according to `continents`.
+    // Results of persons that are not part of the competition are skipped
+    pub fn regional_results(&self, continents: &HashMap<CountryCode, ContinentId>) -> Vec<RegionalResult> {
+        self.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .flat_map(|round| round.results.iter().map(move |r| (round, r)))
+            .filter_map(|(round, result)| {
+                let person = self.find_person(result.person_id)?;
+                Some(RegionalResult {
+                    round_id: round.id.clone(),
+                    person_id: result.person_id,
+                    country_iso2: person.country_iso2.clone(),
+                    continent_id: continents.get(&person.country_iso2).cloned(),
+                    best: result.best,
+                    average: result.average,
+                })
+            })
+            .collect()
+    }
+
+    // The best successful result of an event per country or continent, e.g. for "best in country at this competition"
+    // summaries. Ties keep the result of the earliest round
+    pub fn best_per_region(&self, event_id: &EventId, result_type: &ResultType, region: &Region, continents: &HashMap<CountryCode, ContinentId>) -> BTreeMap<String, RegionalResult> {
+        let mut best: BTreeMap<String, RegionalResult> = BTreeMap::new();
+        for result in self.regional_results(continents) {
+            if &result.round_id.event != event_id || !result.get_result(result_type).is_success() {
+                continue;
+            }
+            let Some(key) = result.region(region).map(str::to_string) else {
+                continue;
+            };
+            match best.get(&key) {
+                Some(current) if current.get_result(result_type) <= result.get_result(result_type) => {},
+                _ => {
+                    best.insert(key, result);
+                },
+            }
+        }
+        best
+    }
+}
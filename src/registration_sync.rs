@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{Competition, CompetitionId, CountryCode, Date, EventId, Gender, Person, PersonId, Registration, RegistrationStatus, WCAId, WCARegistrationId, WCAUserId};
+
+// A registration as returned by the WCA v0 registrations API. The user is only included when requested
+// with sufficient permissions, private fields only for organizers and delegates
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApiRegistration {
+    pub id: WCARegistrationId,
+    pub competition_id: CompetitionId,
+    pub user_id: WCAUserId,
+    pub event_ids: Vec<EventId>,
+    // The public endpoint only lists accepted registrations and omits the status
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub guests: Option<u32>,
+    #[serde(default)]
+    pub comments: Option<String>,
+    #[serde(default)]
+    pub administrative_notes: Option<String>,
+    #[serde(default)]
+    pub user: Option<ApiUser>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApiUser {
+    pub id: WCAUserId,
+    pub name: String,
+    pub wca_id: Option<WCAId>,
+    pub country_iso2: CountryCode,
+    pub gender: Gender,
+    #[serde(default)]
+    pub dob: Option<Date>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    pub added: Vec<PersonId>,
+    pub updated: Vec<PersonId>,
+    // Registrations without user data for persons not yet part of the competition. With the private_properties
+    // feature also those whose user data lacks the birthdate, which persons can't be created without
+    pub skipped: Vec<WCARegistrationId>,
+    // Persons with a registration that is not part of the payload. They are left unchanged,
+    // since the public endpoint does not list registrations that are not accepted
+    pub missing: Vec<PersonId>,
+}
+
+impl ApiRegistration {
+    pub fn registration_status(&self) -> RegistrationStatus {
+        match self.status.as_deref() {
+            None | Some("accepted") => RegistrationStatus::Accepted,
+            Some("deleted") | Some("cancelled") | Some("rejected") => RegistrationStatus::Deleted,
+            Some(_) => RegistrationStatus::Pending,
+        }
+    }
+
+    pub fn to_registration(&self) -> Registration {
+        Registration {
+            wca_registration_id: self.id,
+            event_ids: self.event_ids.clone(),
+            status: self.registration_status(),
+            #[cfg(feature = "private_properties")]
+            guests: self.guests.unwrap_or_default(),
+            #[cfg(feature = "private_properties")]
+            comments: self.comments.clone().unwrap_or_default(),
+            #[cfg(feature = "private_properties")]
+            administrative_notes: self.administrative_notes.clone().unwrap_or_default(),
+            is_competing: !self.event_ids.is_empty(),
+        }
+    }
+
+    // Keeps the private fields of the previous registration that are not part of the payload
+    fn merge_registration(&self, previous: Option<&Registration>) -> Registration {
+        let registration = self.to_registration();
+        #[cfg(feature = "private_properties")]
+        if let Some(previous) = previous {
+            return Registration {
+                guests: self.guests.unwrap_or(previous.guests),
+                comments: self.comments.clone().unwrap_or_else(|| previous.comments.clone()),
+                administrative_notes: self.administrative_notes.clone().unwrap_or_else(|| previous.administrative_notes.clone()),
+                ..registration
+            };
+        }
+        #[cfg(not(feature = "private_properties"))]
+        let _ = previous;
+        registration
+    }
+
+    // Creates a person without assignments or personal bests. Returns None if the payload doesn't include the user,
+    // or with the private_properties feature if it doesn't include the birthdate of the user
+    pub fn to_person(&self, registrant_id: PersonId) -> Option<Person> {
+        let user = self.user.as_ref()?;
+        Some(Person {
+            registrant_id: Some(registrant_id),
+            name: user.name.clone(),
+            wca_user_id: user.id,
+            wca_id: user.wca_id.clone(),
+            country_iso2: user.country_iso2.clone(),
+            gender: user.gender.clone(),
+            #[cfg(feature = "private_properties")]
+            birthdate: user.dob?,
+            #[cfg(feature = "private_properties")]
+            email: user.email.clone().unwrap_or_default(),
+            avatar: None,
+            roles: vec![],
+            registration: Some(self.to_registration()),
+            assignments: vec![],
+            personal_bests: vec![],
+            extensions: vec![],
+        })
+    }
+}
+
+impl Competition {
+    // Updates the registrations of existing persons (matched by WCA user id) and adds persons for new registrations.
+    // Assignments, roles, personal bests and extensions of existing persons are preserved, private fields are only
+    // overwritten if they are part of the payload.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(competition_id = %self.id, registrations = registrations.len())))]
+    pub fn sync_registrations(&mut self, registrations: &[ApiRegistration]) -> SyncReport {
+        let mut report = SyncReport::default();
+        let mut next_registrant_id = self.persons.iter().filter_map(|p| p.registrant_id).max().unwrap_or_default() + 1;
+        for api_registration in registrations {
+            let Some(person) = self.persons.iter_mut().find(|p| p.wca_user_id == api_registration.user_id) else {
+                match api_registration.to_person(next_registrant_id) {
+                    Some(person) => {
+                        trace_change!(person_id = next_registrant_id, "added person");
+                        report.added.push(next_registrant_id);
+                        self.persons.push(person);
+                        next_registrant_id += 1;
+                    },
+                    None => report.skipped.push(api_registration.id),
+                }
+                continue;
+            };
+            let registration = api_registration.merge_registration(person.registration.as_ref());
+            if let Some(user) = &api_registration.user {
+                person.name = user.name.clone();
+                person.wca_id = user.wca_id.clone();
+                person.country_iso2 = user.country_iso2.clone();
+                person.gender = user.gender.clone();
+            }
+            if person.registration.as_ref() != Some(&registration) {
+                person.registration = Some(registration);
+                if let Some(registrant_id) = person.registrant_id {
+                    trace_change!(person_id = registrant_id, "updated registration");
+                    report.updated.push(registrant_id);
+                }
+            }
+        }
+        report.missing = self.persons.iter()
+            .filter(|p| p.registration.is_some())
+            .filter(|p| !registrations.iter().any(|r| r.user_id == p.wca_user_id))
+            .filter_map(|p| p.registrant_id)
+            .collect();
+        report
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use serde::Serialize;
+use serde_json::Value;
+use crate::diff::escape;
+use crate::types::{ActivityCode, ActivityId, Attempt, AssignmentCode, AttemptResult, Competition, EventId, Person, PersonId, RoundId, RoundResult};
+
+// A change made by one of the fixers
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Repair {
+    ActivityIdChanged { name: String, from: ActivityId, to: ActivityId },
+    ActivityCodeNormalized { path: String, from: String, to: String },
+    RoundsReordered { event_id: EventId },
+    ScrambleSetCountChanged { round_id: RoundId, from: u32, to: u32 },
+    StationNumberChanged { person_id: PersonId, activity_id: ActivityId, from: u32, to: Option<u32> },
+    // Rounds in which both persons had different entered attempts, see `Competition::merge_duplicate_persons`
+    PersonsMerged { name: String, kept: Option<PersonId>, merged: Option<PersonId>, conflicting_results: Vec<RoundId> },
+}
+
+fn registrant(person_id: &Option<PersonId>) -> String {
+    person_id.map_or_else(|| "no registrant id".to_string(), |id| format!("registrant {id}"))
+}
+
+impl Display for Repair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ActivityIdChanged { name, from, to } => write!(f, "Changed duplicate id {from} of activity {name} to {to}"),
+            Self::ActivityCodeNormalized { path, from, to } => write!(f, "{path}: changed {from} to {to}"),
+            Self::RoundsReordered { event_id } => write!(f, "Sorted the rounds of event {event_id}"),
+            Self::ScrambleSetCountChanged { round_id, from, to } => write!(f, "Changed the scramble set count of round {round_id} from {from} to {to}"),
+            Self::StationNumberChanged { person_id, activity_id, from, to: Some(to) } => write!(f, "Moved person {person_id} from station {from} to {to} in activity {activity_id}"),
+            Self::StationNumberChanged { person_id, activity_id, from, to: None } => write!(f, "Removed invalid station {from} of person {person_id} in activity {activity_id}"),
+            Self::PersonsMerged { name, kept, merged, conflicting_results } if conflicting_results.is_empty() => write!(f, "Merged duplicate person {name} ({}) into {}", registrant(merged), registrant(kept)),
+            Self::PersonsMerged { name, kept, merged, conflicting_results } => {
+                let rounds: Vec<String> = conflicting_results.iter().map(|r| r.to_string()).collect();
+                write!(f, "Merged duplicate person {name} ({}) into {}, their results in {} conflict and the attempts of the more complete one were kept", registrant(merged), registrant(kept), rounds.join(", "))
+            },
+        }
+    }
+}
+
+// Lowercases activity codes and round ids of a raw document that only parse in lowercase, e.g. "333-R1". Must run
+// before parsing, as such documents are rejected otherwise
+pub fn normalize_activity_codes(value: &mut Value) -> Vec<Repair> {
+    let mut repairs = vec![];
+    normalize_codes(value, "", &mut repairs);
+    repairs
+}
+
+fn normalize_codes(value: &mut Value, path: &str, repairs: &mut Vec<Repair>) {
+    match value {
+        Value::Object(map) => {
+            let is_round = path.rsplit('/').nth(1) == Some("rounds");
+            for (key, value) in map.iter_mut() {
+                let path = format!("{path}/{}", escape(key));
+                let parses = |code: &str| match key.as_str() {
+                    "activityCode" => code.parse::<ActivityCode>().is_ok(),
+                    "id" if is_round => code.parse::<RoundId>().is_ok(),
+                    _ => false,
+                };
+                match value {
+                    Value::String(code) if !parses(code) && parses(&code.to_lowercase()) => {
+                        let normalized = code.to_lowercase();
+                        repairs.push(Repair::ActivityCodeNormalized { path, from: code.clone(), to: normalized.clone() });
+                        *code = normalized;
+                    },
+                    _ => normalize_codes(value, &path, repairs),
+                }
+            }
+        },
+        Value::Array(values) => {
+            for (i, value) in values.iter_mut().enumerate() {
+                normalize_codes(value, &format!("{path}/{i}"), repairs);
+            }
+        },
+        _ => {},
+    }
+}
+
+impl Competition {
+    // Runs all fixers of parsed documents
+    pub fn repair(&mut self) -> Vec<Repair> {
+        let mut repairs = self.merge_duplicate_persons();
+        repairs.extend(self.deduplicate_activity_ids());
+        repairs.extend(self.sort_rounds());
+        repairs.extend(self.reconcile_scramble_set_counts());
+        repairs.extend(self.clamp_station_numbers(None));
+        repairs
+    }
+
+    // Merges persons sharing a WCA account or WCA id, as created by importing registrations twice, into the first
+    // of them. Roles, registered events, assignments and personal bests are combined and results are moved to the
+    // first person. If both have a result in the same round, they are combined attempt by attempt. Where both
+    // entered a different attempt, the one of the result with more entered attempts is kept and the round is
+    // reported. References to persons in extensions are not updated
+    pub fn merge_duplicate_persons(&mut self) -> Vec<Repair> {
+        let mut repairs = vec![];
+        let mut i = 0;
+        while i < self.persons.len() {
+            let person = &self.persons[i];
+            match self.persons[..i].iter().position(|p| p.wca_user_id == person.wca_user_id || (p.wca_id.is_some() && p.wca_id == person.wca_id)) {
+                Some(first) => {
+                    let duplicate = self.persons.remove(i);
+                    repairs.push(self.merge_person(first, duplicate));
+                },
+                None => i += 1,
+            }
+        }
+        repairs
+    }
+
+    fn merge_person(&mut self, index: usize, duplicate: Person) -> Repair {
+        let person = &mut self.persons[index];
+        let kept = person.registrant_id.or(duplicate.registrant_id);
+        person.registrant_id = kept;
+        person.wca_id = person.wca_id.take().or(duplicate.wca_id);
+        person.avatar = person.avatar.take().or(duplicate.avatar);
+        extend_unique(&mut person.roles, duplicate.roles, |a, b| a == b);
+        extend_unique(&mut person.assignments, duplicate.assignments, |a, b| a.activity_id == b.activity_id && a.assignment_code == b.assignment_code);
+        extend_unique(&mut person.personal_bests, duplicate.personal_bests, |a, b| a.event_id == b.event_id && a._type == b._type);
+        extend_unique(&mut person.extensions, duplicate.extensions, |a, b| a == b);
+        match (&mut person.registration, duplicate.registration) {
+            (Some(registration), Some(other)) => extend_unique(&mut registration.event_ids, other.event_ids, |a, b| a == b),
+            (registration @ None, other) => *registration = other,
+            _ => {},
+        }
+        let name = person.name.clone();
+        let mut conflicting_results = vec![];
+        if let (Some(kept), Some(merged)) = (kept, duplicate.registrant_id) {
+            for round in self.events.iter_mut().flat_map(|e| e.rounds.iter_mut()) {
+                let kept_result = round.results.iter().position(|r| r.person_id == kept);
+                let merged_result = round.results.iter().enumerate()
+                    .find(|(i, r)| r.person_id == merged && Some(*i) != kept_result)
+                    .map(|(i, _)| i);
+                match (kept_result, merged_result) {
+                    (None, Some(i)) => round.results[i].person_id = kept,
+                    (Some(k), Some(i)) => {
+                        let other = round.results.remove(i);
+                        let k = if i < k { k - 1 } else { k };
+                        if merge_attempts(&mut round.results[k], other) {
+                            conflicting_results.push(round.id.clone());
+                        }
+                        #[cfg(all(feature = "parse_attempt_result", feature = "parse_puzzle_type"))]
+                        round.update_results();
+                    },
+                    _ => {},
+                }
+            }
+        }
+        trace_change!(name = %name, kept = ?kept, merged = ?duplicate.registrant_id, "merged duplicate person");
+        Repair::PersonsMerged { name, kept, merged: duplicate.registrant_id, conflicting_results }
+    }
+
+    // Gives every activity but the first with an already used id a new one. Assignments keep referring to the
+    // first activity with the id
+    pub fn deduplicate_activity_ids(&mut self) -> Vec<Repair> {
+        let mut next_id = self.schedule.all_activities().iter().map(|a| a.id).max().unwrap_or_default() + 1;
+        let mut seen = HashSet::new();
+        let mut repairs = vec![];
+        self.schedule.for_each_activity_mut(|activity| {
+            if !seen.insert(activity.id) {
+                trace_change!(from = activity.id, to = next_id, "changed duplicate activity id");
+                repairs.push(Repair::ActivityIdChanged { name: activity.name.clone(), from: activity.id, to: next_id });
+                activity.id = next_id;
+                next_id += 1;
+            }
+        });
+        repairs
+    }
+
+    // Sorts the rounds of every event by their number
+    pub fn sort_rounds(&mut self) -> Vec<Repair> {
+        self.events.iter_mut()
+            .filter(|e| !e.rounds.is_sorted_by_key(|r| r.id.round))
+            .map(|event| {
+                event.rounds.sort_by_key(|r| r.id.round);
+                trace_change!(event_id = %event.id, "sorted rounds");
+                Repair::RoundsReordered { event_id: event.id.clone() }
+            })
+            .collect()
+    }
+
+    // Sets the scramble set count of every round to the number of its scramble sets, or if it has none to the
+    // number of distinct scramble sets of its activities
+    pub fn reconcile_scramble_set_counts(&mut self) -> Vec<Repair> {
+        let mut scheduled_sets: HashMap<RoundId, HashSet<_>> = HashMap::new();
+        for activity in self.schedule.all_activities() {
+            if let (ActivityCode::Official(code), Some(scramble_set_id)) = (&activity.activity_code, activity.scramble_set_id) {
+                if let Some(round) = code.round {
+                    scheduled_sets.entry(RoundId { event: code.event.clone(), round }).or_default().insert(scramble_set_id);
+                }
+            }
+        }
+        let mut repairs = vec![];
+        for round in self.events.iter_mut().flat_map(|e| e.rounds.iter_mut()) {
+            let count = match round.scramble_sets.len() {
+                0 => scheduled_sets.get(&round.id).map_or(0, |sets| sets.len()),
+                count => count,
+            } as u32;
+            if count > 0 && count != round.scramble_set_count {
+                trace_change!(round_id = %round.id, from = round.scramble_set_count, to = count, "changed scramble set count");
+                repairs.push(Repair::ScrambleSetCountChanged { round_id: round.id.clone(), from: round.scramble_set_count, to: count });
+                round.scramble_set_count = count;
+            }
+        }
+        repairs
+    }
+
+    // Moves competitors with station 0, a station already taken by another competitor of the activity or, if given,
+    // a station above `max_station` to the lowest free station. The station is removed if none is free
+    pub fn clamp_station_numbers(&mut self, max_station: Option<u32>) -> Vec<Repair> {
+        let mut taken: HashMap<ActivityId, HashSet<u32>> = HashMap::new();
+        for assignment in self.persons.iter().flat_map(|p| p.assignments.iter()).filter(|a| a.assignment_code == AssignmentCode::Competitor) {
+            if let Some(station) = assignment.station_number {
+                taken.entry(assignment.activity_id).or_default().insert(station);
+            }
+        }
+        let mut kept: HashMap<ActivityId, HashSet<u32>> = HashMap::new();
+        let mut repairs = vec![];
+        for person in self.persons.iter_mut() {
+            let Some(person_id) = person.registrant_id else {
+                continue;
+            };
+            for assignment in person.assignments.iter_mut().filter(|a| a.assignment_code == AssignmentCode::Competitor) {
+                let Some(from) = assignment.station_number else {
+                    continue;
+                };
+                let in_range = from > 0 && max_station.is_none_or(|max| from <= max);
+                // The first competitor with a station keeps it
+                if in_range && kept.entry(assignment.activity_id).or_default().insert(from) {
+                    continue;
+                }
+                let taken = taken.entry(assignment.activity_id).or_default();
+                let to = (1..=max_station.unwrap_or(u32::MAX)).find(|s| !taken.contains(s));
+                taken.extend(to);
+                kept.entry(assignment.activity_id).or_default().extend(to);
+                trace_change!(person_id, activity_id = assignment.activity_id, from, to = ?to, "clamped station number");
+                repairs.push(Repair::StationNumberChanged { person_id, activity_id: assignment.activity_id, from, to });
+                assignment.station_number = to;
+            }
+        }
+        repairs
+    }
+}
+
+#[cfg(feature = "parse_attempt_result")]
+const SKIPPED: AttemptResult = AttemptResult::Skipped;
+#[cfg(not(feature = "parse_attempt_result"))]
+const SKIPPED: AttemptResult = 0;
+
+// Takes over the attempts of `other` that weren't entered in `result`. Returns whether both entered a different
+// attempt, in which case the attempt of the result with more entered attempts is kept
+fn merge_attempts(result: &mut RoundResult, other: RoundResult) -> bool {
+    let entered = |r: &RoundResult| r.attempts.iter().filter(|a| a.result != SKIPPED).count();
+    let prefer_other = entered(&other) > entered(result);
+    let mut conflict = false;
+    for (i, attempt) in other.attempts.into_iter().enumerate().filter(|(_, a)| a.result != SKIPPED) {
+        if result.attempts.len() <= i {
+            result.attempts.resize(i + 1, Attempt { result: SKIPPED, reconstruction: None });
+        }
+        let current = &mut result.attempts[i];
+        if current.result == SKIPPED {
+            *current = attempt;
+        } else if *current != attempt {
+            conflict = true;
+            if prefer_other {
+                *current = attempt;
+            }
+        }
+    }
+    conflict
+}
+
+fn extend_unique<T>(items: &mut Vec<T>, other: Vec<T>, same: impl Fn(&T, &T) -> bool) {
+    for item in other {
+        if !items.iter().any(|i| same(i, &item)) {
+            items.push(item);
+        }
+    }
+}
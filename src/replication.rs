@@ -0,0 +1,401 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::types::{Attempt, AttemptResult, Competition, DateTime, Extension, PersonId, Round, RoundId, RoundResult};
+
+pub const EXTENSION_ID: &str = crate::extensions::ATTEMPT_CLOCKS_ID;
+pub const SPEC_URL: &str = crate::extensions::ATTEMPT_CLOCKS_SPEC_URL;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplicationError {
+    // Persons are part of a round once they have a result in it, e.g. after advancing to it
+    UnknownPerson { round_id: RoundId, person_id: PersonId },
+    // Attempt indices are zero based and can't reach the number of attempts of the round's format
+    InvalidAttempt { round_id: RoundId, attempt_index: usize },
+}
+
+impl Display for ReplicationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownPerson { round_id, person_id } => write!(f, "Person {person_id} is not part of round {round_id}"),
+            Self::InvalidAttempt { round_id, attempt_index } => write!(f, "Round {round_id} has no attempt {}", attempt_index + 1),
+        }
+    }
+}
+
+impl std::error::Error for ReplicationError {}
+
+// Version of an attempt as the number of writes seen from every device
+pub type VectorClock = BTreeMap<String, u64>;
+
+// Attempts can't have extensions, so the clocks of all attempts of a round are stored on the round
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttemptClocksExtension {
+    pub id: MustBe!("wcif-rs.AttemptClocks"),
+    pub spec_url: String,
+    pub data: AttemptClocks,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttemptClocks {
+    pub entries: Vec<AttemptClock>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttemptClock {
+    pub person_id: PersonId,
+    // Zero based
+    pub attempt_index: usize,
+    pub clock: VectorClock,
+    // Device and time of the last write, to break ties between concurrent writes
+    pub device_id: String,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
+    pub entered_at: DateTime,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    // Attempts taken over from the other replica, as round, person and attempt index
+    pub updated: Vec<(RoundId, PersonId, usize)>,
+    // Attempts written on both replicas without one seeing the other, resolved by the later write
+    pub conflicts: Vec<(RoundId, PersonId, usize)>,
+}
+
+// None if the clocks are concurrent
+fn compare_clocks(a: &VectorClock, b: &VectorClock) -> Option<Ordering> {
+    let mut order = Ordering::Equal;
+    for device in a.keys().chain(b.keys()) {
+        let (x, y) = (a.get(device).unwrap_or(&0), b.get(device).unwrap_or(&0));
+        match (order, x.cmp(y)) {
+            (_, Ordering::Equal) => {},
+            (Ordering::Equal, o) => order = o,
+            (o, p) if o != p => return None,
+            _ => {},
+        }
+    }
+    Some(order)
+}
+
+fn merge_clocks(a: &VectorClock, b: &VectorClock) -> VectorClock {
+    let mut merged = a.clone();
+    for (device, count) in b {
+        let entry = merged.entry(device.clone()).or_insert(0);
+        *entry = (*entry).max(*count);
+    }
+    merged
+}
+
+// Orders concurrent writes the same way on every replica: an entered attempt always wins against a skipped one,
+// then the later write, then the greater device id and finally the greater raw result, for attempts that were
+// never written with a clock
+fn tie_break(a: (Option<&AttemptClock>, AttemptResult), b: (Option<&AttemptClock>, AttemptResult)) -> Ordering {
+    let key = |(clock, result): (Option<&AttemptClock>, AttemptResult)| (result != AttemptResult::Skipped, clock.map(|c| c.entered_at), clock.map(|c| c.device_id.clone()), result.to_raw());
+    key(a).cmp(&key(b))
+}
+
+impl Round {
+    pub fn attempt_clocks(&self) -> Option<&AttemptClocks> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::AttemptClocks(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    fn attempt_clocks_mut(&mut self) -> &mut AttemptClocks {
+        if self.attempt_clocks().is_none() {
+            self.extensions.push(Extension::AttemptClocks(AttemptClocksExtension {
+                id: Default::default(),
+                spec_url: SPEC_URL.to_string(),
+                data: AttemptClocks::default(),
+            }));
+        }
+        self.extensions.iter_mut().find_map(|e| match e {
+            Extension::AttemptClocks(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("attempt clocks extension was just added")
+    }
+
+    pub fn attempt_clock(&self, person_id: PersonId, attempt_index: usize) -> Option<&AttemptClock> {
+        self.attempt_clocks()?.entries.iter().find(|e| e.person_id == person_id && e.attempt_index == attempt_index)
+    }
+
+    fn attempt_result(&self, person_id: PersonId, attempt_index: usize) -> Option<&Attempt> {
+        self.results.iter().find(|r| r.person_id == person_id)?.attempts.get(attempt_index)
+    }
+
+    fn set_attempt(&mut self, person_id: PersonId, attempt_index: usize, attempt: Attempt, clock: Option<AttemptClock>) -> Result<(), ReplicationError> {
+        if attempt_index >= self.format.expected_solve_count() as usize {
+            return Err(ReplicationError::InvalidAttempt { round_id: self.id.clone(), attempt_index });
+        }
+        let Some(result) = self.results.iter_mut().find(|r| r.person_id == person_id) else {
+            return Err(ReplicationError::UnknownPerson { round_id: self.id.clone(), person_id });
+        };
+        if result.attempts.len() <= attempt_index {
+            result.attempts.resize(attempt_index + 1, Attempt { result: AttemptResult::Skipped, reconstruction: None });
+        }
+        result.attempts[attempt_index] = attempt;
+        if clock.is_some() || self.attempt_clocks().is_some() {
+            let clocks = self.attempt_clocks_mut();
+            clocks.entries.retain(|e| e.person_id != person_id || e.attempt_index != attempt_index);
+            clocks.entries.extend(clock);
+        }
+        Ok(())
+    }
+
+    // Sets an attempt on this replica, advancing the clock of the device. All writes must go through here for merges
+    // to see them
+    pub fn record_attempt(&mut self, person_id: PersonId, attempt_index: usize, attempt: Attempt, device_id: &str, now: DateTime) -> Result<(), ReplicationError> {
+        let mut clock = self.attempt_clock(person_id, attempt_index).map(|c| c.clock.clone()).unwrap_or_default();
+        *clock.entry(device_id.to_string()).or_insert(0) += 1;
+        self.set_attempt(person_id, attempt_index, attempt, Some(AttemptClock {
+            person_id,
+            attempt_index,
+            clock,
+            device_id: device_id.to_string(),
+            entered_at: now,
+        }))?;
+        self.update_results();
+        Ok(())
+    }
+
+    // Merges the attempts of another replica of the round. Per attempt, the write that has seen the other wins,
+    // concurrent writes are resolved by `tie_break`. Merging is commutative, so all replicas converge regardless
+    // of the order they are merged in. Persons added to the round on the other replica are added here too, attempts
+    // the round's format doesn't have are left out
+    pub fn merge_replica(&mut self, other: &Round) -> MergeReport {
+        let mut report = MergeReport::default();
+        let added: Vec<PersonId> = other.results.iter()
+            .map(|r| r.person_id)
+            .filter(|id| !self.results.iter().any(|r| r.person_id == *id))
+            .collect();
+        for person_id in &added {
+            self.results.push(RoundResult {
+                person_id: *person_id,
+                ranking: None,
+                attempts: vec![],
+                best: AttemptResult::Skipped,
+                average: AttemptResult::Skipped,
+            });
+        }
+        let expected = self.format.expected_solve_count() as usize;
+        let mut keys: Vec<(PersonId, usize)> = other.results.iter()
+            .flat_map(|r| (0..r.attempts.len()).map(|i| (r.person_id, i)))
+            .chain(other.attempt_clocks().iter().flat_map(|c| c.entries.iter()).map(|e| (e.person_id, e.attempt_index)))
+            .filter(|(person_id, attempt_index)| *attempt_index < expected && self.results.iter().any(|r| r.person_id == *person_id))
+            .collect();
+        keys.sort();
+        keys.dedup();
+        for (person_id, attempt_index) in keys {
+            let skipped = Attempt { result: AttemptResult::Skipped, reconstruction: None };
+            let ours = self.attempt_result(person_id, attempt_index).cloned().unwrap_or(skipped.clone());
+            let theirs = other.attempt_result(person_id, attempt_index).cloned().unwrap_or(skipped);
+            let our_clock = self.attempt_clock(person_id, attempt_index).cloned();
+            let their_clock = other.attempt_clock(person_id, attempt_index);
+            let empty = VectorClock::new();
+            let order = compare_clocks(our_clock.as_ref().map_or(&empty, |c| &c.clock), their_clock.map_or(&empty, |c| &c.clock));
+            let take_theirs = match order {
+                Some(Ordering::Less) => true,
+                Some(Ordering::Greater) => false,
+                Some(Ordering::Equal) if ours == theirs => false,
+                _ => {
+                    report.conflicts.push((self.id.clone(), person_id, attempt_index));
+                    tie_break((our_clock.as_ref(), ours.result), (their_clock, theirs.result)) == Ordering::Less
+                },
+            };
+            if !take_theirs && their_clock.is_none() {
+                continue;
+            }
+            let winner = if take_theirs { their_clock } else { our_clock.as_ref() };
+            // Attempts never written with a clock on either replica stay without one
+            let clock = (our_clock.is_some() || their_clock.is_some()).then(|| AttemptClock {
+                person_id,
+                attempt_index,
+                clock: merge_clocks(our_clock.as_ref().map_or(&empty, |c| &c.clock), their_clock.map_or(&empty, |c| &c.clock)),
+                device_id: winner.map(|c| c.device_id.clone()).unwrap_or_default(),
+                entered_at: winner.map_or(DateTime::UNIX_EPOCH, |c| c.entered_at),
+            });
+            if take_theirs && ours != theirs {
+                report.updated.push((self.id.clone(), person_id, attempt_index));
+            }
+            if our_clock != clock || take_theirs {
+                self.set_attempt(person_id, attempt_index, if take_theirs { theirs } else { ours }, clock)
+                    .expect("person and attempt index were checked");
+            }
+        }
+        if !report.updated.is_empty() || !added.is_empty() {
+            self.update_results();
+        }
+        report
+    }
+}
+
+impl Competition {
+    // Merges every round of another replica of the competition. Rounds that only exist in the other replica are
+    // left out, the schedule of events is not replicated
+    pub fn merge_replica(&mut self, other: &Competition) -> MergeReport {
+        let mut report = MergeReport::default();
+        for round in other.events.iter().flat_map(|e| e.rounds.iter()) {
+            if let Some(ours) = self.find_round_mut(&round.id) {
+                let round_report = ours.merge_replica(round);
+                report.updated.extend(round_report.updated);
+                report.conflicts.extend(round_report.conflicts);
+            }
+        }
+        trace_change!(updated = report.updated.len(), conflicts = report.conflicts.len(), "merged replica");
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    fn round(attempts: &[(PersonId, &[i64])]) -> Round {
+        let results: Vec<_> = attempts.iter()
+            .map(|(person_id, results)| json!({
+                "personId": person_id,
+                "ranking": null,
+                "attempts": results.iter().map(|r| json!({ "result": r, "reconstruction": null })).collect::<Vec<_>>(),
+                "best": 0,
+                "average": 0,
+            }))
+            .collect();
+        serde_json::from_value(json!({
+            "id": "333-r1",
+            "format": "a",
+            "timeLimit": null,
+            "cutoff": null,
+            "advancementCondition": null,
+            "results": results,
+            "scrambleSetCount": 1,
+        })).unwrap()
+    }
+
+    fn at(seconds: i64) -> DateTime {
+        DateTime::from_timestamp(1_700_000_000 + seconds, 0).unwrap()
+    }
+
+    fn record(round: &mut Round, person_id: PersonId, attempt_index: usize, centiseconds: u32, device_id: &str, seconds: i64) {
+        let attempt = Attempt { result: AttemptResult::Success(centiseconds), reconstruction: None };
+        round.record_attempt(person_id, attempt_index, attempt, device_id, at(seconds)).unwrap();
+    }
+
+    // Results and clocks independent of the order they were added in
+    fn state(round: &Round) -> (Vec<RoundResult>, Vec<AttemptClock>) {
+        let mut results = round.results.clone();
+        results.sort_by_key(|r| r.person_id);
+        let mut clocks = round.attempt_clocks().map(|c| c.entries.clone()).unwrap_or_default();
+        clocks.sort_by_key(|c| (c.person_id, c.attempt_index));
+        (results, clocks)
+    }
+
+    fn merged(replicas: &[&Round]) -> Round {
+        let mut round = replicas[0].clone();
+        for replica in &replicas[1..] {
+            round.merge_replica(replica);
+        }
+        round
+    }
+
+    #[test]
+    fn two_replicas_converge() {
+        let base = round(&[(1, &[]), (2, &[])]);
+        let (mut a, mut b) = (base.clone(), base);
+        record(&mut a, 1, 0, 1000, "a", 1);
+        record(&mut b, 1, 0, 1100, "b", 2);
+        record(&mut a, 2, 0, 900, "a", 3);
+        record(&mut b, 2, 1, 950, "b", 4);
+
+        let (ab, ba) = (merged(&[&a, &b]), merged(&[&b, &a]));
+        assert_eq!(state(&ab), state(&ba));
+        assert_eq!(ab.attempt_result(1, 0).unwrap().result, AttemptResult::Success(1100));
+        assert_eq!(ab.attempt_result(2, 0).unwrap().result, AttemptResult::Success(900));
+        assert_eq!(ab.attempt_result(2, 1).unwrap().result, AttemptResult::Success(950));
+    }
+
+    #[test]
+    fn merge_reports_conflicts_and_updates() {
+        let base = round(&[(1, &[])]);
+        let (mut a, mut b) = (base.clone(), base);
+        record(&mut a, 1, 0, 1000, "a", 2);
+        record(&mut b, 1, 0, 1100, "b", 1);
+        record(&mut b, 1, 1, 1200, "b", 3);
+
+        let report = a.merge_replica(&b);
+        assert_eq!(report.conflicts, vec![(a.id.clone(), 1, 0)]);
+        assert_eq!(report.updated, vec![(a.id.clone(), 1, 1)]);
+        assert_eq!(a.attempt_result(1, 0).unwrap().result, AttemptResult::Success(1000));
+    }
+
+    #[test]
+    fn three_replicas_converge_in_any_order() {
+        let base = round(&[(1, &[]), (2, &[])]);
+        let (mut a, mut b, mut c) = (base.clone(), base.clone(), base);
+        record(&mut a, 1, 0, 1000, "a", 1);
+        record(&mut b, 1, 0, 1100, "b", 1);
+        record(&mut c, 1, 0, 1200, "c", 1);
+        record(&mut a, 2, 2, 800, "a", 5);
+        record(&mut c, 2, 2, 850, "c", 4);
+        record(&mut b, 1, 1, 700, "b", 2);
+        // c saw the write of b before overwriting it
+        c.merge_replica(&b);
+        record(&mut c, 1, 1, 750, "c", 0);
+
+        let orders = [[&a, &b, &c], [&a, &c, &b], [&b, &a, &c], [&b, &c, &a], [&c, &a, &b], [&c, &b, &a]];
+        let expected = state(&merged(&orders[0]));
+        for order in &orders[1..] {
+            assert_eq!(state(&merged(order)), expected);
+        }
+        // Merging pairs first converges to the same state
+        let mut ab = merged(&[&a, &b]);
+        let bc = merged(&[&b, &c]);
+        ab.merge_replica(&bc);
+        assert_eq!(state(&ab), expected);
+
+        let result = |person_id, attempt_index| ab.attempt_result(person_id, attempt_index).unwrap().result;
+        // Written at the same time, the greater device id wins
+        assert_eq!(result(1, 0), AttemptResult::Success(1200));
+        assert_eq!(result(1, 1), AttemptResult::Success(750));
+        assert_eq!(result(2, 2), AttemptResult::Success(800));
+    }
+
+    #[test]
+    fn entered_attempt_wins_against_skipped() {
+        let mut a = round(&[(1, &[-1])]);
+        let mut b = round(&[(1, &[0])]);
+        record(&mut b, 1, 1, 1000, "b", 1);
+
+        let (ab, ba) = (merged(&[&a, &b]), merged(&[&b, &a]));
+        assert_eq!(state(&ab), state(&ba));
+        assert_eq!(ab.attempt_result(1, 0).unwrap().result, AttemptResult::DNF);
+        assert_eq!(ab.attempt_result(1, 1).unwrap().result, AttemptResult::Success(1000));
+
+        a.merge_replica(&b);
+        b.merge_replica(&a);
+        assert_eq!(state(&a), state(&b));
+    }
+
+    #[test]
+    fn merge_adds_persons_of_other_replica() {
+        let mut a = round(&[(1, &[])]);
+        let mut b = round(&[(1, &[]), (2, &[])]);
+        record(&mut b, 2, 0, 1000, "b", 1);
+        a.merge_replica(&b);
+        assert_eq!(a.attempt_result(2, 0).unwrap().result, AttemptResult::Success(1000));
+    }
+
+    #[test]
+    fn record_attempt_rejects_invalid_attempts() {
+        let mut round = round(&[(1, &[])]);
+        let attempt = Attempt { result: AttemptResult::Success(1000), reconstruction: None };
+        assert_eq!(round.record_attempt(1, 5, attempt.clone(), "a", at(0)), Err(ReplicationError::InvalidAttempt { round_id: round.id.clone(), attempt_index: 5 }));
+        assert_eq!(round.record_attempt(2, 0, attempt, "a", at(0)), Err(ReplicationError::UnknownPerson { round_id: round.id.clone(), person_id: 2 }));
+        assert!(round.attempt_clocks().is_none());
+    }
+}
@@ -0,0 +1,202 @@
+use std::cmp::Ordering;
+use crate::types::{Attempt, AttemptResult, AttemptResultValue, EventId, ResultType, Round, RoundFormat, RoundResult};
+
+pub fn compute_best(attempts: &[Attempt]) -> AttemptResult {
+    attempts.iter()
+        .map(|a| a.result)
+        .filter(|r| *r != AttemptResult::Skipped)
+        .min()
+        .unwrap_or(AttemptResult::Skipped)
+}
+
+// Computes the average (or mean) as defined by the regulations. Rounds that do not have a
+// complete set of attempts, e.g. because the competitor did not make the cutoff, have no average.
+pub fn compute_average(event_id: &EventId, format: &RoundFormat, attempts: &[Attempt]) -> AttemptResult {
+    let expected = format.expected_solve_count() as usize;
+    let results: Vec<AttemptResult> = attempts.iter()
+        .map(|a| a.result)
+        .filter(|r| *r != AttemptResult::Skipped)
+        .collect();
+    if results.len() < expected || !has_average(event_id, format) {
+        return AttemptResult::Skipped;
+    }
+    let mut counting = results[..expected].to_vec();
+    if *format == RoundFormat::AverageOf5 {
+        counting.sort();
+        counting = counting[1..expected - 1].to_vec();
+    }
+    let mut sum: u64 = 0;
+    for result in &counting {
+        match result.value() {
+            Some(x) => sum += x as u64,
+            None => return AttemptResult::DNF,
+        }
+    }
+    let count = counting.len() as u64;
+    if *event_id == EventId::FewestMoves333 {
+        return AttemptResult::Success(((sum * 100 + count / 2) / count) as u32);
+    }
+    let average = (sum + count / 2) / count;
+    // Averages over 10 minutes are rounded to the nearest second, see regulation 9f2
+    if average > 60000 {
+        AttemptResult::Success(((average + 50) / 100 * 100) as u32)
+    } else {
+        AttemptResult::Success(average as u32)
+    }
+}
+
+// Timed results over 10 minutes are rounded to the nearest second, see regulation 9f1
+pub fn round_timed_result(centiseconds: AttemptResultValue) -> AttemptResultValue {
+    if centiseconds > 60000 {
+        (centiseconds + 50) / 100 * 100
+    } else {
+        centiseconds
+    }
+}
+
+fn has_average(event_id: &EventId, format: &RoundFormat) -> bool {
+    match format {
+        RoundFormat::AverageOf5 | RoundFormat::MeanOf3 => true,
+        RoundFormat::BestOf3 => matches!(event_id, EventId::Blind333 | EventId::Blind444 | EventId::Blind555),
+        RoundFormat::BestOf1 | RoundFormat::BestOf2 => false,
+    }
+}
+
+impl RoundResult {
+    pub fn update_best_and_average(&mut self, event_id: &EventId, format: &RoundFormat) {
+        self.best = compute_best(&self.attempts);
+        self.average = compute_average(event_id, format, &self.attempts);
+    }
+
+    // Attempts done so far, without the skipped ones
+    fn done_attempts(&self) -> Vec<Attempt> {
+        self.attempts.iter()
+            .filter(|a| a.result != AttemptResult::Skipped)
+            .cloned()
+            .collect()
+    }
+
+    // The average with `result` as the last attempt. None unless exactly one attempt of a format with an average is missing
+    pub fn average_with_last_attempt(&self, event_id: &EventId, format: &RoundFormat, result: AttemptResult) -> Option<AttemptResult> {
+        let mut attempts = self.done_attempts();
+        if !has_average(event_id, format) || attempts.len() + 1 != format.expected_solve_count() as usize {
+            return None;
+        }
+        attempts.push(Attempt { result, reconstruction: None });
+        Some(compute_average(event_id, format, &attempts))
+    }
+
+    // Best possible average (BPA), reached if the last attempt is the best of all. It is then dropped from an average
+    // of 5, leaving the mean of the best three other attempts. Means count every attempt, so they have no BPA
+    pub fn best_possible_average(&self, event_id: &EventId, format: &RoundFormat) -> Option<AttemptResult> {
+        let mut attempts = self.done_attempts();
+        if *format != RoundFormat::AverageOf5 || attempts.len() + 1 != format.expected_solve_count() as usize {
+            return None;
+        }
+        attempts.sort_by_key(|a| a.result);
+        Some(compute_average(event_id, &RoundFormat::MeanOf3, &attempts[..3]))
+    }
+
+    // Worst possible average (WPA), reached if the last attempt is a DNF
+    pub fn worst_possible_average(&self, event_id: &EventId, format: &RoundFormat) -> Option<AttemptResult> {
+        self.average_with_last_attempt(event_id, format, AttemptResult::DNF)
+    }
+}
+
+// Compares two results of a round with the given format, better results are ordered first
+pub fn compare_results(format: &RoundFormat, a: &RoundResult, b: &RoundResult) -> Ordering {
+    let result_type = format.sort_by();
+    a.get_result(&result_type).cmp(b.get_result(&result_type))
+        .then(a.best.cmp(&b.best))
+}
+
+// Ranks results of a round with the given format, tied results share a ranking. Results without any attempts are not ranked
+pub fn rank_results(format: &RoundFormat, results: &mut [RoundResult]) {
+    let mut order: Vec<usize> = (0..results.len()).collect();
+    order.sort_by(|a, b| compare_results(format, &results[*a], &results[*b]));
+    let mut rankings = vec![None; results.len()];
+    let mut previous: Option<usize> = None;
+    for (position, index) in order.iter().enumerate() {
+        if results[*index].best == AttemptResult::Skipped {
+            continue;
+        }
+        rankings[*index] = match previous {
+            Some(p) if compare_results(format, &results[p], &results[*index]) == Ordering::Equal => rankings[p],
+            _ => Some(position as u64 + 1),
+        };
+        previous = Some(*index);
+    }
+    for (result, ranking) in results.iter_mut().zip(rankings) {
+        result.ranking = ranking;
+    }
+}
+
+impl Round {
+    // Compares two results of this round, better results are ordered first
+    pub fn compare_results(&self, a: &RoundResult, b: &RoundResult) -> Ordering {
+        compare_results(&self.format, a, b)
+    }
+
+    // Recomputes best, average and rankings of all results. Results without any attempts are not ranked.
+    pub fn update_results(&mut self) {
+        let event_id = self.id.event.clone();
+        for result in self.results.iter_mut() {
+            result.update_best_and_average(&event_id, &self.format);
+        }
+        self.update_rankings();
+    }
+
+    pub fn update_rankings(&mut self) {
+        rank_results(&self.format, &mut self.results);
+    }
+}
+
+// Formats a result as displayed on the WCA website, e.g. "1:02.34", "DNF", "28" for a fewest moves single
+// or "9/10 58:12" for multi blind. Averages of fewest moves are displayed with two decimals.
+pub fn format_result(event_id: &EventId, result_type: &ResultType, result: &AttemptResult) -> String {
+    let value = match result {
+        AttemptResult::Success(x) => *x,
+        AttemptResult::DNF => return "DNF".to_string(),
+        AttemptResult::DNS => return "DNS".to_string(),
+        AttemptResult::Skipped => return String::new(),
+    };
+    match (event_id, result_type) {
+        (EventId::FewestMoves333, ResultType::Single) => value.to_string(),
+        (EventId::FewestMoves333, ResultType::Average) => format!("{}.{:0>2}", value / 100, value % 100),
+        (EventId::MultiBlind333, _) => {
+            let missed = value % 100;
+            let seconds = value / 100 % 100_000;
+            let solved = 99 - value / 10_000_000 + missed;
+            format!("{solved}/{} {}", solved + missed, format_seconds(seconds))
+        },
+        (EventId::MultiBlindOldStyle333, _) => {
+            let seconds = value % 100_000;
+            let attempted = value / 100_000 % 100;
+            let solved = 99 - value / 10_000_000 % 100;
+            format!("{solved}/{attempted} {}", format_seconds(seconds))
+        },
+        _ => format_centiseconds(value),
+    }
+}
+
+pub fn format_centiseconds(centiseconds: AttemptResultValue) -> String {
+    let hours = centiseconds / 360_000;
+    let minutes = centiseconds / 6000 % 60;
+    let seconds = centiseconds / 100 % 60;
+    let fraction = centiseconds % 100;
+    if hours > 0 {
+        format!("{hours}:{minutes:0>2}:{seconds:0>2}.{fraction:0>2}")
+    } else if minutes > 0 {
+        format!("{minutes}:{seconds:0>2}.{fraction:0>2}")
+    } else {
+        format!("{seconds}.{fraction:0>2}")
+    }
+}
+
+fn format_seconds(seconds: AttemptResultValue) -> String {
+    if seconds >= 3600 {
+        format!("{}:{:0>2}:{:0>2}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    } else {
+        format!("{}:{:0>2}", seconds / 60, seconds % 60)
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::diff::{diff_values, Change};
+use crate::types::Competition;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundTripReport {
+    pub changes: Vec<Change>,
+}
+
+impl RoundTripReport {
+    pub fn is_lossless(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    // Fields present in the original document that are missing after the round trip
+    pub fn dropped(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|c| matches!(c, Change::Removed { .. }))
+    }
+
+    // Fields that were not present in the original document but were written by this crate
+    pub fn added(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|c| matches!(c, Change::Added { .. }))
+    }
+
+    pub fn altered(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|c| matches!(c, Change::Changed { .. }))
+    }
+}
+
+impl Display for RoundTripReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_lossless() {
+            return write!(f, "Round trip is lossless");
+        }
+        for change in &self.changes {
+            match change {
+                Change::Added { path, value } => writeln!(f, "added {path}: {value}")?,
+                Change::Removed { path, value } => writeln!(f, "dropped {path}: {value}")?,
+                Change::Changed { path, old, new } => writeln!(f, "altered {path}: {old} -> {new}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Parses the document, serializes it again and reports every difference to the original JSON
+pub fn roundtrip_check(json: &str) -> Result<RoundTripReport, serde_json::Error> {
+    let original: Value = serde_json::from_str(json)?;
+    let competition: Competition = serde_json::from_value(original.clone())?;
+    let serialized = serde_json::to_value(&competition)?;
+    Ok(RoundTripReport {
+        changes: diff_values(&original, &serialized),
+    })
+}
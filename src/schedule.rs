@@ -0,0 +1,165 @@
+use std::fmt::{Display, Formatter};
+use crate::types::{Activity, ActivityCode, ActivityId, Competition, PersonId, Room, RoomId, Schedule, Venue};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScheduleError {
+    UnknownActivity { activity_id: ActivityId },
+    UnknownRoom { room_id: RoomId },
+    // A child activity can only be moved into a room that has an activity with the same code as its parent
+    NoParentInRoom { activity_id: ActivityId, room_id: RoomId },
+}
+
+impl Display for ScheduleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownActivity { activity_id } => write!(f, "Activity {activity_id} does not exist"),
+            Self::UnknownRoom { room_id } => write!(f, "Room {room_id} does not exist"),
+            Self::NoParentInRoom { activity_id, room_id } => write!(f, "Room {room_id} has no parent activity for activity {activity_id}"),
+        }
+    }
+}
+
+// The activities affected by a schedule change and the persons with assignments to them
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ActivityChange {
+    // The activity and all of its nested child activities
+    pub activity_ids: Vec<ActivityId>,
+    pub affected_persons: Vec<PersonId>,
+}
+
+fn take_activity(activities: &mut Vec<Activity>, id: ActivityId) -> Option<Activity> {
+    if let Some(position) = activities.iter().position(|a| a.id == id) {
+        return Some(activities.remove(position));
+    }
+    activities.iter_mut().find_map(|a| take_activity(&mut a.child_activities, id))
+}
+
+fn find_activity_mut<'a>(activities: &'a mut [Activity], code: &ActivityCode) -> Option<&'a mut Activity> {
+    for activity in activities {
+        if &activity.activity_code == code {
+            return Some(activity);
+        }
+        if let Some(found) = find_activity_mut(&mut activity.child_activities, code) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+fn parent_code(activities: &[Activity], id: ActivityId) -> Option<&ActivityCode> {
+    activities.iter().find_map(|a| match a.child_activities.iter().any(|c| c.id == id) {
+        true => Some(&a.activity_code),
+        false => parent_code(&a.child_activities, id),
+    })
+}
+
+impl Venue {
+    // Great-circle distance between the coordinates of two venues
+    pub fn distance_km(&self, other: &Venue) -> f64 {
+        const EARTH_RADIUS_KM: f64 = 6371.0;
+        let radians = |microdegrees: i32| (microdegrees as f64 / 1_000_000.0).to_radians();
+        let (lat1, lat2) = (radians(self.latitude_microdegrees), radians(other.latitude_microdegrees));
+        let delta_lat = lat2 - lat1;
+        let delta_lon = radians(other.longitude_microdegrees) - radians(self.longitude_microdegrees);
+        let a = (delta_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (delta_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+}
+
+impl Schedule {
+    pub fn find_room(&self, room_id: RoomId) -> Option<&Room> {
+        self.venues.iter().flat_map(|v| v.rooms.iter()).find(|r| r.id == room_id)
+    }
+
+    pub fn find_room_mut(&mut self, room_id: RoomId) -> Option<&mut Room> {
+        self.venues.iter_mut().flat_map(|v| v.rooms.iter_mut()).find(|r| r.id == room_id)
+    }
+
+    // The room an activity or one of its ancestors is scheduled in
+    pub fn room_of(&self, activity_id: ActivityId) -> Option<&Room> {
+        self.venues.iter()
+            .flat_map(|v| v.rooms.iter())
+            .find(|r| r.activities.iter().flat_map(|a| a.all_activities()).any(|a| a.id == activity_id))
+    }
+
+    // Removes an activity together with its child activities, wherever it is nested
+    pub fn remove_activity(&mut self, activity_id: ActivityId) -> Option<Activity> {
+        self.venues.iter_mut()
+            .flat_map(|v| v.rooms.iter_mut())
+            .find_map(|r| take_activity(&mut r.activities, activity_id))
+    }
+
+    // Validates a move and returns the code of the parent the activity has to be added to, None for top level activities
+    // or if the activity already is in the room
+    fn move_target(&self, activity_id: ActivityId, room_id: RoomId) -> Result<Option<Option<ActivityCode>>, ScheduleError> {
+        let source = self.room_of(activity_id).ok_or(ScheduleError::UnknownActivity { activity_id })?;
+        let target = self.find_room(room_id).ok_or(ScheduleError::UnknownRoom { room_id })?;
+        if source.id == room_id {
+            return Ok(None);
+        }
+        let parent = parent_code(&source.activities, activity_id);
+        if let Some(parent) = parent {
+            if !target.activities.iter().flat_map(|a| a.all_activities()).any(|a| &a.activity_code == parent) {
+                return Err(ScheduleError::NoParentInRoom { activity_id, room_id });
+            }
+        }
+        Ok(Some(parent.cloned()))
+    }
+
+    // Moves an activity together with its child activities into another room. Top level activities are appended
+    // to the activities of the room, child activities to the activity with the same code as their current parent.
+    // Ids are kept, so assignments follow the activity
+    pub fn move_activity(&mut self, activity_id: ActivityId, room_id: RoomId) -> Result<(), ScheduleError> {
+        let Some(parent) = self.move_target(activity_id, room_id)? else {
+            return Ok(());
+        };
+        let activity = self.remove_activity(activity_id).expect("activity was just found");
+        let target = self.find_room_mut(room_id).expect("room was just found");
+        match parent {
+            Some(parent) => find_activity_mut(&mut target.activities, &parent)
+                .expect("parent was just found")
+                .child_activities.push(activity),
+            None => target.activities.push(activity),
+        }
+        Ok(())
+    }
+}
+
+impl Competition {
+    fn activity_change(&self, activity_id: ActivityId) -> Result<ActivityChange, ScheduleError> {
+        let activity = self.schedule.find_activity(activity_id).ok_or(ScheduleError::UnknownActivity { activity_id })?;
+        let activity_ids: Vec<ActivityId> = activity.all_activities().iter().map(|a| a.id).collect();
+        let affected_persons = self.persons.iter()
+            .filter(|p| p.assignments.iter().any(|a| activity_ids.contains(&a.activity_id)))
+            .filter_map(|p| p.registrant_id)
+            .collect();
+        Ok(ActivityChange { activity_ids, affected_persons })
+    }
+
+    // Removes an activity with its child activities and all assignments to them. With `dry_run` the
+    // document is left unchanged and only the persons that would lose assignments are reported
+    pub fn remove_activity(&mut self, activity_id: ActivityId, dry_run: bool) -> Result<ActivityChange, ScheduleError> {
+        let change = self.activity_change(activity_id)?;
+        if dry_run {
+            return Ok(change);
+        }
+        self.schedule.remove_activity(activity_id);
+        trace_change!(activity_id, affected_persons = change.affected_persons.len(), "removed activity");
+        for person in &mut self.persons {
+            person.assignments.retain(|a| !change.activity_ids.contains(&a.activity_id));
+        }
+        Ok(change)
+    }
+
+    // Moves an activity with its child activities into another room, see `Schedule::move_activity`. Assignments
+    // are kept, the affected persons are reported since their station numbers may refer to the previous room
+    pub fn move_activity(&mut self, activity_id: ActivityId, room_id: RoomId, dry_run: bool) -> Result<ActivityChange, ScheduleError> {
+        let change = self.activity_change(activity_id)?;
+        self.schedule.move_target(activity_id, room_id)?;
+        if !dry_run {
+            self.schedule.move_activity(activity_id, room_id)?;
+            trace_change!(activity_id, room_id, "moved activity");
+        }
+        Ok(change)
+    }
+}
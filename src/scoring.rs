@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::types::{AttemptResult, Competition, EventId, PersonId, ResultType};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SumOfRanks {
+    pub ranking: u64,
+    pub person_id: PersonId,
+    pub name: String,
+    pub total: u64,
+    pub ranks: Vec<(EventId, u64)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KinchScore {
+    pub ranking: u64,
+    pub person_id: PersonId,
+    pub name: String,
+    // Mean of the scores of all events, events without a result count as 0
+    pub total: f64,
+    pub scores: Vec<(EventId, f64)>,
+}
+
+// Score of a multi blind result, points plus the fraction of the hour left. None for other events
+fn multi_blind_score(event_id: &EventId, value: u32) -> Option<f64> {
+    let (points, seconds) = match event_id {
+        EventId::MultiBlind333 => (99 - (value / 10_000_000) as i64, value / 100 % 100_000),
+        EventId::MultiBlindOldStyle333 => {
+            let solved = 99 - (value / 10_000_000 % 100) as i64;
+            let attempted = (value / 100_000 % 100) as i64;
+            (2 * solved - attempted, value % 100_000)
+        },
+        _ => return None,
+    };
+    Some(points as f64 + (3600.0 - seconds.min(3600) as f64) / 3600.0)
+}
+
+// Kinch score of a result relative to the best result, 100 for the best
+fn relative_score(event_id: &EventId, result: AttemptResult, best: AttemptResult) -> f64 {
+    let (AttemptResult::Success(value), AttemptResult::Success(best)) = (result, best) else {
+        return 0.0;
+    };
+    match (multi_blind_score(event_id, value), multi_blind_score(event_id, best)) {
+        (Some(score), Some(best)) if best > 0.0 => 100.0 * score.max(0.0) / best,
+        (Some(_), Some(_)) => 0.0,
+        _ if value > 0 => 100.0 * best as f64 / value as f64,
+        _ => 0.0,
+    }
+}
+
+// Result types compared for Kinch scores: the average, except for events where many competitors only have a
+// single. For those the better of both scores counts
+fn kinch_result_types(event_id: &EventId) -> &'static [ResultType] {
+    match event_id {
+        EventId::Blind333 | EventId::Blind444 | EventId::Blind555 | EventId::FewestMoves333 => &[ResultType::Single, ResultType::Average],
+        EventId::MultiBlind333 | EventId::MultiBlindOldStyle333 => &[ResultType::Single],
+        _ => &[ResultType::Average],
+    }
+}
+
+pub(crate) fn rank_by<T>(scores: &mut [T], key: impl Fn(&T) -> f64, set_ranking: impl Fn(&mut T, u64)) {
+    let mut previous: Option<(f64, u64)> = None;
+    for (position, score) in scores.iter_mut().enumerate() {
+        let value = key(score);
+        let ranking = match previous {
+            Some((p, ranking)) if p == value => ranking,
+            _ => position as u64 + 1,
+        };
+        set_ranking(score, ranking);
+        previous = Some((value, ranking));
+    }
+}
+
+impl Competition {
+    fn scored_events(&self, event_ids: &[EventId]) -> Vec<EventId> {
+        self.events.iter()
+            .map(|e| e.id.clone())
+            .filter(|id| event_ids.is_empty() || event_ids.contains(id))
+            .collect()
+    }
+
+    // Final placement of every competitor of an event: the ranking in the last round they have a ranked result in
+    pub fn event_placements(&self, event_id: &EventId) -> HashMap<PersonId, u64> {
+        let mut placements = HashMap::new();
+        let Some(event) = self.events.iter().find(|e| e.id == *event_id) else {
+            return placements;
+        };
+        for round in event.rounds.iter().rev() {
+            for result in &round.results {
+                if let Some(ranking) = result.ranking {
+                    placements.entry(result.person_id).or_insert(ranking);
+                }
+            }
+        }
+        placements
+    }
+
+    // Sum of the placements in the given events (all events if empty), lowest first. Competitors who didn't
+    // compete in an event get the number of competitors of the event plus one
+    pub fn sum_of_ranks(&self, event_ids: &[EventId]) -> Vec<SumOfRanks> {
+        let events: Vec<(EventId, HashMap<PersonId, u64>)> = self.scored_events(event_ids).into_iter()
+            .map(|id| {
+                let placements = self.event_placements(&id);
+                (id, placements)
+            })
+            .collect();
+        let mut scores: Vec<SumOfRanks> = self.persons.iter()
+            .filter_map(|p| p.registrant_id.map(|id| (id, p)))
+            .filter(|(id, _)| events.iter().any(|(_, placements)| placements.contains_key(id)))
+            .map(|(person_id, person)| {
+                let ranks: Vec<(EventId, u64)> = events.iter()
+                    .map(|(event_id, placements)| (event_id.clone(), placements.get(&person_id).copied().unwrap_or(placements.len() as u64 + 1)))
+                    .collect();
+                SumOfRanks {
+                    ranking: 0,
+                    person_id,
+                    name: person.name.clone(),
+                    total: ranks.iter().map(|(_, rank)| rank).sum(),
+                    ranks,
+                }
+            })
+            .collect();
+        scores.sort_by(|a, b| a.total.cmp(&b.total).then_with(|| a.name.cmp(&b.name)));
+        rank_by(&mut scores, |s| s.total as f64, |s, ranking| s.ranking = ranking);
+        scores
+    }
+
+    // Kinch scores over the given events (all events if empty), highest first. The score of an event is the best
+    // result of the competition relative to the competitor's best result of any round, as a percentage
+    pub fn kinch_scores(&self, event_ids: &[EventId]) -> Vec<KinchScore> {
+        let events = self.scored_events(event_ids);
+        let mut bests: HashMap<(PersonId, EventId, ResultType), AttemptResult> = HashMap::new();
+        let mut overall: HashMap<(EventId, ResultType), AttemptResult> = HashMap::new();
+        for event in self.events.iter().filter(|e| events.contains(&e.id)) {
+            for result in event.rounds.iter().flat_map(|r| r.results.iter()) {
+                for result_type in kinch_result_types(&event.id) {
+                    let value = *result.get_result(result_type);
+                    if !value.is_success() {
+                        continue;
+                    }
+                    let best = bests.entry((result.person_id, event.id.clone(), result_type.clone())).or_insert(value);
+                    *best = (*best).min(value);
+                    let best = overall.entry((event.id.clone(), result_type.clone())).or_insert(value);
+                    *best = (*best).min(value);
+                }
+            }
+        }
+        let mut scores: Vec<KinchScore> = self.persons.iter()
+            .filter_map(|p| p.registrant_id.map(|id| (id, p)))
+            .filter(|(id, _)| bests.keys().any(|(person_id, _, _)| person_id == id))
+            .map(|(person_id, person)| {
+                let scores: Vec<(EventId, f64)> = events.iter()
+                    .map(|event_id| {
+                        let score = kinch_result_types(event_id).iter()
+                            .filter_map(|t| {
+                                let result = bests.get(&(person_id, event_id.clone(), t.clone()))?;
+                                let best = overall.get(&(event_id.clone(), t.clone()))?;
+                                Some(relative_score(event_id, *result, *best))
+                            })
+                            .fold(0.0, f64::max);
+                        (event_id.clone(), score)
+                    })
+                    .collect();
+                KinchScore {
+                    ranking: 0,
+                    person_id,
+                    name: person.name.clone(),
+                    total: if events.is_empty() { 0.0 } else { scores.iter().map(|(_, s)| s).sum::<f64>() / events.len() as f64 },
+                    scores,
+                }
+            })
+            .collect();
+        scores.sort_by(|a, b| b.total.total_cmp(&a.total).then_with(|| a.name.cmp(&b.name)));
+        rank_by(&mut scores, |s| s.total, |s, ranking| s.ranking = ranking);
+        scores
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt::{Display, Formatter};
+use std::io::{Cursor, Read};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use crate::types::{Competition, Extension, Round, RoundId, ScrambleSet};
+
+pub const EXTENSION_ID: &str = crate::extensions::ENCRYPTED_SCRAMBLE_SETS_ID;
+pub const SPEC_URL: &str = crate::extensions::ENCRYPTED_SCRAMBLE_SETS_SPEC_URL;
+const PBKDF2_ITERATIONS: u32 = 600_000;
+// Upper bound for the iteration count read from a document, so a crafted file can't stall decryption
+const MAX_PBKDF2_ITERATIONS: u32 = 10 * PBKDF2_ITERATIONS;
+
+// Scramble sets of a round encrypted with a password, so the document can be shared before the
+// competition without revealing the scrambles
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedScrambleSetsExtension {
+    pub id: MustBe!("wcif-rs.EncryptedScrambleSets"),
+    pub spec_url: String,
+    pub data: EncryptedScrambleSets,
+}
+
+// The scramble sets as JSON, encrypted with AES-256-GCM using a key derived from the password with PBKDF2-HMAC-SHA256.
+// Binary values are base64 encoded
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedScrambleSets {
+    pub iterations: u32,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+#[derive(Debug)]
+pub enum ScrambleEncryptionError {
+    WrongPassword,
+    InvalidIterations(u32),
+    EncryptionFailed,
+    NotEncrypted,
+    InvalidEncoding(base64::DecodeError),
+    Json(serde_json::Error),
+    Zip(zip::result::ZipError),
+    Io(std::io::Error),
+    MissingInterchange,
+}
+
+impl Display for ScrambleEncryptionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongPassword => write!(f, "Wrong password or corrupted payload"),
+            Self::InvalidIterations(n) => write!(f, "Invalid PBKDF2 iteration count {n}, expected 1 to {MAX_PBKDF2_ITERATIONS}"),
+            Self::EncryptionFailed => write!(f, "Scramble sets could not be encrypted"),
+            Self::NotEncrypted => write!(f, "Round has no encrypted scramble sets"),
+            Self::InvalidEncoding(e) => write!(f, "Invalid base64: {e}"),
+            Self::Json(e) => write!(f, "{e}"),
+            Self::Zip(e) => write!(f, "{e}"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::MissingInterchange => write!(f, "Archive does not contain a TNoodle interchange JSON file"),
+        }
+    }
+}
+
+impl std::error::Error for ScrambleEncryptionError {}
+
+fn derive_key(password: &str, salt: &[u8], iterations: u32) -> Key<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, iterations, &mut key);
+    key.into()
+}
+
+impl EncryptedScrambleSets {
+    pub fn encrypt(scramble_sets: &[ScrambleSet], password: &str) -> Result<Self, ScrambleEncryptionError> {
+        let plaintext = serde_json::to_vec(scramble_sets).map_err(ScrambleEncryptionError::Json)?;
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = Aes256Gcm::generate_nonce(OsRng);
+        let cipher = Aes256Gcm::new(&derive_key(password, &salt, PBKDF2_ITERATIONS));
+        let ciphertext = cipher.encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| ScrambleEncryptionError::EncryptionFailed)?;
+        Ok(Self {
+            iterations: PBKDF2_ITERATIONS,
+            salt: BASE64.encode(salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, password: &str) -> Result<Vec<ScrambleSet>, ScrambleEncryptionError> {
+        if !(1..=MAX_PBKDF2_ITERATIONS).contains(&self.iterations) {
+            return Err(ScrambleEncryptionError::InvalidIterations(self.iterations));
+        }
+        let salt = BASE64.decode(&self.salt).map_err(ScrambleEncryptionError::InvalidEncoding)?;
+        let nonce = BASE64.decode(&self.nonce).map_err(ScrambleEncryptionError::InvalidEncoding)?;
+        let ciphertext = BASE64.decode(&self.ciphertext).map_err(ScrambleEncryptionError::InvalidEncoding)?;
+        if nonce.len() != 12 {
+            return Err(ScrambleEncryptionError::WrongPassword);
+        }
+        let cipher = Aes256Gcm::new(&derive_key(password, &salt, self.iterations));
+        let plaintext = cipher.decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| ScrambleEncryptionError::WrongPassword)?;
+        serde_json::from_slice(&plaintext).map_err(ScrambleEncryptionError::Json)
+    }
+}
+
+impl Round {
+    pub fn encrypted_scramble_sets(&self) -> Option<&EncryptedScrambleSets> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::EncryptedScrambleSets(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    // Replaces any previously attached encrypted scramble sets
+    pub fn attach_encrypted_scramble_sets(&mut self, encrypted: EncryptedScrambleSets) {
+        self.extensions.retain(|e| !matches!(e, Extension::EncryptedScrambleSets(_)));
+        self.extensions.push(Extension::EncryptedScrambleSets(EncryptedScrambleSetsExtension {
+            id: Default::default(),
+            spec_url: SPEC_URL.to_string(),
+            data: encrypted,
+        }));
+    }
+
+    // Moves the plaintext scramble sets into an encrypted extension
+    pub fn encrypt_scramble_sets(&mut self, password: &str) -> Result<(), ScrambleEncryptionError> {
+        let encrypted = EncryptedScrambleSets::encrypt(&self.scramble_sets, password)?;
+        self.attach_encrypted_scramble_sets(encrypted);
+        self.scramble_sets.clear();
+        Ok(())
+    }
+
+    // Restores the plaintext scramble sets and removes the encrypted extension
+    pub fn decrypt_scramble_sets(&mut self, password: &str) -> Result<(), ScrambleEncryptionError> {
+        let encrypted = self.encrypted_scramble_sets().ok_or(ScrambleEncryptionError::NotEncrypted)?;
+        self.scramble_sets = encrypted.decrypt(password)?;
+        self.extensions.retain(|e| !matches!(e, Extension::EncryptedScrambleSets(_)));
+        Ok(())
+    }
+}
+
+impl Competition {
+    // Decrypts the scramble sets of all rounds that have encrypted scramble sets attached.
+    // Nothing is modified if the password is wrong for any of them
+    pub fn decrypt_scramble_sets(&mut self, password: &str) -> Result<(), ScrambleEncryptionError> {
+        let mut decrypted = vec![];
+        for round in self.events.iter().flat_map(|e| e.rounds.iter()) {
+            if let Some(encrypted) = round.encrypted_scramble_sets() {
+                decrypted.push((round.id.clone(), encrypted.decrypt(password)?));
+            }
+        }
+        for (round_id, scramble_sets) in decrypted {
+            if let Some(round) = self.find_round_mut(&round_id) {
+                round.scramble_sets = scramble_sets;
+                round.extensions.retain(|e| !matches!(e, Extension::EncryptedScrambleSets(_)));
+            }
+        }
+        Ok(())
+    }
+
+    // Reads the scrambles of a TNoodle zip and attaches them to the matching rounds, encrypted with `password`.
+    // Returns the ids of the rounds scrambles were attached to
+    pub fn attach_tnoodle_scrambles(&mut self, zip: &[u8], zip_password: Option<&str>, password: &str) -> Result<Vec<RoundId>, ScrambleEncryptionError> {
+        let mut attached = vec![];
+        for (round_id, scramble_sets) in read_tnoodle_zip(zip, zip_password)? {
+            let Some(round) = self.find_round_mut(&round_id) else {
+                continue;
+            };
+            round.attach_encrypted_scramble_sets(EncryptedScrambleSets::encrypt(&scramble_sets, password)?);
+            attached.push(round_id);
+        }
+        Ok(attached)
+    }
+}
+
+// Extracts the scramble sets per round from the interchange JSON of a (possibly password protected) TNoodle zip
+pub fn read_tnoodle_zip(zip: &[u8], password: Option<&str>) -> Result<Vec<(RoundId, Vec<ScrambleSet>)>, ScrambleEncryptionError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(zip)).map_err(ScrambleEncryptionError::Zip)?;
+    let name = archive.file_names()
+        .find(|n| n.starts_with("Interchange/") && n.ends_with(".json"))
+        .ok_or(ScrambleEncryptionError::MissingInterchange)?
+        .to_string();
+    let mut file = match password {
+        Some(password) => archive.by_name_decrypt(&name, password.as_bytes()),
+        None => archive.by_name(&name),
+    }.map_err(|e| match e {
+        zip::result::ZipError::InvalidPassword => ScrambleEncryptionError::WrongPassword,
+        e => ScrambleEncryptionError::Zip(e),
+    })?;
+    let mut json = vec![];
+    file.read_to_end(&mut json).map_err(ScrambleEncryptionError::Io)?;
+    let interchange: Value = serde_json::from_slice(&json).map_err(ScrambleEncryptionError::Json)?;
+    let wcif = interchange.get("wcif").unwrap_or(&interchange);
+    let mut scrambles = vec![];
+    for round in wcif.get("events").and_then(|e| e.as_array()).into_iter().flatten()
+        .flat_map(|e| e.get("rounds").and_then(|r| r.as_array()).into_iter().flatten()) {
+        let round_id: RoundId = serde_json::from_value(round.get("id").cloned().unwrap_or_default())
+            .map_err(ScrambleEncryptionError::Json)?;
+        let scramble_sets: Vec<ScrambleSet> = serde_json::from_value(round.get("scrambleSets").cloned().unwrap_or(Value::Array(vec![])))
+            .map_err(ScrambleEncryptionError::Json)?;
+        scrambles.push((round_id, scramble_sets));
+    }
+    Ok(scrambles)
+}
@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use crate::types::{Activity, ActivityCode, ActivityId, AttemptIdType, Competition, GroupIdType, RoundId, ScrambleSetId};
+
+// Activities with the same group and attempt number share a scramble set, e.g. group 1 running on two stages at the same time
+pub type ScrambleKey = (Option<GroupIdType>, Option<AttemptIdType>);
+
+pub fn scramble_key(activity: &Activity) -> Option<ScrambleKey> {
+    match &activity.activity_code {
+        ActivityCode::Official(code) => Some((code.group, code.attempt)),
+        ActivityCode::Unofficial(_) => None,
+    }
+}
+
+impl Competition {
+    // The activities of a round that are scrambled for, i.e. the leaves of the round's activity trees.
+    // These are group activities, attempt activities for events scrambled per attempt (fewest moves, multi blind)
+    // or the round activity itself if it isn't split up.
+    pub fn scrambled_activities(&self, round_id: &RoundId) -> Vec<&Activity> {
+        self.schedule.all_activities().into_iter()
+            .filter(|a| a.child_activities.is_empty())
+            .filter(|a| match &a.activity_code {
+                ActivityCode::Official(code) => code.event == round_id.event && code.round == Some(round_id.round),
+                ActivityCode::Unofficial(_) => false,
+            })
+            .collect()
+    }
+
+    // Assigns the scramble sets of each round to its scrambled activities round robin, ordered by group and attempt number.
+    // Activities with the same group and attempt number in different rooms get the same scramble set.
+    // Returns the rounds that have scheduled activities but no scramble sets to assign.
+    pub fn assign_scramble_sets(&mut self) -> Vec<RoundId> {
+        let mut missing = vec![];
+        let mut assignments: HashMap<ActivityId, ScrambleSetId> = HashMap::new();
+        for round in self.events.iter().flat_map(|e| e.rounds.iter()) {
+            let activities = self.scrambled_activities(&round.id);
+            if activities.is_empty() {
+                continue;
+            }
+            if round.scramble_sets.is_empty() {
+                missing.push(round.id.clone());
+                continue;
+            }
+            let mut keys: Vec<ScrambleKey> = activities.iter()
+                .filter_map(|a| scramble_key(a))
+                .collect();
+            keys.sort();
+            keys.dedup();
+            for activity in activities {
+                let Some(index) = scramble_key(activity).and_then(|k| keys.binary_search(&k).ok()) else {
+                    continue;
+                };
+                assignments.insert(activity.id, round.scramble_sets[index % round.scramble_sets.len()].id);
+            }
+        }
+        self.schedule.for_each_activity_mut(|a| {
+            if let Some(id) = assignments.get(&a.id) {
+                a.scramble_set_id = Some(*id);
+            }
+        });
+        missing
+    }
+}
@@ -0,0 +1,240 @@
+use serde::Serialize;
+use serde_json::Value;
+use crate::extensions::{CHECK_IN_ID, ID_VERIFICATION_ID, INCIDENT_LOG_ID, PAYMENTS_ID, WAITING_LIST_ID};
+use crate::types::{AdvancementCondition, Assignment, Avatar, Competition, CompetitionId, CountryCode, Cutoff, Event, EventId, Extension, FormatVersion, Gender, Person, PersonalBest, PersonId, Qualification, RegistrationInfo, RegistrationStatus, Role, RoundFormat, RoundId, RoundResult, Schedule, ScrambleSet, Series, TimeLimit, WCAId, WCARegistrationId, WCAUserId};
+
+// Extensions of this crate holding organizer data about persons and the competition
+const PRIVATE_EXTENSIONS: [&str; 5] = [PAYMENTS_ID, ID_VERIFICATION_ID, CHECK_IN_ID, INCIDENT_LOG_ID, WAITING_LIST_ID];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializeOptions {
+    // Version written to formatVersion. All fields written by this crate are part of 1.0
+    pub target_version: FormatVersion,
+    pub include_scramble_sets: bool,
+    // Birthdates, emails and the private registration fields, only present with the private_properties feature
+    pub include_private_fields: bool,
+    // Payments, id verifications, check ins, incidents and the waiting list, whether parsed or not
+    pub include_private_extensions: bool,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        SerializeOptions {
+            target_version: FormatVersion::LATEST,
+            include_scramble_sets: true,
+            include_private_fields: true,
+            include_private_extensions: true,
+        }
+    }
+}
+
+impl SerializeOptions {
+    // The shape of the public WCIF served by the WCA website
+    pub fn public() -> Self {
+        SerializeOptions {
+            include_scramble_sets: false,
+            include_private_fields: false,
+            include_private_extensions: false,
+            ..Default::default()
+        }
+    }
+
+    fn extensions<'a>(&self, extensions: &'a [Extension]) -> Vec<&'a Extension> {
+        extensions.iter()
+            .filter(|e| self.include_private_extensions || !PRIVATE_EXTENSIONS.contains(&e.id()))
+            .collect()
+    }
+}
+
+impl Competition {
+    // A view of the document in the shape described by the options, serializing without copying it
+    pub fn view_with(&self, options: &SerializeOptions) -> CompetitionView<'_> {
+        CompetitionView {
+            format_version: options.target_version.clone(),
+            id: &self.id,
+            name: &self.name,
+            short_name: &self.short_name,
+            series: &self.series,
+            persons: self.persons.iter().map(|p| PersonView::new(p, options)).collect(),
+            events: self.events.iter().map(|e| EventView::new(e, options)).collect(),
+            schedule: &self.schedule,
+            registration_info: &self.registration_info,
+            competitor_limit: self.competitor_limit,
+            extensions: options.extensions(&self.extensions),
+        }
+    }
+
+    pub fn to_value_with(&self, options: &SerializeOptions) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(self.view_with(options))
+    }
+
+    pub fn to_json_with(&self, options: &SerializeOptions) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.view_with(options))
+    }
+}
+
+// Borrowing views serializing a document without copying it. Only the lists of persons, events, rounds and
+// extensions are allocated. They mirror the field order of the types they borrow from and must be kept in sync with them
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompetitionView<'a> {
+    format_version: FormatVersion,
+    id: &'a CompetitionId,
+    name: &'a str,
+    short_name: &'a str,
+    series: &'a Option<Series>,
+    persons: Vec<PersonView<'a>>,
+    events: Vec<EventView<'a>>,
+    schedule: &'a Schedule,
+    registration_info: &'a Option<RegistrationInfo>,
+    competitor_limit: Option<u32>,
+    extensions: Vec<&'a Extension>,
+}
+
+// The view of the public WCIF, without private fields, private extensions and scramble sets
+pub type PublicCompetition<'a> = CompetitionView<'a>;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PersonView<'a> {
+    registrant_id: Option<PersonId>,
+    name: &'a str,
+    wca_user_id: WCAUserId,
+    wca_id: &'a Option<WCAId>,
+    country_iso2: &'a CountryCode,
+    gender: &'a Gender,
+    #[cfg(feature = "private_properties")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    birthdate: Option<&'a chrono::NaiveDate>,
+    #[cfg(feature = "private_properties")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<&'a str>,
+    avatar: &'a Option<Avatar>,
+    roles: &'a [Role],
+    registration: Option<RegistrationView<'a>>,
+    assignments: &'a [Assignment],
+    personal_bests: &'a [PersonalBest],
+    extensions: Vec<&'a Extension>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RegistrationView<'a> {
+    wca_registration_id: WCARegistrationId,
+    event_ids: &'a [EventId],
+    status: &'a RegistrationStatus,
+    #[cfg(feature = "private_properties")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    guests: Option<u32>,
+    #[cfg(feature = "private_properties")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comments: Option<&'a str>,
+    #[cfg(feature = "private_properties")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    administrative_notes: Option<&'a str>,
+    is_competing: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EventView<'a> {
+    id: &'a EventId,
+    rounds: Vec<RoundView<'a>>,
+    competitor_limit: Option<u32>,
+    qualification: &'a Option<Qualification>,
+    extensions: Vec<&'a Extension>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RoundView<'a> {
+    id: &'a RoundId,
+    format: &'a RoundFormat,
+    time_limit: &'a Option<TimeLimit>,
+    cutoff: &'a Option<Cutoff>,
+    advancement_condition: &'a Option<AdvancementCondition>,
+    results: &'a [RoundResult],
+    scramble_set_count: u32,
+    #[serde(skip_serializing_if = "<[ScrambleSet]>::is_empty")]
+    scramble_sets: &'a [ScrambleSet],
+    extensions: Vec<&'a Extension>,
+}
+
+impl<'a> PersonView<'a> {
+    #[cfg_attr(not(feature = "private_properties"), allow(unused_variables))]
+    fn new(person: &'a Person, options: &SerializeOptions) -> Self {
+        #[cfg(feature = "private_properties")]
+        let private = options.include_private_fields;
+        PersonView {
+            registrant_id: person.registrant_id,
+            name: &person.name,
+            wca_user_id: person.wca_user_id,
+            wca_id: &person.wca_id,
+            country_iso2: &person.country_iso2,
+            gender: &person.gender,
+            #[cfg(feature = "private_properties")]
+            birthdate: private.then_some(&person.birthdate),
+            #[cfg(feature = "private_properties")]
+            email: private.then_some(person.email.as_str()),
+            avatar: &person.avatar,
+            roles: &person.roles,
+            registration: person.registration.as_ref().map(|r| RegistrationView {
+                wca_registration_id: r.wca_registration_id,
+                event_ids: &r.event_ids,
+                status: &r.status,
+                #[cfg(feature = "private_properties")]
+                guests: private.then_some(r.guests),
+                #[cfg(feature = "private_properties")]
+                comments: private.then_some(r.comments.as_str()),
+                #[cfg(feature = "private_properties")]
+                administrative_notes: private.then_some(r.administrative_notes.as_str()),
+                is_competing: r.is_competing,
+            }),
+            assignments: &person.assignments,
+            personal_bests: &person.personal_bests,
+            extensions: options.extensions(&person.extensions),
+        }
+    }
+}
+
+impl<'a> EventView<'a> {
+    fn new(event: &'a Event, options: &SerializeOptions) -> Self {
+        EventView {
+            id: &event.id,
+            rounds: event.rounds.iter().map(|round| RoundView {
+                id: &round.id,
+                format: &round.format,
+                time_limit: &round.time_limit,
+                cutoff: &round.cutoff,
+                advancement_condition: &round.advancement_condition,
+                results: &round.results,
+                scramble_set_count: round.scramble_set_count,
+                scramble_sets: if options.include_scramble_sets { &round.scramble_sets } else { &[] },
+                extensions: options.extensions(&round.extensions),
+            }).collect(),
+            competitor_limit: event.competitor_limit,
+            qualification: &event.qualification,
+            extensions: options.extensions(&event.extensions),
+        }
+    }
+}
+
+impl<'a> From<&'a Competition> for PublicCompetition<'a> {
+    fn from(competition: &'a Competition) -> Self {
+        competition.view_with(&SerializeOptions::public())
+    }
+}
+
+impl Competition {
+    // A view of the document without private data that serializes to the public WCIF, for servers holding
+    // private data in memory but serving public endpoints
+    pub fn public(&self) -> PublicCompetition<'_> {
+        PublicCompetition::from(self)
+    }
+
+    // Same output as `to_json_with(&SerializeOptions::public())`
+    pub fn serialize_public(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.public())
+    }
+}
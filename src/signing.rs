@@ -0,0 +1,136 @@
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::types::{Competition, DateTime, Extension, PersonId};
+
+pub const EXTENSION_ID: &str = crate::extensions::RESULTS_SIGNATURE_ID;
+pub const SPEC_URL: &str = crate::extensions::RESULTS_SIGNATURE_SPEC_URL;
+
+#[derive(Debug)]
+pub enum SignatureError {
+    Unsigned,
+    InvalidEncoding(base64::DecodeError),
+    InvalidKey(ed25519_dalek::SignatureError),
+    UntrustedKey,
+    // The results changed since they were signed, or the signature is forged
+    Mismatch,
+}
+
+impl Display for SignatureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsigned => write!(f, "The results are not signed"),
+            Self::InvalidEncoding(e) => write!(f, "Invalid base64: {e}"),
+            Self::InvalidKey(e) => write!(f, "Invalid key or signature: {e}"),
+            Self::UntrustedKey => write!(f, "The results are signed with an untrusted key"),
+            Self::Mismatch => write!(f, "The signature does not match the results"),
+        }
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+// Ed25519 signature over the canonical JSON of the results of all rounds and the persons they belong to, see `Competition::results_payload`.
+// Binary values are base64 encoded
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultsSignatureExtension {
+    pub id: MustBe!("wcif-rs.ResultsSignature"),
+    pub spec_url: String,
+    pub data: ResultsSignature,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultsSignature {
+    pub public_key: String,
+    pub signature: String,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
+    pub signed_at: DateTime,
+    pub signer: Option<String>,
+}
+
+// JSON without whitespace and with object keys sorted, so equal values always give the same bytes
+pub fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            let fields: Vec<String> = entries.into_iter()
+                .map(|(key, value)| format!("{}:{}", Value::String(key.clone()), canonical_json(value)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        },
+        Value::Array(values) => format!("[{}]", values.iter().map(canonical_json).collect::<Vec<_>>().join(",")),
+        value => value.to_string(),
+    }
+}
+
+impl Competition {
+    // The signed part of the document: the id of the competition, the id, format and results of every round, with
+    // results ordered by person, and the identity of every person with a result. Everything else, including
+    // extensions of rounds, can change without invalidating the signature
+    pub fn results_payload(&self) -> Vec<u8> {
+        let rounds: Vec<Value> = self.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .map(|round| {
+                let mut results: Vec<_> = round.results.iter().collect();
+                results.sort_by_key(|r| r.person_id);
+                json!({ "id": round.id, "format": round.format, "results": results })
+            })
+            .collect();
+        let person_ids: BTreeSet<PersonId> = self.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .flat_map(|r| r.results.iter().map(|r| r.person_id))
+            .collect();
+        let persons: Vec<Value> = person_ids.into_iter()
+            .map(|id| match self.find_person(id) {
+                Some(person) => json!({ "registrantId": id, "wcaId": person.wca_id, "name": person.name, "countryIso2": person.country_iso2 }),
+                None => json!({ "registrantId": id }),
+            })
+            .collect();
+        canonical_json(&json!({ "competitionId": self.id, "rounds": rounds, "persons": persons })).into_bytes()
+    }
+
+    pub fn results_signature(&self) -> Option<&ResultsSignature> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::ResultsSignature(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    // Replaces a previous signature
+    pub fn sign_results(&mut self, key: &SigningKey, signer: Option<String>, now: DateTime) {
+        let signature = key.sign(&self.results_payload());
+        self.extensions.retain(|e| !matches!(e, Extension::ResultsSignature(_)));
+        self.extensions.push(Extension::ResultsSignature(ResultsSignatureExtension {
+            id: Default::default(),
+            spec_url: SPEC_URL.to_string(),
+            data: ResultsSignature {
+                public_key: BASE64.encode(key.verifying_key().as_bytes()),
+                signature: BASE64.encode(signature.to_bytes()),
+                signed_at: now,
+                signer,
+            },
+        }));
+        trace_change!(signer = ?self.results_signature().and_then(|s| s.signer.as_ref()), "signed results");
+    }
+
+    // Checks that the results were signed by one of the trusted keys and haven't changed since
+    pub fn verify_results(&self, trusted: &[VerifyingKey]) -> Result<&ResultsSignature, SignatureError> {
+        let signature = self.results_signature().ok_or(SignatureError::Unsigned)?;
+        let key_bytes = BASE64.decode(&signature.public_key).map_err(SignatureError::InvalidEncoding)?;
+        let key = trusted.iter()
+            .find(|k| k.as_bytes().as_slice() == key_bytes)
+            .ok_or(SignatureError::UntrustedKey)?;
+        let signature_bytes = BASE64.decode(&signature.signature).map_err(SignatureError::InvalidEncoding)?;
+        let ed25519_signature = Signature::from_slice(&signature_bytes).map_err(SignatureError::InvalidKey)?;
+        key.verify_strict(&self.results_payload(), &ed25519_signature).map_err(|_| SignatureError::Mismatch)?;
+        Ok(signature)
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use rand::Rng;
+use crate::results::round_timed_result;
+use crate::types::{Attempt, AttemptResult, Competition, EventId, Person, PersonId, RegistrationStatus, Round, RoundId, RoundResult};
+
+// Fabricates plausible results for every round of every event. Existing results are replaced.
+// Round one is contested by every accepted competitor registered for the event, later rounds by the
+// competitors advancing from the previous round. Cutoffs, time limits (including cumulative ones) and
+// advancement conditions are honored, so the output passes round progression validation.
+pub fn simulate_competition<R: Rng>(competition: &mut Competition, rng: &mut R) {
+    let skills: HashMap<PersonId, f64> = competition.persons.iter()
+        .filter_map(|p| p.registrant_id)
+        .map(|id| (id, rng.gen_range(0.6..2.5)))
+        .collect();
+    let mut time_spent: HashMap<(RoundId, PersonId), u32> = HashMap::new();
+    for event in competition.events.iter_mut() {
+        let mut competitors: Vec<PersonId> = competition.persons.iter()
+            .filter(|p| is_competing_in(p, &event.id))
+            .filter_map(|p| p.registrant_id)
+            .collect();
+        for round in event.rounds.iter_mut() {
+            round.results = competitors.iter()
+                .map(|person_id| {
+                    let skill = skills.get(person_id).copied().unwrap_or(1.0);
+                    simulate_result(round, *person_id, skill, &mut time_spent, rng)
+                })
+                .collect();
+            round.update_results();
+            competitors = match round.advancing_persons() {
+                Some(advancing) => round.results.iter()
+                    .map(|r| r.person_id)
+                    .filter(|p| advancing.contains(p))
+                    .collect(),
+                None => vec![],
+            };
+        }
+    }
+}
+
+fn is_competing_in(person: &Person, event_id: &EventId) -> bool {
+    person.registration.as_ref()
+        .is_some_and(|r| r.is_competing && r.status == RegistrationStatus::Accepted && r.event_ids.contains(event_id))
+}
+
+fn simulate_result<R: Rng>(round: &Round, person_id: PersonId, skill: f64, time_spent: &mut HashMap<(RoundId, PersonId), u32>, rng: &mut R) -> RoundResult {
+    let solve_count = round.format.expected_solve_count() as usize;
+    let mut attempts = Vec::with_capacity(solve_count);
+    for i in 0..solve_count {
+        if let Some(cutoff) = round.cutoff.as_ref() {
+            let made_cutoff = attempts.iter()
+                .take(cutoff.number_of_attempts)
+                .any(|a: &Attempt| a.result < cutoff.attempt_result);
+            if i == cutoff.number_of_attempts && !made_cutoff {
+                break;
+            }
+        }
+        let result = simulate_attempt(round, person_id, skill, time_spent, rng);
+        attempts.push(Attempt {
+            result,
+            reconstruction: None,
+        });
+    }
+    RoundResult {
+        person_id,
+        ranking: None,
+        attempts,
+        best: AttemptResult::Skipped,
+        average: AttemptResult::Skipped,
+    }
+}
+
+fn simulate_attempt<R: Rng>(round: &Round, person_id: PersonId, skill: f64, time_spent: &mut HashMap<(RoundId, PersonId), u32>, rng: &mut R) -> AttemptResult {
+    let event_id = &round.id.event;
+    if *event_id == EventId::MultiBlind333 || *event_id == EventId::MultiBlindOldStyle333 {
+        return simulate_multi_blind(event_id, skill, rng);
+    }
+    let dnf_rate = if event_id.is_blind() { 0.3 } else { 0.03 };
+    if rng.gen_bool(0.005) {
+        return AttemptResult::DNS;
+    }
+    if rng.gen_bool(dnf_rate) {
+        return AttemptResult::DNF;
+    }
+    let value = (expected_result(event_id) * skill * rng.gen_range(0.85..1.15)).round().max(1.0) as u32;
+    if *event_id == EventId::FewestMoves333 {
+        return AttemptResult::Success(value.min(80));
+    }
+    let value = round_timed_result(value);
+    let Some(time_limit) = round.time_limit.as_ref() else {
+        return AttemptResult::Success(value);
+    };
+    if time_limit.cumulative_round_ids.is_empty() {
+        return if value < time_limit.centiseconds {
+            AttemptResult::Success(value)
+        } else {
+            AttemptResult::DNF
+        };
+    }
+    let used: u32 = time_limit.cumulative_round_ids.iter()
+        .filter_map(|id| time_spent.get(&(id.clone(), person_id)))
+        .sum();
+    if used >= time_limit.centiseconds {
+        return AttemptResult::DNS;
+    }
+    *time_spent.entry((round.id.clone(), person_id)).or_default() += value.min(time_limit.centiseconds - used);
+    if used + value < time_limit.centiseconds {
+        AttemptResult::Success(value)
+    } else {
+        AttemptResult::DNF
+    }
+}
+
+// Multi blind results are encoded as 0DDTTTTTMM and old style results as 1SSAATTTTT, see the WCA results export
+// documentation
+fn simulate_multi_blind<R: Rng>(event_id: &EventId, skill: f64, rng: &mut R) -> AttemptResult {
+    let attempted = ((12.0 / skill) as u32).clamp(2, 40) + rng.gen_range(0..=2);
+    let missed = rng.gen_range(0..=attempted / 3);
+    let solved = attempted - missed;
+    if solved < 2 || solved < missed {
+        return AttemptResult::DNF;
+    }
+    // 10 minutes per cube, up to one hour, see regulation H1b
+    let time_limit = 600 * attempted.min(6);
+    let seconds = (time_limit as f64 * rng.gen_range(0.6..1.0)) as u32;
+    if *event_id == EventId::MultiBlindOldStyle333 {
+        return AttemptResult::Success(1_000_000_000 + (99 - solved) * 10_000_000 + attempted * 100_000 + seconds);
+    }
+    let difference = solved - missed;
+    AttemptResult::Success((99 - difference) * 10_000_000 + seconds * 100 + missed)
+}
+
+// Rough mean result of an average competitor, in centiseconds or moves for fewest moves
+fn expected_result(event_id: &EventId) -> f64 {
+    match event_id {
+        EventId::Cube333 => 1500.0,
+        EventId::Cube222 => 500.0,
+        EventId::Cube444 => 5500.0,
+        EventId::Cube555 => 10000.0,
+        EventId::Cube666 => 19000.0,
+        EventId::Cube777 => 28000.0,
+        EventId::Blind333 => 12000.0,
+        EventId::FewestMoves333 => 32.0,
+        EventId::OneHanded333 => 2500.0,
+        EventId::Feet333 => 6000.0,
+        EventId::Clock => 1000.0,
+        EventId::Megaminx => 8000.0,
+        EventId::Pyraminx => 600.0,
+        EventId::Skewb => 700.0,
+        EventId::Square1 => 2000.0,
+        EventId::Blind444 => 45000.0,
+        EventId::Blind555 => 90000.0,
+        EventId::Magic => 150.0,
+        EventId::MasterMagic => 350.0,
+        EventId::MultiBlind333 | EventId::MultiBlindOldStyle333 => 0.0,
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::{BTreeMap, HashMap};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::types::{ActivityId, AssignmentCode, Competition, Date, Person, PersonId, Role, RoomId, StaffAssignment};
+
+// Staff assignments of all activities, grouped by day (local to the venue) and room
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffRoster {
+    pub days: Vec<RosterDay>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterDay {
+    pub date: Date,
+    pub rooms: Vec<RosterRoom>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterRoom {
+    pub room_id: RoomId,
+    pub name: String,
+    pub shifts: Vec<RosterShift>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterShift {
+    pub activity_id: ActivityId,
+    pub name: String,
+    pub start_time: NaiveDateTime,
+    pub end_time: NaiveDateTime,
+    pub staff: Vec<RosterTask>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterTask {
+    pub task: StaffAssignment,
+    pub persons: Vec<RosterPerson>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterPerson {
+    pub registrant_id: Option<PersonId>,
+    pub name: String,
+    pub station_number: Option<u32>,
+}
+
+// Staff workload of every person with assignments, to check that tasks are spread fairly
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaffWorkload {
+    pub persons: Vec<PersonWorkload>,
+    pub mean_tasks: f64,
+    pub min_tasks: usize,
+    pub max_tasks: usize,
+    // Gini coefficients, 0 if everybody does the same amount of work and close to 1 if a few persons do all of it
+    pub task_gini: f64,
+    pub staffing_time_gini: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonWorkload {
+    pub registrant_id: Option<PersonId>,
+    pub name: String,
+    // Number of assignments per task, e.g. "judge"
+    pub tasks: BTreeMap<String, usize>,
+    pub total_tasks: usize,
+    pub staffing_minutes: i64,
+    pub competing_minutes: i64,
+}
+
+impl PersonWorkload {
+    // Time spent staffing relative to competing, None for persons who don't compete
+    pub fn staffing_ratio(&self) -> Option<f64> {
+        (self.competing_minutes > 0).then(|| self.staffing_minutes as f64 / self.competing_minutes as f64)
+    }
+}
+
+pub fn gini(values: &[f64]) -> f64 {
+    let total: f64 = values.iter().sum();
+    if values.is_empty() || total <= 0.0 {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len() as f64;
+    let weighted: f64 = sorted.iter().enumerate().map(|(i, x)| (2.0 * (i as f64 + 1.0) - n - 1.0) * x).sum();
+    weighted / (n * total)
+}
+
+impl Competition {
+    // Workload of all persons with at least one assignment, the busiest first
+    pub fn staff_workload(&self) -> StaffWorkload {
+        let mut persons: Vec<PersonWorkload> = self.persons.iter()
+            .filter(|p| !p.assignments.is_empty())
+            .map(|person| {
+                let mut workload = PersonWorkload {
+                    registrant_id: person.registrant_id,
+                    name: person.name.clone(),
+                    tasks: BTreeMap::new(),
+                    total_tasks: 0,
+                    staffing_minutes: 0,
+                    competing_minutes: 0,
+                };
+                for assignment in &person.assignments {
+                    let minutes = self.schedule.find_activity(assignment.activity_id)
+                        .map_or(0, |a| a.get_duration().num_minutes());
+                    match &assignment.assignment_code {
+                        AssignmentCode::Competitor => workload.competing_minutes += minutes,
+                        AssignmentCode::Staff(task) => {
+                            *workload.tasks.entry(task.to_string()).or_default() += 1;
+                            workload.total_tasks += 1;
+                            workload.staffing_minutes += minutes;
+                        },
+                    }
+                }
+                workload
+            })
+            .collect();
+        persons.sort_by(|a, b| b.total_tasks.cmp(&a.total_tasks)
+            .then_with(|| b.staffing_minutes.cmp(&a.staffing_minutes))
+            .then_with(|| a.name.cmp(&b.name)));
+        let tasks: Vec<f64> = persons.iter().map(|p| p.total_tasks as f64).collect();
+        let staffing_minutes: Vec<f64> = persons.iter().map(|p| p.staffing_minutes as f64).collect();
+        StaffWorkload {
+            mean_tasks: if persons.is_empty() { 0.0 } else { tasks.iter().sum::<f64>() / persons.len() as f64 },
+            min_tasks: persons.iter().map(|p| p.total_tasks).min().unwrap_or_default(),
+            max_tasks: persons.iter().map(|p| p.total_tasks).max().unwrap_or_default(),
+            task_gini: gini(&tasks),
+            staffing_time_gini: gini(&staffing_minutes),
+            persons,
+        }
+    }
+
+    pub fn persons_with_role(&self, role: &Role) -> Vec<&Person> {
+        self.persons.iter()
+            .filter(|p| p.roles.contains(role))
+            .collect()
+    }
+
+    pub fn staff_for_activity(&self, activity_id: ActivityId) -> HashMap<StaffAssignment, Vec<&Person>> {
+        let mut staff: HashMap<StaffAssignment, Vec<&Person>> = HashMap::new();
+        for person in &self.persons {
+            for assignment in person.assignments.iter().filter(|a| a.activity_id == activity_id) {
+                if let AssignmentCode::Staff(task) = &assignment.assignment_code {
+                    staff.entry(task.clone()).or_default().push(person);
+                }
+            }
+        }
+        staff
+    }
+
+    pub fn staff_roster(&self) -> StaffRoster {
+        let mut days: BTreeMap<Date, BTreeMap<RoomId, RosterRoom>> = BTreeMap::new();
+        for (venue, room, activity) in self.schedule.activities_with_rooms() {
+            let mut tasks: BTreeMap<String, RosterTask> = BTreeMap::new();
+            for person in &self.persons {
+                for assignment in person.assignments.iter().filter(|a| a.activity_id == activity.id) {
+                    let AssignmentCode::Staff(task) = &assignment.assignment_code else {
+                        continue;
+                    };
+                    tasks.entry(task.to_string())
+                        .or_insert_with(|| RosterTask { task: task.clone(), persons: vec![] })
+                        .persons.push(RosterPerson {
+                            registrant_id: person.registrant_id,
+                            name: person.name.clone(),
+                            station_number: assignment.station_number,
+                        });
+                }
+            }
+            if tasks.is_empty() {
+                continue;
+            }
+            days.entry(venue.local_date(&activity.start_time))
+                .or_default()
+                .entry(room.id)
+                .or_insert_with(|| RosterRoom { room_id: room.id, name: room.name.clone(), shifts: vec![] })
+                .shifts.push(RosterShift {
+                    activity_id: activity.id,
+                    name: activity.name.clone(),
+                    start_time: venue.local_time(&activity.start_time),
+                    end_time: venue.local_time(&activity.end_time),
+                    staff: tasks.into_values().collect(),
+                });
+        }
+        StaffRoster {
+            days: days.into_iter()
+                .map(|(date, rooms)| RosterDay {
+                    date,
+                    rooms: rooms.into_values()
+                        .map(|mut room| {
+                            room.shifts.sort_by_key(|s| (s.start_time, s.end_time));
+                            room
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{Attempt, AttemptResult, EventId, PersonId, Round, RoundFormat, RoundResult};
+
+// Result a competitor needs on their next attempt to reach a goal, assuming all other results stay as they are
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Target {
+    // Reached even with a DNF
+    Guaranteed,
+    // Reached with this result or better
+    Needs(AttemptResult),
+    // Not reachable with any result
+    Impossible,
+}
+
+// Upper bound for the search if the round has no time limit, above any encoded multi blind result
+const MAX_RESULT: u32 = 999_999_999;
+
+// Binary search for the slowest result up to `upper` that reaches a goal, better results must never do worse
+fn slowest_reaching(upper: u32, reaches: impl Fn(AttemptResult) -> bool) -> Target {
+    if reaches(AttemptResult::DNF) {
+        return Target::Guaranteed;
+    }
+    if !reaches(AttemptResult::Success(1)) {
+        return Target::Impossible;
+    }
+    let (mut low, mut high) = (1, upper);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if reaches(AttemptResult::Success(mid)) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Target::Needs(AttemptResult::Success(low))
+}
+
+impl Round {
+    // Index of the attempt a person does next, None if they are done with the round (including missing the cutoff)
+    pub fn next_attempt_index(&self, person_id: PersonId) -> Option<usize> {
+        let attempts: Vec<AttemptResult> = self.results.iter()
+            .find(|r| r.person_id == person_id)
+            .map(|r| r.attempts.iter().take_while(|a| a.result != AttemptResult::Skipped).map(|a| a.result).collect())
+            .unwrap_or_default();
+        if attempts.len() >= self.format.expected_solve_count() as usize {
+            return None;
+        }
+        if let Some(cutoff) = &self.cutoff {
+            if attempts.len() >= cutoff.number_of_attempts && !attempts.iter().take(cutoff.number_of_attempts).any(|a| *a < cutoff.attempt_result) {
+                return None;
+            }
+        }
+        Some(attempts.len())
+    }
+
+    // The round as it would be if the person got `result` on their next attempt
+    fn with_next_attempt(&self, person_id: PersonId, index: usize, result: AttemptResult) -> Round {
+        let mut round = self.clone();
+        if !round.results.iter().any(|r| r.person_id == person_id) {
+            round.results.push(RoundResult {
+                person_id,
+                ranking: None,
+                attempts: vec![],
+                best: AttemptResult::Skipped,
+                average: AttemptResult::Skipped,
+            });
+        }
+        let person_result = round.results.iter_mut().find(|r| r.person_id == person_id).expect("result was just added");
+        person_result.attempts.resize(index, Attempt { result: AttemptResult::Skipped, reconstruction: None });
+        person_result.attempts.push(Attempt { result, reconstruction: None });
+        round.update_results();
+        round
+    }
+
+    // Ranking a person would have with `result` on their next attempt. None if they have no attempts left
+    pub fn projected_ranking(&self, person_id: PersonId, result: AttemptResult) -> Option<u64> {
+        let index = self.next_attempt_index(person_id)?;
+        self.with_next_attempt(person_id, index, result)
+            .results.iter()
+            .find(|r| r.person_id == person_id)?
+            .ranking
+    }
+
+    // Slowest next attempt that satisfies `goal`, which must not be harder to reach with better attempts
+    pub fn needed_for(&self, person_id: PersonId, goal: impl Fn(&Round) -> bool) -> Target {
+        let Some(index) = self.next_attempt_index(person_id) else {
+            return Target::Impossible;
+        };
+        let upper = self.time_limit.as_ref()
+            .filter(|t| t.cumulative_round_ids.is_empty())
+            .map_or(MAX_RESULT, |t| t.centiseconds.saturating_sub(1).max(1));
+        slowest_reaching(upper, |result| goal(&self.with_next_attempt(person_id, index, result)))
+    }
+
+    pub fn needed_for_ranking(&self, person_id: PersonId, ranking: u64) -> Target {
+        self.needed_for(person_id, |round| round.results.iter()
+            .find(|r| r.person_id == person_id)
+            .and_then(|r| r.ranking)
+            .is_some_and(|r| r <= ranking))
+    }
+
+    pub fn needed_for_podium(&self, person_id: PersonId) -> Target {
+        self.needed_for_ranking(person_id, 3)
+    }
+
+    // Based on the current results of the round, so the limits of percentage based conditions can still change.
+    // Impossible for finals
+    pub fn needed_to_advance(&self, person_id: PersonId) -> Target {
+        if self.advancement_condition.is_none() {
+            return Target::Impossible;
+        }
+        self.needed_for(person_id, |round| round.advancing_persons().is_some_and(|p| p.contains(&person_id)))
+    }
+}
+
+impl RoundResult {
+    // Last attempt needed for an average better than `average`, e.g. to show "needs sub-12.34" on live displays.
+    // Impossible unless exactly one attempt of a format with an average is missing
+    pub fn needed_for_average(&self, event_id: &EventId, format: &RoundFormat, average: AttemptResult) -> Target {
+        slowest_reaching(MAX_RESULT, |result| self.average_with_last_attempt(event_id, format, result).is_some_and(|a| a < average))
+    }
+}
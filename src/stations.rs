@@ -0,0 +1,122 @@
+use std::collections::{HashMap, HashSet};
+use crate::types::{ActivityCode, ActivityId, AssignmentCode, Competition, EventId, Person, PersonId, ResultType};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StationStrategy {
+    // In the order the competitors appear in the document
+    Sequential,
+    // Fastest competitor (by personal best of the activity's event) gets station 1
+    BySeed,
+    // Competitors keep the station they had in other activities if it is still free,
+    // which allows printing the station on all scorecards (see Groupifier's print_stations)
+    FixedPerCompetitor,
+}
+
+impl Person {
+    // World rankings of the average and single personal bests of an event, lower is better.
+    // Missing personal bests rank behind all others
+    pub fn seed(&self, event_id: &EventId) -> (u64, u64) {
+        let pb = |result_type: ResultType| self.personal_bests.iter()
+            .find(|pb| &pb.event_id == event_id && pb._type == result_type)
+            .map(|pb| pb.world_ranking);
+        (pb(ResultType::Average).unwrap_or(u64::MAX), pb(ResultType::Single).unwrap_or(u64::MAX))
+    }
+}
+
+impl Competition {
+    fn competitors_of(&self, activity_id: ActivityId) -> Vec<usize> {
+        self.persons.iter()
+            .enumerate()
+            .filter(|(_, p)| p.assignments.iter().any(|a| a.activity_id == activity_id && a.assignment_code == AssignmentCode::Competitor))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    fn set_station(&mut self, person_index: usize, activity_id: ActivityId, station_number: u32) {
+        for assignment in self.persons[person_index].assignments.iter_mut() {
+            if assignment.activity_id == activity_id && assignment.assignment_code == AssignmentCode::Competitor {
+                assignment.station_number = Some(station_number);
+            }
+        }
+    }
+
+    // Assigns station numbers 1..n to the competitors of an activity, overwriting existing station numbers
+    pub fn assign_stations(&mut self, activity_id: ActivityId, strategy: StationStrategy) {
+        let mut competitors = self.competitors_of(activity_id);
+        match strategy {
+            StationStrategy::Sequential => {},
+            StationStrategy::BySeed => {
+                let event_id = match self.schedule.find_activity(activity_id).map(|a| &a.activity_code) {
+                    Some(ActivityCode::Official(code)) => code.event.clone(),
+                    _ => return self.assign_stations(activity_id, StationStrategy::Sequential),
+                };
+                competitors.sort_by_cached_key(|index| self.persons[*index].seed(&event_id));
+            },
+            StationStrategy::FixedPerCompetitor => {
+                let mut taken = HashSet::new();
+                let mut unassigned = vec![];
+                for index in competitors {
+                    let mut previous: Vec<u32> = self.persons[index].assignments.iter()
+                        .filter(|a| a.activity_id != activity_id && a.assignment_code == AssignmentCode::Competitor)
+                        .filter_map(|a| a.station_number)
+                        .collect();
+                    previous.sort();
+                    match previous.into_iter().find(|s| !taken.contains(s)) {
+                        Some(station) => {
+                            taken.insert(station);
+                            self.set_station(index, activity_id, station);
+                        },
+                        None => unassigned.push(index),
+                    }
+                }
+                let mut free = (1..).filter(|s| !taken.contains(s));
+                for index in unassigned {
+                    let station = free.next().unwrap_or_default();
+                    self.set_station(index, activity_id, station);
+                }
+                return;
+            },
+        }
+        for (i, index) in competitors.into_iter().enumerate() {
+            self.set_station(index, activity_id, i as u32 + 1);
+        }
+    }
+
+    // Compacts the station numbers of an activity to 1..n, keeping the relative order.
+    // Competitors without a station are appended at the end
+    pub fn renumber_stations(&mut self, activity_id: ActivityId) {
+        let mut competitors: Vec<(Option<u32>, usize)> = self.competitors_of(activity_id).into_iter()
+            .map(|index| {
+                let station = self.persons[index].assignments.iter()
+                    .find(|a| a.activity_id == activity_id && a.assignment_code == AssignmentCode::Competitor)
+                    .and_then(|a| a.station_number);
+                (station, index)
+            })
+            .collect();
+        competitors.sort_by_key(|(station, index)| (station.is_none(), *station, *index));
+        for (i, (_, index)) in competitors.into_iter().enumerate() {
+            self.set_station(index, activity_id, i as u32 + 1);
+        }
+    }
+
+    // Station numbers used by more than one competitor within the same activity
+    pub fn duplicate_stations(&self) -> Vec<(ActivityId, u32, Vec<PersonId>)> {
+        let mut stations: HashMap<(ActivityId, u32), Vec<PersonId>> = HashMap::new();
+        for person in &self.persons {
+            let Some(person_id) = person.registrant_id else {
+                continue;
+            };
+            for assignment in person.assignments.iter().filter(|a| a.assignment_code == AssignmentCode::Competitor) {
+                if let Some(station) = assignment.station_number {
+                    stations.entry((assignment.activity_id, station)).or_default().push(person_id);
+                }
+            }
+        }
+        let mut duplicates: Vec<_> = stations.into_iter()
+            .filter(|(_, persons)| persons.len() > 1)
+            .map(|((activity_id, station), persons)| (activity_id, station, persons))
+            .collect();
+        duplicates.sort();
+        duplicates
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{AttemptResult, AttemptResultValue, Competition, Round, RoundId};
+
+pub const PERCENTILES: [u8; 5] = [10, 25, 50, 75, 90];
+
+// Aggregates of a set of results. Skipped results are ignored, DNS are counted but are not attempts, so they don't
+// count towards the DNF rate. Values are as stored, i.e. centiseconds, moves (times 100 for fewest moves averages)
+// or encoded multi blind results
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultStatistics {
+    pub successes: usize,
+    pub dnf: usize,
+    pub dns: usize,
+    // DNF among successes and DNF, None without either
+    pub dnf_rate: Option<f64>,
+    // Of the successful results
+    pub mean: Option<f64>,
+    pub standard_deviation: Option<f64>,
+    pub median: Option<f64>,
+    pub best: Option<AttemptResultValue>,
+    pub worst: Option<AttemptResultValue>,
+    pub percentiles: Vec<Percentile>,
+}
+
+// Nearest rank percentile of the successful results
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Percentile {
+    pub percentile: u8,
+    pub value: AttemptResultValue,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundStatistics {
+    pub round_id: RoundId,
+    // Competitors with at least one attempt
+    pub competitors: usize,
+    // All attempts of all competitors
+    pub attempts: ResultStatistics,
+    pub singles: ResultStatistics,
+    pub averages: ResultStatistics,
+}
+
+impl ResultStatistics {
+    pub fn from_results(results: impl IntoIterator<Item = AttemptResult>) -> Self {
+        let (mut dnf, mut dns) = (0, 0);
+        let mut values = vec![];
+        for result in results {
+            match result {
+                AttemptResult::Success(value) => values.push(value),
+                AttemptResult::DNF => dnf += 1,
+                AttemptResult::DNS => dns += 1,
+                AttemptResult::Skipped => {},
+            }
+        }
+        values.sort();
+        let successes = values.len();
+        let mean = (successes > 0).then(|| values.iter().map(|v| *v as f64).sum::<f64>() / successes as f64);
+        let standard_deviation = mean.map(|mean| {
+            (values.iter().map(|v| (*v as f64 - mean).powi(2)).sum::<f64>() / successes as f64).sqrt()
+        });
+        let median = (successes > 0).then(|| match successes % 2 {
+            0 => (values[successes / 2 - 1] as f64 + values[successes / 2] as f64) / 2.0,
+            _ => values[successes / 2] as f64,
+        });
+        let percentiles = if values.is_empty() {
+            vec![]
+        } else {
+            PERCENTILES.iter()
+                .map(|p| {
+                    let rank = (*p as usize * successes).div_ceil(100).max(1);
+                    Percentile { percentile: *p, value: values[rank - 1] }
+                })
+                .collect()
+        };
+        Self {
+            successes,
+            dnf,
+            dns,
+            dnf_rate: (successes + dnf > 0).then(|| dnf as f64 / (successes + dnf) as f64),
+            mean,
+            standard_deviation,
+            median,
+            best: values.first().copied(),
+            worst: values.last().copied(),
+            percentiles,
+        }
+    }
+}
+
+impl Round {
+    pub fn statistics(&self) -> RoundStatistics {
+        let results: Vec<_> = self.results.iter()
+            .filter(|r| r.attempts.iter().any(|a| a.result != AttemptResult::Skipped))
+            .collect();
+        RoundStatistics {
+            round_id: self.id.clone(),
+            competitors: results.len(),
+            attempts: ResultStatistics::from_results(results.iter().flat_map(|r| r.attempts.iter().map(|a| a.result))),
+            singles: ResultStatistics::from_results(results.iter().map(|r| r.best)),
+            averages: ResultStatistics::from_results(results.iter().map(|r| r.average)),
+        }
+    }
+}
+
+impl Competition {
+    // Statistics of every round with results
+    pub fn round_statistics(&self) -> Vec<RoundStatistics> {
+        self.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .filter(|r| !r.results.is_empty())
+            .map(|r| r.statistics())
+            .collect()
+    }
+}
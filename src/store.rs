@@ -0,0 +1,126 @@
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use chrono::{NaiveDateTime, SubsecRound};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use crate::diff::Change;
+use crate::types::{Competition, CompetitionId, DateTime};
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.3fZ";
+const EXTENSION: &str = ".json.gz";
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    // Competition ids are alphanumeric, anything else could name a file outside of the store
+    InvalidCompetitionId(CompetitionId),
+}
+
+impl Display for StoreError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Json(e) => write!(f, "{e}"),
+            Self::InvalidCompetitionId(id) => write!(f, "Invalid competition id {id:?}"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    pub competition_id: CompetitionId,
+    pub taken_at: DateTime,
+    pub path: PathBuf,
+}
+
+// A directory of gzip compressed snapshots of competitions, one file per snapshot named after the competition
+// and the time the snapshot was taken, e.g. "TestOpen2024-20240301T101500.000Z.json.gz"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    // Creates the directory if it doesn't exist yet
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, StoreError> {
+        std::fs::create_dir_all(dir.as_ref()).map_err(StoreError::Io)?;
+        Ok(Self { dir: dir.as_ref().to_path_buf() })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    // Saves a snapshot, replacing one of the same competition taken at the same millisecond. The snapshot is
+    // written to a temporary file first, so readers never see a partially written one
+    pub fn save(&self, competition: &Competition, now: DateTime) -> Result<Snapshot, StoreError> {
+        if competition.id.is_empty() || !competition.id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(StoreError::InvalidCompetitionId(competition.id.clone()));
+        }
+        let now = now.trunc_subsecs(3);
+        let name = format!("{}-{}{EXTENSION}", competition.id, now.format(TIMESTAMP_FORMAT));
+        let path = self.dir.join(&name);
+        let temp_path = self.dir.join(format!("{name}.tmp"));
+        let written = write_snapshot(&temp_path, competition).and_then(|_| std::fs::rename(&temp_path, &path).map_err(StoreError::Io));
+        if written.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+        written?;
+        Ok(Snapshot { competition_id: competition.id.clone(), taken_at: now, path })
+    }
+
+    // Snapshots of a competition, oldest first. Files that don't follow the naming scheme are ignored
+    pub fn list(&self, competition_id: &str) -> Result<Vec<Snapshot>, StoreError> {
+        let mut snapshots = vec![];
+        for entry in std::fs::read_dir(&self.dir).map_err(StoreError::Io)? {
+            let path = entry.map_err(StoreError::Io)?.path();
+            let Some(snapshot) = parse_snapshot_path(&path) else {
+                continue;
+            };
+            if snapshot.competition_id == competition_id {
+                snapshots.push(snapshot);
+            }
+        }
+        snapshots.sort_by_key(|s| s.taken_at);
+        Ok(snapshots)
+    }
+
+    pub fn latest(&self, competition_id: &str) -> Result<Option<Snapshot>, StoreError> {
+        Ok(self.list(competition_id)?.pop())
+    }
+
+    pub fn load(&self, snapshot: &Snapshot) -> Result<Competition, StoreError> {
+        let file = File::open(&snapshot.path).map_err(StoreError::Io)?;
+        serde_json::from_reader(GzDecoder::new(BufReader::new(file))).map_err(StoreError::Json)
+    }
+
+    // Changes from the older to the newer snapshot
+    pub fn diff(&self, old: &Snapshot, new: &Snapshot) -> Result<Vec<Change>, StoreError> {
+        self.load(old)?.diff(&self.load(new)?).map_err(StoreError::Json)
+    }
+}
+
+fn write_snapshot(path: &Path, competition: &Competition) -> Result<(), StoreError> {
+    let file = File::create(path).map_err(StoreError::Io)?;
+    let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+    serde_json::to_writer(&mut encoder, competition).map_err(StoreError::Json)?;
+    let file = encoder.finish().map_err(StoreError::Io)?.into_inner().map_err(|e| StoreError::Io(e.into_error()))?;
+    file.sync_all().map_err(StoreError::Io)
+}
+
+fn parse_snapshot_path(path: &Path) -> Option<Snapshot> {
+    let name = path.file_name()?.to_str()?.strip_suffix(EXTENSION)?;
+    let (competition_id, timestamp) = name.rsplit_once('-')?;
+    let taken_at = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?.and_utc();
+    Some(Snapshot {
+        competition_id: competition_id.to_string(),
+        taken_at,
+        path: path.to_path_buf(),
+    })
+}
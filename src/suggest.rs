@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use crate::types::{ActivityCode, AdvancementCondition, AttemptResult, Competition, Cutoff, EventId, RegistrationStatus, Round, RoundId, TimeLimit};
+
+// Distribution of single solve times of the competitors of an event, as points (fraction, centiseconds) of its
+// cumulative distribution function in increasing order. Between points the distribution is linear
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolveTimeDistribution {
+    pub points: Vec<(f64, u32)>,
+}
+
+impl SolveTimeDistribution {
+    // Rough distributions of competitors at an average competition. None for events without time limits
+    // and events that are no longer held
+    pub fn default_for(event_id: &EventId) -> Option<Self> {
+        let points: &[(f64, u32)] = match event_id {
+            EventId::Cube333 => &[(0.0, 600), (0.25, 1500), (0.5, 2100), (0.75, 3000), (0.9, 4500), (1.0, 9000)],
+            EventId::Cube222 => &[(0.0, 150), (0.25, 450), (0.5, 700), (0.75, 1100), (0.9, 1700), (1.0, 4000)],
+            EventId::Cube444 => &[(0.0, 2200), (0.25, 5500), (0.5, 7500), (0.75, 10500), (0.9, 14000), (1.0, 30000)],
+            EventId::Cube555 => &[(0.0, 4000), (0.25, 10000), (0.5, 13500), (0.75, 18000), (0.9, 24000), (1.0, 45000)],
+            EventId::Cube666 => &[(0.0, 7500), (0.25, 19000), (0.5, 25000), (0.75, 33000), (0.9, 42000), (1.0, 70000)],
+            EventId::Cube777 => &[(0.0, 11000), (0.25, 28000), (0.5, 37000), (0.75, 48000), (0.9, 60000), (1.0, 90000)],
+            EventId::Blind333 => &[(0.0, 1500), (0.25, 6000), (0.5, 12000), (0.75, 24000), (0.9, 40000), (1.0, 60000)],
+            EventId::OneHanded333 => &[(0.0, 800), (0.25, 2500), (0.5, 3500), (0.75, 5000), (0.9, 7000), (1.0, 15000)],
+            EventId::Clock => &[(0.0, 300), (0.25, 900), (0.5, 1400), (0.75, 2200), (0.9, 3500), (1.0, 9000)],
+            EventId::Megaminx => &[(0.0, 2500), (0.25, 7000), (0.5, 10000), (0.75, 14000), (0.9, 19000), (1.0, 40000)],
+            EventId::Pyraminx => &[(0.0, 100), (0.25, 500), (0.5, 800), (0.75, 1200), (0.9, 1900), (1.0, 5000)],
+            EventId::Skewb => &[(0.0, 100), (0.25, 600), (0.5, 900), (0.75, 1400), (0.9, 2200), (1.0, 6000)],
+            EventId::Square1 => &[(0.0, 500), (0.25, 2000), (0.5, 3000), (0.75, 4500), (0.9, 7000), (1.0, 15000)],
+            EventId::Blind444 => &[(0.0, 6000), (0.25, 30000), (0.5, 60000), (0.75, 120000), (0.9, 180000), (1.0, 360000)],
+            EventId::Blind555 => &[(0.0, 12000), (0.25, 60000), (0.5, 120000), (0.75, 200000), (0.9, 300000), (1.0, 600000)],
+            _ => return None,
+        };
+        Some(Self { points: points.to_vec() })
+    }
+
+    // Fraction of solves faster than `centiseconds`
+    pub fn cdf(&self, centiseconds: f64) -> f64 {
+        let Some(first) = self.points.first() else {
+            return 1.0;
+        };
+        if centiseconds < first.1 as f64 {
+            return 0.0;
+        }
+        for window in self.points.windows(2) {
+            let ((p0, t0), (p1, t1)) = (window[0], window[1]);
+            if centiseconds < t1 as f64 {
+                return p0 + (p1 - p0) * (centiseconds - t0 as f64) / (t1 - t0) as f64;
+            }
+        }
+        1.0
+    }
+
+    // The time below which `fraction` of solves are
+    pub fn quantile(&self, fraction: f64) -> u32 {
+        for window in self.points.windows(2) {
+            let ((p0, t0), (p1, t1)) = (window[0], window[1]);
+            if fraction <= p1 && p1 > p0 {
+                return t0 + ((t1 - t0) as f64 * (fraction - p0).max(0.0) / (p1 - p0)) as u32;
+            }
+        }
+        self.points.last().map(|p| p.1).unwrap_or_default()
+    }
+
+    // Expected time of a solve that is stopped at the time limit, the integral of 1 - F(t) from 0 to `limit`
+    pub fn expected_capped(&self, limit: f64) -> f64 {
+        let mut expected = 0.0;
+        let (mut t0, mut p0) = (0.0, 0.0);
+        for &(p1, t1) in &self.points {
+            let t1 = t1 as f64;
+            if t1 > t0 {
+                let end = t1.min(limit);
+                let p_end = p0 + (p1 - p0) * (end - t0) / (t1 - t0);
+                expected += (end - t0) * (2.0 - p0 - p_end) / 2.0;
+            }
+            if t1 >= limit {
+                return expected;
+            }
+            (t0, p0) = (t1, p1);
+        }
+        expected + (limit - t0).max(0.0) * (1.0 - p0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuggestionConfig {
+    pub stations_per_room: u32,
+    // Scrambling, inspection and running per attempt, in centiseconds
+    pub overhead_per_attempt: u32,
+    // Candidates in decreasing order, the first one that makes the round fit is suggested
+    pub time_limits: Vec<u32>,
+    // Cutoffs are tried at these quantiles of the distribution in decreasing order, after all time limits failed without one
+    pub cutoff_quantiles: Vec<f64>,
+    // Replaces the default distribution of an event
+    pub distributions: HashMap<EventId, SolveTimeDistribution>,
+}
+
+impl Default for SuggestionConfig {
+    fn default() -> Self {
+        Self {
+            stations_per_room: 8,
+            overhead_per_attempt: 4500,
+            time_limits: vec![60000, 30000, 18000, 12000, 9000, 6000],
+            cutoff_quantiles: vec![0.9, 0.75, 0.6, 0.5],
+            distributions: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundSuggestion {
+    pub round_id: RoundId,
+    pub competitors: usize,
+    // Station time scheduled for the round and expected to be needed with the suggested limits, in centiseconds
+    pub available: u64,
+    pub required: u64,
+    pub time_limit: TimeLimit,
+    pub cutoff: Option<Cutoff>,
+    // False if not even the most restrictive candidate makes the round fit
+    pub feasible: bool,
+}
+
+impl Competition {
+    // Estimated number of competitors of a round: the results if there are any, accepted registrations for first
+    // rounds, otherwise the number of competitors the previous round advances
+    pub fn expected_competitors(&self, round_id: &RoundId) -> usize {
+        if let Some(round) = self.find_round(round_id).filter(|r| !r.results.is_empty()) {
+            return round.results.len();
+        }
+        if round_id.round <= 1 {
+            return self.persons.iter()
+                .filter_map(|p| p.registration.as_ref())
+                .filter(|r| r.status == RegistrationStatus::Accepted && r.event_ids.contains(&round_id.event))
+                .count();
+        }
+        let previous_id = RoundId { event: round_id.event.clone(), round: round_id.round - 1 };
+        let previous = self.expected_competitors(&previous_id);
+        let max_advancing = previous * 3 / 4;
+        match self.find_round(&previous_id).and_then(|r| r.advancement_condition.as_ref()) {
+            Some(AdvancementCondition::Ranking { level }) => (*level as usize).min(max_advancing),
+            Some(AdvancementCondition::Percent { level }) => (previous * *level as usize / 100).min(max_advancing),
+            Some(AdvancementCondition::AttemptResult { .. }) | None => max_advancing,
+        }
+    }
+
+    // Station time scheduled for a round in centiseconds, the durations of its activities in all rooms
+    pub fn scheduled_station_time(&self, round_id: &RoundId, stations_per_room: u32) -> u64 {
+        self.schedule.all_activities().iter()
+            .filter(|a| matches!(&a.activity_code, ActivityCode::Official(code) if *round_id == *code))
+            .map(|a| a.get_duration().num_milliseconds().max(0) as u64 / 10 * stations_per_room as u64)
+            .sum()
+    }
+
+    // Suggests the least restrictive time limit and cutoff for every round with a time limit that lets the expected
+    // competitors finish in the scheduled time. Rounds without scheduled activities are skipped
+    pub fn suggest_limits(&self, config: &SuggestionConfig) -> Vec<RoundSuggestion> {
+        self.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .filter_map(|round| self.suggest_round_limits(round, config))
+            .collect()
+    }
+
+    fn suggest_round_limits(&self, round: &Round, config: &SuggestionConfig) -> Option<RoundSuggestion> {
+        let distribution = config.distributions.get(&round.id.event).cloned()
+            .or_else(|| SolveTimeDistribution::default_for(&round.id.event))?;
+        let available = self.scheduled_station_time(&round.id, config.stations_per_room);
+        if available == 0 {
+            return None;
+        }
+        let competitors = self.expected_competitors(&round.id);
+        let attempts = round.format.expected_solve_count() as usize;
+        let required = |time_limit: u32, cutoff: Option<&Cutoff>| {
+            let per_attempt = distribution.expected_capped(time_limit as f64) + config.overhead_per_attempt as f64;
+            let per_competitor = match cutoff {
+                Some(cutoff) => {
+                    let value = cutoff.attempt_result.value().unwrap_or_default() as f64;
+                    let cutoff_attempts = cutoff.number_of_attempts.min(attempts);
+                    let making_cutoff = 1.0 - (1.0 - distribution.cdf(value)).powi(cutoff_attempts as i32);
+                    per_attempt * (cutoff_attempts as f64 + making_cutoff * (attempts - cutoff_attempts) as f64)
+                },
+                None => per_attempt * attempts as f64,
+            };
+            (per_competitor * competitors as f64).ceil() as u64
+        };
+        let cutoff_attempts = if attempts == 5 { 2 } else { 1 };
+        let candidates = config.time_limits.iter()
+            .map(|time_limit| (*time_limit, None))
+            .chain(config.cutoff_quantiles.iter().map(|quantile| {
+                let cutoff = Cutoff {
+                    number_of_attempts: cutoff_attempts,
+                    attempt_result: AttemptResult::Success(distribution.quantile(*quantile) / 100 * 100),
+                };
+                (config.time_limits.last().copied().unwrap_or(60000), Some(cutoff))
+            }));
+        let mut suggestion = None;
+        for (time_limit, cutoff) in candidates {
+            let needed = required(time_limit, cutoff.as_ref());
+            let feasible = needed <= available;
+            suggestion = Some(RoundSuggestion {
+                round_id: round.id.clone(),
+                competitors,
+                available,
+                required: needed,
+                time_limit: TimeLimit { centiseconds: time_limit, cumulative_round_ids: vec![] },
+                cutoff,
+                feasible,
+            });
+            if feasible {
+                break;
+            }
+        }
+        suggestion
+    }
+}
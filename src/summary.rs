@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use serde::{Deserialize, Serialize};
+use crate::export::wire_name;
+use crate::results::format_result;
+use crate::types::{AdvancementCondition, AssignmentCode, Competition, Cutoff, Date, EventId, RegistrationStatus, ResultType, RoundFormat, RoundId, TimeLimit};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompetitionSummary {
+    pub id: String,
+    pub name: String,
+    pub competitor_count: usize,
+    pub competitor_limit: Option<u32>,
+    pub staff_count: usize,
+    pub role_counts: BTreeMap<String, usize>,
+    pub events: Vec<EventSummary>,
+    pub days: Vec<DaySummary>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventSummary {
+    pub event_id: EventId,
+    pub registered: usize,
+    pub rounds: Vec<RoundSummary>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundSummary {
+    pub round_id: RoundId,
+    pub format: RoundFormat,
+    pub time_limit: Option<TimeLimit>,
+    pub cutoff: Option<Cutoff>,
+    pub advancement_condition: Option<AdvancementCondition>,
+    pub competitors: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DaySummary {
+    pub date: Date,
+    pub activities: Vec<ScheduleEntry>,
+}
+
+// A top level activity, times are local to the venue
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleEntry {
+    pub start_time: chrono::NaiveDateTime,
+    pub end_time: chrono::NaiveDateTime,
+    pub venue: String,
+    pub room: String,
+    pub name: String,
+}
+
+impl Competition {
+    pub fn summary(&self) -> CompetitionSummary {
+        let accepted: Vec<_> = self.persons.iter()
+            .filter_map(|p| p.registration.as_ref())
+            .filter(|r| r.status == RegistrationStatus::Accepted && r.is_competing)
+            .collect();
+        let mut role_counts = BTreeMap::new();
+        for role in self.persons.iter().flat_map(|p| p.roles.iter()) {
+            *role_counts.entry(wire_name(role)).or_insert(0) += 1;
+        }
+        let staff_count = self.persons.iter()
+            .filter(|p| p.assignments.iter().any(|a| matches!(a.assignment_code, AssignmentCode::Staff(_))))
+            .count();
+        let events = self.events.iter()
+            .map(|event| EventSummary {
+                event_id: event.id.clone(),
+                registered: accepted.iter().filter(|r| r.event_ids.contains(&event.id)).count(),
+                rounds: event.rounds.iter()
+                    .map(|round| RoundSummary {
+                        round_id: round.id.clone(),
+                        format: round.format.clone(),
+                        time_limit: round.time_limit.clone(),
+                        cutoff: round.cutoff.clone(),
+                        advancement_condition: round.advancement_condition.clone(),
+                        competitors: round.results.len(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        let mut days: BTreeMap<Date, Vec<ScheduleEntry>> = BTreeMap::new();
+        for venue in &self.schedule.venues {
+            for room in &venue.rooms {
+                for activity in &room.activities {
+                    days.entry(venue.local_date(&activity.start_time)).or_default().push(ScheduleEntry {
+                        start_time: venue.local_time(&activity.start_time),
+                        end_time: venue.local_time(&activity.end_time),
+                        venue: venue.name.clone(),
+                        room: room.name.clone(),
+                        name: activity.name.clone(),
+                    });
+                }
+            }
+        }
+        CompetitionSummary {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            competitor_count: accepted.len(),
+            competitor_limit: self.competitor_limit,
+            staff_count,
+            role_counts,
+            events,
+            days: days.into_iter()
+                .map(|(date, mut activities)| {
+                    activities.sort_by_key(|a| a.start_time);
+                    DaySummary { date, activities }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CompetitionSummary {
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        let _ = self.write_markdown(&mut md);
+        md
+    }
+
+    fn write_markdown(&self, md: &mut String) -> std::fmt::Result {
+        writeln!(md, "# {}", self.name)?;
+        writeln!(md)?;
+        match self.competitor_limit {
+            Some(limit) => writeln!(md, "**Competitors:** {} / {limit}", self.competitor_count)?,
+            None => writeln!(md, "**Competitors:** {}", self.competitor_count)?,
+        }
+        writeln!(md, "**Staff:** {}", self.staff_count)?;
+        writeln!(md)?;
+        writeln!(md, "## Events")?;
+        writeln!(md)?;
+        writeln!(md, "| Event | Round | Format | Time limit | Cutoff | Proceed | Competitors |")?;
+        writeln!(md, "|---|---|---|---|---|---|---|")?;
+        for event in &self.events {
+            for round in &event.rounds {
+                writeln!(md, "| {} | {} | {} | {} | {} | {} | {} |",
+                    event.event_id.get_name(),
+                    round.round_id.round,
+                    format_name(&round.format),
+                    round.time_limit.as_ref().map(format_time_limit).unwrap_or_default(),
+                    round.cutoff.as_ref().map(|c| format_cutoff(&event.event_id, c)).unwrap_or_default(),
+                    round.advancement_condition.as_ref().map(|a| format_advancement(&event.event_id, &round.format, a)).unwrap_or_default(),
+                    if round.round_id.round == 1 { event.registered } else { round.competitors },
+                )?;
+            }
+        }
+        writeln!(md)?;
+        writeln!(md, "## Schedule")?;
+        for day in &self.days {
+            writeln!(md)?;
+            writeln!(md, "### {}", day.date.format("%A, %B %-d, %Y"))?;
+            writeln!(md)?;
+            writeln!(md, "| Time | Room | Activity |")?;
+            writeln!(md, "|---|---|---|")?;
+            for activity in &day.activities {
+                writeln!(md, "| {} - {} | {} | {} |", activity.start_time.format("%H:%M"), activity.end_time.format("%H:%M"), activity.room, activity.name)?;
+            }
+        }
+        if !self.role_counts.is_empty() {
+            writeln!(md)?;
+            writeln!(md, "## Roles")?;
+            writeln!(md)?;
+            writeln!(md, "| Role | Count |")?;
+            writeln!(md, "|---|---|")?;
+            for (role, count) in &self.role_counts {
+                writeln!(md, "| {role} | {count} |")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn format_name(format: &RoundFormat) -> &'static str {
+    match format {
+        RoundFormat::BestOf1 => "Best of 1",
+        RoundFormat::BestOf2 => "Best of 2",
+        RoundFormat::BestOf3 => "Best of 3",
+        RoundFormat::AverageOf5 => "Average of 5",
+        RoundFormat::MeanOf3 => "Mean of 3",
+    }
+}
+
+pub fn format_time_limit(time_limit: &TimeLimit) -> String {
+    let limit = crate::results::format_centiseconds(time_limit.centiseconds);
+    if time_limit.cumulative_round_ids.is_empty() {
+        limit
+    } else {
+        format!("{limit} cumulative")
+    }
+}
+
+pub fn format_cutoff(event_id: &EventId, cutoff: &Cutoff) -> String {
+    format!("{} attempt(s) to get < {}", cutoff.number_of_attempts, format_result(event_id, &ResultType::Single, &cutoff.attempt_result))
+}
+
+pub fn format_advancement(event_id: &EventId, format: &RoundFormat, condition: &AdvancementCondition) -> String {
+    match condition {
+        AdvancementCondition::Ranking { level } => format!("Top {level}"),
+        AdvancementCondition::Percent { level } => format!("Top {level}%"),
+        AdvancementCondition::AttemptResult { level } => format!("Result < {}", format_result(event_id, &format.sort_by(), level)),
+    }
+}
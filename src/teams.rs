@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::scoring::rank_by;
+use crate::types::{AttemptResult, Competition, EventId, Extension, PersonId, ResultType, RoundId};
+
+pub const EXTENSION_ID: &str = crate::extensions::TEAMS_ID;
+pub const SPEC_URL: &str = crate::extensions::TEAMS_SPEC_URL;
+
+// Teams of persons for side competitions, e.g. between clubs or regions. A person can be part of several teams
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamsExtension {
+    pub id: MustBe!("wcif-rs.Teams"),
+    pub spec_url: String,
+    pub data: Teams,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Teams {
+    pub teams: Vec<Team>,
+    // Number of best member results that count towards the team result, all members if None
+    pub counting_members: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Team {
+    pub id: u32,
+    pub name: String,
+    pub person_ids: Vec<PersonId>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamRoundStanding {
+    pub ranking: u64,
+    pub team_id: u32,
+    pub name: String,
+    // Sum of the counting member results, DNF if fewer members than counting have a successful result
+    pub result: AttemptResult,
+    // Results of the members who competed in the round, best first
+    pub member_results: Vec<(PersonId, AttemptResult)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamSumOfRanks {
+    pub ranking: u64,
+    pub team_id: u32,
+    pub name: String,
+    pub total: u64,
+    // Sum of ranks of every member, best first
+    pub member_totals: Vec<(PersonId, u64)>,
+}
+
+impl Teams {
+    pub fn find_team(&self, team_id: u32) -> Option<&Team> {
+        self.teams.iter().find(|t| t.id == team_id)
+    }
+
+    pub fn teams_of(&self, person_id: PersonId) -> Vec<&Team> {
+        self.teams.iter().filter(|t| t.person_ids.contains(&person_id)).collect()
+    }
+
+    fn counting(&self, team: &Team) -> usize {
+        self.counting_members.unwrap_or(team.person_ids.len())
+    }
+}
+
+impl Competition {
+    pub fn teams(&self) -> Option<&Teams> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::Teams(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    pub fn teams_mut(&mut self) -> &mut Teams {
+        if self.teams().is_none() {
+            self.extensions.push(Extension::Teams(TeamsExtension {
+                id: Default::default(),
+                spec_url: SPEC_URL.to_string(),
+                data: Teams::default(),
+            }));
+        }
+        self.extensions.iter_mut().find_map(|e| match e {
+            Extension::Teams(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("teams extension was just added")
+    }
+
+    // Team results of a round, the sum of the best counting member results. Sums are only meaningful for times and
+    // moves, not for encoded multi blind results. Teams without a member in the round are left out
+    pub fn team_round_standings(&self, round_id: &RoundId, result_type: &ResultType) -> Vec<TeamRoundStanding> {
+        let (Some(teams), Some(round)) = (self.teams(), self.find_round(round_id)) else {
+            return vec![];
+        };
+        let mut standings: Vec<TeamRoundStanding> = teams.teams.iter()
+            .filter_map(|team| {
+                let mut member_results: Vec<(PersonId, AttemptResult)> = round.results.iter()
+                    .filter(|r| team.person_ids.contains(&r.person_id))
+                    .map(|r| (r.person_id, *r.get_result(result_type)))
+                    .filter(|(_, r)| *r != AttemptResult::Skipped)
+                    .collect();
+                if member_results.is_empty() {
+                    return None;
+                }
+                member_results.sort_by_key(|(_, r)| *r);
+                let counting: Vec<u32> = member_results.iter()
+                    .take(teams.counting(team))
+                    .filter_map(|(_, r)| match r {
+                        AttemptResult::Success(value) => Some(*value),
+                        _ => None,
+                    })
+                    .collect();
+                let result = if counting.len() < teams.counting(team) {
+                    AttemptResult::DNF
+                } else {
+                    AttemptResult::Success(counting.iter().sum())
+                };
+                Some(TeamRoundStanding { ranking: 0, team_id: team.id, name: team.name.clone(), result, member_results })
+            })
+            .collect();
+        standings.sort_by(|a, b| a.result.cmp(&b.result).then_with(|| a.name.cmp(&b.name)));
+        let mut previous: Option<(AttemptResult, u64)> = None;
+        for (position, standing) in standings.iter_mut().enumerate() {
+            standing.ranking = match previous {
+                Some((result, ranking)) if result == standing.result => ranking,
+                _ => position as u64 + 1,
+            };
+            previous = Some((standing.result, standing.ranking));
+        }
+        standings
+    }
+
+    // Team standings by the sum of ranks of their best counting members over the given events (all events if
+    // empty), lowest first. Members get the number of competitors of an event plus one for events they didn't
+    // compete in, like in `sum_of_ranks`. Teams with fewer members than counting get that for the missing members too
+    pub fn team_sum_of_ranks(&self, event_ids: &[EventId]) -> Vec<TeamSumOfRanks> {
+        let Some(teams) = self.teams() else {
+            return vec![];
+        };
+        let placements: Vec<HashMap<PersonId, u64>> = self.events.iter()
+            .filter(|e| event_ids.is_empty() || event_ids.contains(&e.id))
+            .map(|e| self.event_placements(&e.id))
+            .collect();
+        let mut standings: Vec<TeamSumOfRanks> = teams.teams.iter()
+            .map(|team| {
+                let mut member_totals: Vec<(PersonId, u64)> = team.person_ids.iter()
+                    .map(|person_id| (*person_id, placements.iter()
+                        .map(|p| p.get(person_id).copied().unwrap_or(p.len() as u64 + 1))
+                        .sum()))
+                    .collect();
+                member_totals.sort_by_key(|(_, total)| *total);
+                let missing = teams.counting(team).saturating_sub(member_totals.len()) as u64;
+                let absent_total: u64 = placements.iter().map(|p| p.len() as u64 + 1).sum();
+                TeamSumOfRanks {
+                    ranking: 0,
+                    team_id: team.id,
+                    name: team.name.clone(),
+                    total: member_totals.iter().take(teams.counting(team)).map(|(_, total)| total).sum::<u64>() + missing * absent_total,
+                    member_totals,
+                }
+            })
+            .collect();
+        standings.sort_by(|a, b| a.total.cmp(&b.total).then_with(|| a.name.cmp(&b.name)));
+        rank_by(&mut standings, |s| s.total as f64, |s, ranking| s.ranking = ranking);
+        standings
+    }
+}
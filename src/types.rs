@@ -1,7 +1,6 @@
 use std::fmt::{Debug, Display, Formatter};
 use std::num::ParseIntError;
 use std::str::FromStr;
-use monostate::MustBe;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use chrono::TimeDelta;
@@ -66,7 +65,7 @@ pub type AttemptResultValue = u32;
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Competition {
-    pub format_version: MustBe!("1.0"),
+    pub format_version: FormatVersion,
     pub id: CompetitionId,
     pub name: String,
     pub short_name: String,
@@ -74,17 +73,117 @@ pub struct Competition {
     pub persons: Vec<Person>,
     pub events: Vec<Event>,
     pub schedule: Schedule,
-    pub registration_info: RegistrationInfo,
+    pub registration_info: Option<RegistrationInfo>,
     pub competitor_limit: Option<u32>,
+    #[serde(default)]
     pub extensions: Vec<Extension>
 }
 
+// Version of the WCIF specification a document follows. Deserializing only accepts versions this crate knows.
+// Lenient parsing with `compliance::parse` also accepts compatible versions it doesn't know, which are kept as is,
+// see FormatVersion::is_compatible
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
+pub enum FormatVersion {
+    #[default]
+    V1_0,
+    Unknown(String),
+}
+
+impl FormatVersion {
+    pub const LATEST: FormatVersion = FormatVersion::V1_0;
+
+    // Minor versions only add fields, so documents of the same major version can still be read
+    pub fn is_compatible(&self) -> bool {
+        match self {
+            Self::V1_0 => true,
+            Self::Unknown(version) => version.split_once('.').is_some_and(|(major, minor)| major == "1" && minor.parse::<u32>().is_ok()),
+        }
+    }
+}
+
+impl Display for FormatVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::V1_0 => write!(f, "1.0"),
+            Self::Unknown(version) => write!(f, "{version}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatVersionParseError(pub String);
+
+impl Display for FormatVersionParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unsupported format version {}", self.0)
+    }
+}
+
+impl std::error::Error for FormatVersionParseError {}
+
+impl FromStr for FormatVersion {
+    type Err = FormatVersionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1.0" => Ok(Self::V1_0),
+            _ => Err(FormatVersionParseError(s.to_string())),
+        }
+    }
+}
+
+impl FormatVersion {
+    // Any version found in a document, known or not
+    pub(crate) fn from_document(version: &str) -> Self {
+        version.parse().unwrap_or_else(|_| Self::Unknown(version.to_string()))
+    }
+}
+
+impl Competition {
+    pub fn format_version(&self) -> &FormatVersion {
+        &self.format_version
+    }
+
+    pub fn find_person(&self, person_id: PersonId) -> Option<&Person> {
+        self.persons.iter().find(|p| p.registrant_id == Some(person_id))
+    }
+
+    pub fn find_person_mut(&mut self, person_id: PersonId) -> Option<&mut Person> {
+        self.persons.iter_mut().find(|p| p.registrant_id == Some(person_id))
+    }
+
+    pub fn find_round(&self, round_id: &RoundId) -> Option<&Round> {
+        self.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .find(|r| r.id == *round_id)
+    }
+
+    pub fn find_round_mut(&mut self, round_id: &RoundId) -> Option<&mut Round> {
+        self.events.iter_mut()
+            .flat_map(|e| e.rounds.iter_mut())
+            .find(|r| r.id == *round_id)
+    }
+
+    pub fn start_datetime(&self) -> DateTime {
+        self.schedule.start_datetime()
+    }
+
+    pub fn end_datetime(&self) -> DateTime {
+        self.schedule.end_datetime()
+    }
+
+    pub fn is_ongoing(&self, now: &DateTime) -> bool {
+        self.start_datetime() <= *now && *now < self.end_datetime()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Series {
     pub id: SeriesId,
     pub name: String,
     pub short_name: String,
+    #[serde(alias = "competitionIds")]
     pub competitions_ids: Vec<CompetitionId>
 }
 
@@ -101,15 +200,20 @@ pub struct Person {
     pub birthdate: chrono::NaiveDate,
     #[cfg(feature = "private_properties")]
     pub email: String,
+    #[serde(default, deserialize_with = "Avatar::deserialize_lenient")]
     pub avatar: Option<Avatar>,
+    #[serde(default)]
     pub roles: Vec<Role>,
     pub registration: Option<Registration>,
+    #[serde(default)]
     pub assignments: Vec<Assignment>,
+    #[serde(default)]
     pub personal_bests: Vec<PersonalBest>,
+    #[serde(default)]
     pub extensions: Vec<Extension>
 }
 
-#[derive(Clone, Debug, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Gender {
     #[serde(rename = "m")]
     Male,
@@ -119,7 +223,7 @@ pub enum Gender {
     Other
 }
 
-#[derive(Clone, PartialEq, Hash, SerializeDisplay, DeserializeFromStr)]
+#[derive(Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
 pub struct WCAId {
     pub year: u16,
     pub discriminant: u8,
@@ -132,6 +236,19 @@ impl Display for WCAId {
     }
 }
 
+impl PartialOrd for WCAId {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Same order as the string representation
+impl Ord for WCAId {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.year, &self.name, self.discriminant).cmp(&(other.year, &other.name, other.discriminant))
+    }
+}
+
 impl Debug for WCAId {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self}")
@@ -159,9 +276,9 @@ impl FromStr for WCAId {
         if s.len() != 10 {
             return Err(WCAIdParseError::LengthError(s.len()))
         }
-        let year = u16::from_str(&s[..4]).map_err(|e|WCAIdParseError::ParseIntError(e))?;
+        let year = u16::from_str(&s[..4]).map_err(WCAIdParseError::ParseIntError)?;
         let name = &s[4..8];
-        let discriminant = u8::from_str(&s[8..]).map_err(|e|WCAIdParseError::ParseIntError(e))?;
+        let discriminant = u8::from_str(&s[8..]).map_err(WCAIdParseError::ParseIntError)?;
         Ok(WCAId {
             year,
             name: name.to_string(),
@@ -170,24 +287,74 @@ impl FromStr for WCAId {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+// Roles as emitted by the WCA website ("delegate", "trainee-delegate", "organizer") and staff roles used by other
+// tools ("staff" or "staff-judge", "staff-dataentry" etc.). Unknown roles are kept as they are
+#[derive(Clone, Debug, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
 pub enum Role {
-    #[serde(rename = "delegate")]
     Delegate,
-    #[serde(rename = "trainee-delegate")]
     TraineeDelegate,
-    #[serde(rename = "organizer")]
     Organizer,
-    #[serde(untagged)]
+    Staff(Option<StaffAssignment>),
     Other(String),
 }
 
+// Spellings used by older documents and other tools, as (alias, canonical role)
+const ROLE_ALIASES: [(&str, &str); 6] = [
+    ("trainee_delegate", "trainee-delegate"),
+    ("traineeDelegate", "trainee-delegate"),
+    ("candidate-delegate", "trainee-delegate"),
+    ("candidate_delegate", "trainee-delegate"),
+    ("junior-delegate", "trainee-delegate"),
+    ("organiser", "organizer"),
+];
+
 impl Role {
     pub fn is_delegate(&self) -> bool {
+        matches!(self, Self::Delegate | Self::TraineeDelegate)
+    }
+
+    pub fn is_staff(&self) -> bool {
+        matches!(self, Self::Staff(_))
+    }
+
+    // Human readable name, e.g. "Trainee Delegate" or "Staff (judge)"
+    pub fn label(&self) -> String {
+        match self {
+            Self::Delegate => "Delegate".to_string(),
+            Self::TraineeDelegate => "Trainee Delegate".to_string(),
+            Self::Organizer => "Organizer".to_string(),
+            Self::Staff(None) => "Staff".to_string(),
+            Self::Staff(Some(staff)) => format!("Staff ({staff})"),
+            Self::Other(x) => x.clone(),
+        }
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Delegate | Self::TraineeDelegate => true,
-            _ => false
+            Self::Delegate => write!(f, "delegate"),
+            Self::TraineeDelegate => write!(f, "trainee-delegate"),
+            Self::Organizer => write!(f, "organizer"),
+            Self::Staff(None) => write!(f, "staff"),
+            Self::Staff(Some(staff)) => write!(f, "staff-{staff}"),
+            Self::Other(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = ROLE_ALIASES.iter().find(|(alias, _)| *alias == s).map_or(s, |(_, role)| role);
+        match s {
+            "delegate" => Ok(Self::Delegate),
+            "trainee-delegate" => Ok(Self::TraineeDelegate),
+            "organizer" => Ok(Self::Organizer),
+            "staff" => Ok(Self::Staff(None)),
+            x if x.starts_with("staff-") && x.len() > 6 => Ok(Self::Staff(Some(StaffAssignment::from_str(&x[6..])?))),
+            x => Ok(Self::Other(x.to_owned())),
         }
     }
 }
@@ -207,18 +374,23 @@ pub struct Registration {
     pub is_competing: bool,
 }
 
-#[derive(Clone, Debug, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum RegistrationStatus {
     Accepted,
+    // The WCA website reports registrations on the waiting list as pending
+    #[serde(alias = "waiting_list", alias = "waitingList")]
     Pending,
+    #[serde(alias = "cancelled", alias = "rejected")]
     Deleted
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RegistrationInfo {
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
     pub open_time: DateTime,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
     pub close_time: DateTime,
     pub base_entry_fee: u64,
     pub currency_code: CurrencyCode,
@@ -233,6 +405,79 @@ pub struct Avatar {
     pub thumb_url: String,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum AvatarSize {
+    Original,
+    Thumbnail,
+}
+
+impl Avatar {
+    // Creates an avatar from the URL of the full size image, deriving the thumbnail URL
+    pub fn from_url(url: String) -> Self {
+        let thumb_url = Self::derived_url(&url, AvatarSize::Thumbnail);
+        Avatar { url, thumb_url }
+    }
+
+    pub fn get_url(&self, size: AvatarSize) -> &str {
+        match size {
+            AvatarSize::Original => &self.url,
+            AvatarSize::Thumbnail => &self.thumb_url,
+        }
+    }
+
+    // The URL of a size variant following the WCA convention of suffixing the file name,
+    // e.g. ".../1630621356.jpeg" and ".../1630621356_thumb.jpeg"
+    pub fn derived_url(url: &str, size: AvatarSize) -> String {
+        let suffix = match size {
+            AvatarSize::Original => "",
+            AvatarSize::Thumbnail => "_thumb",
+        };
+        let (path, query) = match url.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (url, None),
+        };
+        let file_start = path.rfind('/').map(|i| i + 1).unwrap_or(0);
+        let (stem, extension) = match path[file_start..].rfind('.') {
+            Some(i) => path.split_at(file_start + i),
+            None => (path, ""),
+        };
+        let stem = stem.strip_suffix("_thumb").unwrap_or(stem);
+        match query {
+            Some(query) => format!("{stem}{suffix}{extension}?{query}"),
+            None => format!("{stem}{suffix}{extension}"),
+        }
+    }
+
+    // Whether both URLs are absolute http(s) URLs with a host
+    pub fn is_valid(&self) -> bool {
+        fn valid(url: &str) -> bool {
+            let rest = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"));
+            rest.is_some_and(|r| !r.is_empty() && !r.starts_with('/') && !r.contains(char::is_whitespace))
+        }
+        valid(&self.url) && valid(&self.thumb_url)
+    }
+
+    // The WCA sometimes emits avatars with a null url or without a thumbnail.
+    // Avatars without url are treated as missing, a missing thumbnail is derived from the url.
+    fn deserialize_lenient<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Option<Avatar>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RawAvatar {
+            url: Option<String>,
+            thumb_url: Option<String>,
+        }
+
+        let Some(raw) = Option::<RawAvatar>::deserialize(d)? else {
+            return Ok(None);
+        };
+        Ok(match (raw.url.filter(|u| !u.is_empty()), raw.thumb_url.filter(|u| !u.is_empty())) {
+            (Some(url), Some(thumb_url)) => Some(Avatar { url, thumb_url }),
+            (Some(url), None) => Some(Avatar::from_url(url)),
+            (None, _) => None,
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Assignment {
@@ -325,6 +570,7 @@ pub struct Event {
     pub rounds: Vec<Round>,
     pub competitor_limit: Option<u32>,
     pub qualification: Option<Qualification>,
+    #[serde(default)]
     pub extensions: Vec<Extension>,
 }
 
@@ -336,15 +582,17 @@ pub struct Round {
     pub time_limit: Option<TimeLimit>,
     pub cutoff: Option<Cutoff>,
     pub advancement_condition: Option<AdvancementCondition>,
+    #[serde(default)]
     pub results: Vec<RoundResult>,
     pub scramble_set_count: u32,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub scramble_sets: Vec<ScrambleSet>,
+    #[serde(default)]
     pub extensions: Vec<Extension>,
 }
 
-#[derive(Clone, Debug, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum RoundFormat {
     #[serde(rename = "1")]
@@ -395,7 +643,7 @@ pub struct Cutoff {
     pub attempt_result: AttemptResult,
 }
 
-#[derive(Clone, Debug, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(tag = "type")]
 pub enum AdvancementCondition {
@@ -438,6 +686,15 @@ pub struct RoundResult {
     pub average: AttemptResult,
 }
 
+impl RoundResult {
+    pub fn get_result(&self, result_type: &ResultType) -> &AttemptResult {
+        match result_type {
+            ResultType::Single => &self.best,
+            ResultType::Average => &self.average,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Attempt {
@@ -468,11 +725,12 @@ pub struct Schedule {
 pub struct Venue {
     pub id: VenueId,
     pub name: String,
-    pub latitude_microdegrees: u32,
-    pub longitude_microdegrees: u32,
+    pub latitude_microdegrees: i32,
+    pub longitude_microdegrees: i32,
     pub country_iso2: CountryCode,
     pub timezone: String,
     pub rooms: Vec<Room>,
+    #[serde(default)]
     pub extensions: Vec<Extension>
 }
 
@@ -483,10 +741,11 @@ pub struct Room {
     pub name: String,
     pub color: String,
     pub activities: Vec<Activity>,
+    #[serde(default)]
     pub extensions: Vec<Extension>
 }
 
-#[derive(Clone, Debug, PartialEq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ResultType {
     Single,
@@ -499,16 +758,129 @@ pub struct Activity {
     pub id: ActivityId,
     pub name: String,
     pub activity_code: ActivityCode,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
     pub start_time: DateTime,
+    #[serde(deserialize_with = "crate::datetime::deserialize")]
     pub end_time: DateTime,
+    #[serde(default)]
     pub child_activities: Vec<Activity>,
     pub scramble_set_id: Option<ScrambleSetId>,
+    #[serde(default)]
     pub extensions: Vec<Extension>
 }
 
 impl Activity {
     pub fn get_duration(&self) -> TimeDelta {
-        self.end_time.signed_duration_since(&self.start_time)
+        self.end_time.signed_duration_since(self.start_time)
+    }
+
+    // This activity followed by all of its descendants, depth first
+    pub fn all_activities(&self) -> Vec<&Activity> {
+        let mut activities = vec![self];
+        for child in &self.child_activities {
+            activities.extend(child.all_activities());
+        }
+        activities
+    }
+
+    // Calls f for this activity and all of its descendants, depth first
+    pub fn for_each_activity_mut<F: FnMut(&mut Activity)>(&mut self, f: &mut F) {
+        f(self);
+        for child in self.child_activities.iter_mut() {
+            child.for_each_activity_mut(f);
+        }
+    }
+}
+
+impl Venue {
+    #[cfg(feature = "timezones")]
+    pub fn get_timezone(&self) -> Option<chrono_tz::Tz> {
+        self.timezone.parse().ok()
+    }
+
+    // The date at the venue at the given time. Falls back to UTC if the timezone is unknown or the timezones
+    // feature is disabled
+    pub fn local_date(&self, time: &DateTime) -> Date {
+        self.local_time(time).date()
+    }
+
+    pub fn local_time(&self, time: &DateTime) -> chrono::NaiveDateTime {
+        #[cfg(feature = "timezones")]
+        if let Some(tz) = self.get_timezone() {
+            return time.with_timezone(&tz).naive_local();
+        }
+        time.naive_utc()
+    }
+
+    // The time of a local time at the venue, the earlier one if it is ambiguous. None if the local time is skipped by
+    // a daylight saving time change. Falls back to UTC like local_time
+    pub fn utc_time(&self, local: &chrono::NaiveDateTime) -> Option<DateTime> {
+        #[cfg(feature = "timezones")]
+        if let Some(tz) = self.get_timezone() {
+            return local.and_local_timezone(tz).earliest().map(|t| t.with_timezone(&chrono::Utc));
+        }
+        Some(local.and_utc())
+    }
+}
+
+impl Schedule {
+    // All activities of all rooms, including nested child activities
+    pub fn all_activities(&self) -> Vec<&Activity> {
+        self.venues.iter()
+            .flat_map(|v| v.rooms.iter())
+            .flat_map(|r| r.activities.iter())
+            .flat_map(|a| a.all_activities())
+            .collect()
+    }
+
+    pub fn find_activity(&self, id: ActivityId) -> Option<&Activity> {
+        self.all_activities().into_iter().find(|a| a.id == id)
+    }
+
+    // All activities including nested child activities, together with the venue and room they take place in
+    pub fn activities_with_rooms(&self) -> Vec<(&Venue, &Room, &Activity)> {
+        self.venues.iter()
+            .flat_map(|v| v.rooms.iter().map(move |r| (v, r)))
+            .flat_map(|(v, r)| r.activities.iter()
+                .flat_map(|a| a.all_activities())
+                .map(move |a| (v, r, a)))
+            .collect()
+    }
+
+    // Start of the earliest activity, or midnight of the first day at the first venue if there are no activities
+    pub fn start_datetime(&self) -> DateTime {
+        self.all_activities().iter()
+            .map(|a| a.start_time)
+            .min()
+            .unwrap_or_else(|| self.midnight_after_days(0))
+    }
+
+    // End of the latest activity, or midnight after the last day at the first venue if there are no activities
+    pub fn end_datetime(&self) -> DateTime {
+        self.all_activities().iter()
+            .map(|a| a.end_time)
+            .max()
+            .unwrap_or_else(|| self.midnight_after_days(self.number_of_days as u64))
+    }
+
+    fn midnight_after_days(&self, days: u64) -> DateTime {
+        let date = self.start_date + chrono::Days::new(days);
+        let midnight = date.and_time(chrono::NaiveTime::MIN);
+        #[cfg(feature = "timezones")]
+        if let Some(dt) = self.venues.first()
+            .and_then(|v| v.get_timezone())
+            .and_then(|tz| midnight.and_local_timezone(tz).earliest()) {
+            return dt.with_timezone(&chrono::Utc);
+        }
+        midnight.and_utc()
+    }
+
+    pub fn for_each_activity_mut<F: FnMut(&mut Activity)>(&mut self, mut f: F) {
+        for activity in self.venues.iter_mut()
+            .flat_map(|v| v.rooms.iter_mut())
+            .flat_map(|r| r.activities.iter_mut()) {
+            activity.for_each_activity_mut(&mut f);
+        }
     }
 }
 
@@ -528,6 +900,51 @@ pub enum Extension {
     #[cfg(feature = "delegate_dashboard")]
     #[serde(untagged)]
     DelegateDashboardGroups(crate::delegate_dashboard::GroupsExtension),
+    #[cfg(feature = "scramble_encryption")]
+    #[serde(untagged)]
+    EncryptedScrambleSets(crate::scramble_encryption::EncryptedScrambleSetsExtension),
+    #[cfg(feature = "check_in")]
+    #[serde(untagged)]
+    CheckIn(crate::check_in::CheckInExtension),
+    #[cfg(feature = "id_verification")]
+    #[serde(untagged)]
+    IdVerification(crate::id_verification::IdVerificationExtension),
+    #[cfg(feature = "payments")]
+    #[serde(untagged)]
+    Payments(crate::payments::PaymentsExtension),
+    #[cfg(feature = "result_verification")]
+    #[serde(untagged)]
+    ResultVerification(crate::verification::ResultVerificationExtension),
+    #[cfg(feature = "attempt_provenance")]
+    #[serde(untagged)]
+    AttemptProvenance(crate::provenance::AttemptProvenanceExtension),
+    #[cfg(feature = "extra_attempts")]
+    #[serde(untagged)]
+    ExtraAttempts(crate::extra_attempts::ExtraAttemptsExtension),
+    #[cfg(feature = "penalties")]
+    #[serde(untagged)]
+    Penalties(crate::penalties::PenaltiesExtension),
+    #[cfg(feature = "replication")]
+    #[serde(untagged)]
+    AttemptClocks(crate::replication::AttemptClocksExtension),
+    #[cfg(feature = "signing")]
+    #[serde(untagged)]
+    ResultsSignature(crate::signing::ResultsSignatureExtension),
+    #[cfg(feature = "waiting_list")]
+    #[serde(untagged)]
+    WaitingList(crate::waiting_list::WaitingListExtension),
+    #[cfg(feature = "incidents")]
+    #[serde(untagged)]
+    IncidentLog(crate::incidents::IncidentLogExtension),
+    #[cfg(feature = "unofficial_events")]
+    #[serde(untagged)]
+    UnofficialEvents(crate::unofficial::UnofficialEventsExtension),
+    #[cfg(feature = "unofficial_events")]
+    #[serde(untagged)]
+    UnofficialResults(crate::unofficial::UnofficialResultsExtension),
+    #[cfg(feature = "teams")]
+    #[serde(untagged)]
+    Teams(crate::teams::TeamsExtension),
     #[serde(untagged)]
     Unknown(UnknownExtension)
 }
@@ -542,12 +959,14 @@ pub struct UnknownExtension {
 
 #[cfg(feature = "parse_attempt_result")]
 mod attempt_result {
+    use std::cmp::Ordering;
+    use std::fmt::Formatter;
     use serde::{Serializer};
-    use serde::de::Error;
-    use serde_json::Value;
+    use serde::de::{Error, Visitor};
     use crate::types::AttemptResultValue;
 
-    #[derive(Copy, Clone, PartialEq, Debug, Hash)]
+    #[allow(clippy::upper_case_acronyms)]
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
     pub enum AttemptResult {
         Skipped,
         DNF,
@@ -555,18 +974,35 @@ mod attempt_result {
         Success(AttemptResultValue),
     }
 
-    impl<'de> serde::Deserialize<'de> for AttemptResult {
-        fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
-            let value = Value::deserialize(d)?;
+    struct AttemptResultVisitor;
+
+    impl Visitor<'_> for AttemptResultVisitor {
+        type Value = AttemptResult;
 
-            Ok(match value.as_i64().ok_or(Error::custom("Not a number"))? {
+        fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+            write!(f, "an attempt result")
+        }
+
+        fn visit_i64<E: Error>(self, v: i64) -> Result<Self::Value, E> {
+            Ok(match v {
                 -2 => AttemptResult::DNS,
                 -1 => AttemptResult::DNF,
                 0 => AttemptResult::Skipped,
-                x if x > 0 => AttemptResult::Success(x as u32),
+                x if x > 0 && x <= AttemptResultValue::MAX as i64 => AttemptResult::Success(x as u32),
                 _ => Err(Error::custom("not a valid result"))?,
             })
         }
+
+        fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+            self.visit_i64(i64::try_from(v).map_err(|_| Error::custom("not a valid result"))?)
+        }
+    }
+
+    // Deserialized with a visitor instead of through Value, this is one of the hottest paths when parsing documents with results
+    impl<'de> serde::Deserialize<'de> for AttemptResult {
+        fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            d.deserialize_i64(AttemptResultVisitor)
+        }
     }
 
     impl serde::Serialize for AttemptResult {
@@ -582,6 +1018,50 @@ mod attempt_result {
             }
         }
     }
+
+    impl AttemptResult {
+        // The integer representation used in WCIF documents
+        pub fn to_raw(self) -> i64 {
+            match self {
+                AttemptResult::Skipped => 0,
+                AttemptResult::DNF => -1,
+                AttemptResult::DNS => -2,
+                AttemptResult::Success(x) => x as i64,
+            }
+        }
+
+        pub fn is_success(&self) -> bool {
+            matches!(self, AttemptResult::Success(_))
+        }
+
+        pub fn value(&self) -> Option<AttemptResultValue> {
+            match self {
+                AttemptResult::Success(x) => Some(*x),
+                _ => None
+            }
+        }
+    }
+
+    // Orders results from best to worst, successful results always rank before unsuccessful ones
+    impl PartialOrd for AttemptResult {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for AttemptResult {
+        fn cmp(&self, other: &Self) -> Ordering {
+            fn rank(result: &AttemptResult) -> (u8, AttemptResultValue) {
+                match result {
+                    AttemptResult::Success(x) => (0, *x),
+                    AttemptResult::DNF => (1, 0),
+                    AttemptResult::DNS => (2, 0),
+                    AttemptResult::Skipped => (3, 0),
+                }
+            }
+            rank(self).cmp(&rank(other))
+        }
+    }
 }
 
 #[cfg(feature = "parse_activity_code")]
@@ -693,8 +1173,8 @@ mod activity_code {
         type Err = String;
 
         fn from_str(s: &str) -> Result<Self, Self::Err> {
-            if s.starts_with("other-") {
-                Ok(ActivityCode::Unofficial(UnofficialActivityCode::from_str(&s[6..])?))
+            if let Some(code) = s.strip_prefix("other-") {
+                Ok(ActivityCode::Unofficial(UnofficialActivityCode::from_str(code)?))
             } else {
                 Ok(ActivityCode::Official(EventActivityCode::<EventId>::from_str(s)?))
             }
@@ -736,7 +1216,7 @@ mod activity_code {
                 "awards" => Ok(UnofficialActivityCode::Awards),
                 "misc" => Ok(UnofficialActivityCode::Misc(None)),
                 x if x.starts_with("unofficial-") => Ok(UnofficialActivityCode::Event(EventActivityCode::from_str(&x[11..])?)),
-                x if x.starts_with("misc-") => Ok(UnofficialActivityCode::Misc(Some((&x[5..]).to_string()))),
+                x if x.starts_with("misc-") => Ok(UnofficialActivityCode::Misc(Some(x[5..].to_string()))),
                 #[allow(deprecated)]
                 x => Ok(UnofficialActivityCode::Other(x.to_string())),
             }
@@ -828,7 +1308,7 @@ mod activity_code {
         fn from(value: &RoundId<EventId>) -> Self {
             Self {
                 event: value.event.clone(),
-                round: Some(value.round.clone()),
+                round: Some(value.round),
                 group: None,
                 attempt: None,
             }
@@ -856,6 +1336,7 @@ mod activity_code {
 
 #[cfg(feature = "parse_puzzle_type")]
 mod puzzle_types {
+    use std::cmp::Ordering;
     use std::fmt::{Debug, Display, Formatter};
     use std::str::FromStr;
 
@@ -963,11 +1444,74 @@ mod puzzle_types {
     }
 
 
+    impl PartialOrd for OfficialEventId {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    // Events are ordered the way the WCA lists them
+    impl Ord for OfficialEventId {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.rank().cmp(&other.rank())
+        }
+    }
+
     impl OfficialEventId {
+        // The rank of the event in the WCA database, used to order events. Discontinued events come last
+        pub fn rank(&self) -> u32 {
+            match self {
+                Self::Cube333 => 10,
+                Self::Cube222 => 20,
+                Self::Cube444 => 30,
+                Self::Cube555 => 40,
+                Self::Cube666 => 50,
+                Self::Cube777 => 60,
+                Self::Blind333 => 70,
+                Self::FewestMoves333 => 80,
+                Self::OneHanded333 => 90,
+                Self::Clock => 110,
+                Self::Megaminx => 120,
+                Self::Pyraminx => 130,
+                Self::Skewb => 140,
+                Self::Square1 => 150,
+                Self::Blind444 => 160,
+                Self::Blind555 => 170,
+                Self::MultiBlind333 => 180,
+                Self::Feet333 => 996,
+                Self::Magic => 997,
+                Self::MasterMagic => 998,
+                Self::MultiBlindOldStyle333 => 999,
+            }
+        }
+
         pub fn is_blind(&self) -> bool {
+            matches!(self, Self::Blind333 | Self::Blind444 | Self::Blind555 | Self::MultiBlind333 | Self::MultiBlindOldStyle333)
+        }
+
+        pub fn get_name(&self) -> &'static str {
             match self {
-                Self::Blind333 | Self::Blind444 | Self::Blind555 | Self::MultiBlind333 | Self::MultiBlindOldStyle333 => true,
-                _ => false
+                Self::Cube333 => "3x3x3 Cube",
+                Self::Cube222 => "2x2x2 Cube",
+                Self::Cube444 => "4x4x4 Cube",
+                Self::Cube555 => "5x5x5 Cube",
+                Self::Cube666 => "6x6x6 Cube",
+                Self::Cube777 => "7x7x7 Cube",
+                Self::Blind333 => "3x3x3 Blindfolded",
+                Self::FewestMoves333 => "3x3x3 Fewest Moves",
+                Self::OneHanded333 => "3x3x3 One-Handed",
+                Self::Feet333 => "3x3x3 With Feet",
+                Self::Clock => "Clock",
+                Self::Megaminx => "Megaminx",
+                Self::Pyraminx => "Pyraminx",
+                Self::Skewb => "Skewb",
+                Self::Square1 => "Square-1",
+                Self::Blind444 => "4x4x4 Blindfolded",
+                Self::Blind555 => "5x5x5 Blindfolded",
+                Self::MultiBlind333 => "3x3x3 Multi-Blind",
+                Self::Magic => "Magic",
+                Self::MasterMagic => "Master Magic",
+                Self::MultiBlindOldStyle333 => "3x3x3 Multi-Blind Old Style",
             }
         }
 
@@ -989,4 +1533,67 @@ mod puzzle_types {
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    fn role(s: &str) -> Role {
+        serde_json::from_value(json!(s)).unwrap()
+    }
+
+    #[test]
+    fn roles_round_trip() {
+        let roles = [
+            ("delegate", Role::Delegate),
+            ("trainee-delegate", Role::TraineeDelegate),
+            ("organizer", Role::Organizer),
+            ("staff", Role::Staff(None)),
+            ("staff-judge", Role::Staff(Some(StaffAssignment::Judge))),
+            ("staff-scrambler", Role::Staff(Some(StaffAssignment::Scrambler))),
+            ("staff-runner", Role::Staff(Some(StaffAssignment::Runner))),
+            ("staff-dataentry", Role::Staff(Some(StaffAssignment::DataEntry))),
+            ("staff-announcer", Role::Staff(Some(StaffAssignment::Announcer))),
+            ("staff-photographer", Role::Staff(Some(StaffAssignment::Other("photographer".to_string())))),
+            ("sponsor", Role::Other("sponsor".to_string())),
+        ];
+        for (s, expected) in roles {
+            assert_eq!(role(s), expected, "{s}");
+            assert_eq!(serde_json::to_value(&expected).unwrap(), json!(s));
+        }
+    }
+
+    #[test]
+    fn role_aliases_serialize_canonically() {
+        for (alias, canonical) in ROLE_ALIASES {
+            let parsed = role(alias);
+            assert!(!matches!(parsed, Role::Other(_)), "{alias}");
+            assert_eq!(parsed, role(canonical), "{alias}");
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), json!(canonical));
+        }
+    }
+
+    #[test]
+    fn registration_statuses_round_trip() {
+        let statuses = [
+            ("accepted", RegistrationStatus::Accepted),
+            ("pending", RegistrationStatus::Pending),
+            ("deleted", RegistrationStatus::Deleted),
+        ];
+        for (s, expected) in statuses {
+            assert_eq!(serde_json::from_value::<RegistrationStatus>(json!(s)).unwrap(), expected, "{s}");
+            assert_eq!(serde_json::to_value(&expected).unwrap(), json!(s));
+        }
+        let aliases = [
+            ("waiting_list", RegistrationStatus::Pending),
+            ("waitingList", RegistrationStatus::Pending),
+            ("cancelled", RegistrationStatus::Deleted),
+            ("rejected", RegistrationStatus::Deleted),
+        ];
+        for (alias, expected) in aliases {
+            assert_eq!(serde_json::from_value::<RegistrationStatus>(json!(alias)).unwrap(), expected, "{alias}");
+        }
+        assert!(serde_json::from_value::<RegistrationStatus>(json!("Accepted")).is_err());
+    }
+}
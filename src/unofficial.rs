@@ -0,0 +1,179 @@
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::results::{compute_best, compute_average, rank_results};
+use crate::types::{ActivityCode, AdvancementCondition, Attempt, AttemptResult, Competition, Cutoff, EventId, Extension, Person, PersonId, RoundFormat, RoundIdType, RoundResult, TimeLimit, UnofficialActivityCode, UnofficialEventActivityCode};
+
+pub const EVENTS_EXTENSION_ID: &str = crate::extensions::UNOFFICIAL_EVENTS_ID;
+pub const EVENTS_SPEC_URL: &str = crate::extensions::UNOFFICIAL_EVENTS_SPEC_URL;
+pub const RESULTS_EXTENSION_ID: &str = crate::extensions::UNOFFICIAL_RESULTS_ID;
+pub const RESULTS_SPEC_URL: &str = crate::extensions::UNOFFICIAL_RESULTS_SPEC_URL;
+
+// Side events (e.g. Anything Goes Events) are not part of the events of a competition, so their rounds and results
+// are stored on the competition. Their activities use codes like other-unofficial-333mts-r1
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnofficialEventsExtension {
+    pub id: MustBe!("wcif-rs.UnofficialEvents"),
+    pub spec_url: String,
+    pub data: UnofficialEvents,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnofficialEvents {
+    pub events: Vec<UnofficialEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnofficialEvent {
+    pub id: String,
+    pub name: String,
+    pub rounds: Vec<UnofficialRound>,
+}
+
+// Same as an official round, cumulative time limits can only refer to official rounds
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnofficialRound {
+    pub format: RoundFormat,
+    pub time_limit: Option<TimeLimit>,
+    pub cutoff: Option<Cutoff>,
+    pub advancement_condition: Option<AdvancementCondition>,
+    #[serde(default)]
+    pub results: Vec<RoundResult>,
+}
+
+// Some tools store unofficial results on each person instead
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnofficialResultsExtension {
+    pub id: MustBe!("wcif-rs.UnofficialResults"),
+    pub spec_url: String,
+    pub data: UnofficialResults,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnofficialResults {
+    pub results: Vec<UnofficialResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnofficialResult {
+    pub event_id: String,
+    pub round: RoundIdType,
+    pub attempts: Vec<Attempt>,
+}
+
+impl UnofficialEvent {
+    pub fn activity_code(&self, round: RoundIdType) -> ActivityCode {
+        ActivityCode::Unofficial(UnofficialActivityCode::Event(UnofficialEventActivityCode {
+            event: self.id.clone(),
+            round: Some(round),
+            group: None,
+            attempt: None,
+        }))
+    }
+}
+
+impl UnofficialRound {
+    // Recomputes best, average and rankings of all results. Unofficial events are timed, so averages are
+    // computed like those of 3x3x3 Cube
+    pub fn update_results(&mut self) {
+        for result in self.results.iter_mut() {
+            result.best = compute_best(&result.attempts);
+            result.average = compute_average(&EventId::Cube333, &self.format, &result.attempts);
+        }
+        rank_results(&self.format, &mut self.results);
+    }
+}
+
+impl Person {
+    pub fn unofficial_results(&self) -> Option<&UnofficialResults> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::UnofficialResults(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+}
+
+impl Competition {
+    pub fn unofficial_events(&self) -> Option<&UnofficialEvents> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::UnofficialEvents(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    pub fn unofficial_events_mut(&mut self) -> &mut UnofficialEvents {
+        if self.unofficial_events().is_none() {
+            self.extensions.push(Extension::UnofficialEvents(UnofficialEventsExtension {
+                id: Default::default(),
+                spec_url: EVENTS_SPEC_URL.to_string(),
+                data: UnofficialEvents::default(),
+            }));
+        }
+        self.extensions.iter_mut().find_map(|e| match e {
+            Extension::UnofficialEvents(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("unofficial events extension was just added")
+    }
+
+    pub fn find_unofficial_round(&self, event_id: &str, round: RoundIdType) -> Option<&UnofficialRound> {
+        self.unofficial_events()?.events.iter()
+            .find(|e| e.id == event_id)?
+            .rounds.get(round.checked_sub(1)? as usize)
+    }
+
+    // Moves the results stored on persons into the rounds of the competition and ranks all of them. Results of
+    // persons who already have one in the round replace it. Results of rounds that don't exist stay on the person
+    // and are returned
+    pub fn collect_unofficial_results(&mut self) -> Vec<(PersonId, UnofficialResult)> {
+        let rounds: Vec<(String, RoundIdType)> = self.unofficial_events().map(|u| u.events.iter()
+                .flat_map(|e| (1..=e.rounds.len() as RoundIdType).map(|round| (e.id.clone(), round)))
+                .collect())
+            .unwrap_or_default();
+        let mut collected = vec![];
+        let mut unknown = vec![];
+        for person in self.persons.iter_mut() {
+            let Some(person_id) = person.registrant_id else {
+                continue;
+            };
+            for extension in person.extensions.iter_mut() {
+                let Extension::UnofficialResults(x) = extension else {
+                    continue;
+                };
+                let (known, rest): (Vec<_>, Vec<_>) = x.data.results.drain(..)
+                    .partition(|r| rounds.iter().any(|(event_id, round)| *event_id == r.event_id && *round == r.round));
+                collected.extend(known.into_iter().map(|r| (person_id, r)));
+                unknown.extend(rest.iter().cloned().map(|r| (person_id, r)));
+                x.data.results = rest;
+            }
+            person.extensions.retain(|e| !matches!(e, Extension::UnofficialResults(x) if x.data.results.is_empty()));
+        }
+        if rounds.is_empty() {
+            return unknown;
+        }
+        let events = self.unofficial_events_mut();
+        for (person_id, result) in collected {
+            let round = events.events.iter_mut()
+                .find(|e| e.id == result.event_id)
+                .and_then(|e| e.rounds.get_mut(result.round as usize - 1))
+                .expect("round was checked to exist");
+            round.results.retain(|r| r.person_id != person_id);
+            round.results.push(RoundResult {
+                person_id,
+                ranking: None,
+                attempts: result.attempts,
+                best: AttemptResult::Skipped,
+                average: AttemptResult::Skipped,
+            });
+        }
+        for round in events.events.iter_mut().flat_map(|e| e.rounds.iter_mut()) {
+            round.update_results();
+        }
+        unknown
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::fmt::{Display, Formatter};
+use crate::scrambles::scramble_key;
+use chrono::TimeDelta;
+use serde::Serialize;
+use crate::types::{Activity, ActivityId, AdvancementCondition, Competition, Event, EventId, Person, PersonId, RoomId, Round, RoundId, RoundIdType, ScrambleSetId, Venue, WCAId, WCAUserId};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ValidationError {
+    RoundNumbering { event_id: EventId, expected: RoundIdType, found: RoundId },
+    MissingAdvancementCondition { round_id: RoundId },
+    IllegalAdvancement { round_id: RoundId, person_id: PersonId },
+    TooManyReplacements { round_id: RoundId, allowed: usize, found: usize },
+    SharedScrambleSet { scramble_set_id: ScrambleSetId, first: ActivityId, second: ActivityId },
+    MisalignedScrambleSet { scramble_set_id: ScrambleSetId, first: ActivityId, second: ActivityId },
+    DuplicateStation { activity_id: ActivityId, station_number: u32, person_ids: Vec<PersonId> },
+    UnknownCumulativeRound { round_id: RoundId, referenced: RoundId },
+    AsymmetricCumulativeLimit { round_id: RoundId, referenced: RoundId },
+    IncompatibleCumulativeLimit { round_id: RoundId, referenced: RoundId },
+    InsufficientTravelTime { person_id: PersonId, first: ActivityId, second: ActivityId, available_minutes: i64, required_minutes: i64 },
+    DuplicateRegistrantId { person_id: PersonId, names: Vec<String> },
+    DuplicateWcaUserId { wca_user_id: WCAUserId, names: Vec<String> },
+    DuplicateWcaId { wca_id: WCAId, names: Vec<String> },
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RoundNumbering { event_id, expected, found } => write!(f, "Event {event_id} expected round {expected} but found {found}"),
+            Self::MissingAdvancementCondition { round_id } => write!(f, "Round {round_id} is followed by another round but has no advancement condition"),
+            Self::IllegalAdvancement { round_id, person_id } => write!(f, "Person {person_id} did not qualify for round {round_id}"),
+            Self::TooManyReplacements { round_id, allowed, found } => write!(f, "Round {round_id} has {found} replacement competitors but only {allowed} are allowed"),
+            Self::SharedScrambleSet { scramble_set_id, first, second } => write!(f, "Activities {first} and {second} run at the same time in different rooms but share scramble set {scramble_set_id}"),
+            Self::MisalignedScrambleSet { scramble_set_id, first, second } => write!(f, "Activities {first} and {second} share scramble set {scramble_set_id} in different rooms but at different times"),
+            Self::DuplicateStation { activity_id, station_number, person_ids } => write!(f, "Station {station_number} of activity {activity_id} is assigned to multiple persons {person_ids:?}"),
+            Self::UnknownCumulativeRound { round_id, referenced } => write!(f, "Cumulative time limit of round {round_id} references round {referenced} which does not exist"),
+            Self::AsymmetricCumulativeLimit { round_id, referenced } => write!(f, "Cumulative time limit of round {round_id} includes round {referenced}, but {referenced} does not declare the same rounds"),
+            Self::IncompatibleCumulativeLimit { round_id, referenced } => write!(f, "Rounds {round_id} and {referenced} share a cumulative time limit but differ in format or limit"),
+            Self::InsufficientTravelTime { person_id, first, second, available_minutes, required_minutes } => write!(f, "Person {person_id} has {available_minutes} minutes to get from activity {first} to activity {second} at another venue but needs {required_minutes}"),
+            Self::DuplicateRegistrantId { person_id, names } => write!(f, "Registrant id {person_id} is used by multiple persons {names:?}"),
+            Self::DuplicateWcaUserId { wca_user_id, names } => write!(f, "WCA user {wca_user_id} is registered as multiple persons {names:?}"),
+            Self::DuplicateWcaId { wca_id, names } => write!(f, "WCA id {wca_id} is shared by multiple persons {names:?}"),
+        }
+    }
+}
+
+// Estimated time to get from one venue to another: a fixed part for leaving and arriving plus a part per kilometer
+// of straight-line distance between the venues
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TravelTime {
+    pub base: TimeDelta,
+    pub per_km: TimeDelta,
+}
+
+impl Default for TravelTime {
+    // Walking or public transport within a city
+    fn default() -> Self {
+        Self { base: TimeDelta::minutes(10), per_km: TimeDelta::minutes(4) }
+    }
+}
+
+impl TravelTime {
+    pub fn required(&self, from: &Venue, to: &Venue) -> TimeDelta {
+        let seconds = self.base.num_seconds() as f64 + self.per_km.num_seconds() as f64 * from.distance_km(to);
+        TimeDelta::seconds(seconds.ceil() as i64)
+    }
+}
+
+// Checks run by `validate` after the round progression, shared with `validate_parallel`
+pub(crate) const DOCUMENT_CHECKS: [fn(&Competition) -> Vec<ValidationError>; 4] = [
+    Competition::validate_scramble_set_usage,
+    Competition::validate_cumulative_time_limits,
+    validate_stations,
+    Competition::validate_unique_persons,
+];
+
+fn validate_stations(competition: &Competition) -> Vec<ValidationError> {
+    competition.duplicate_stations().into_iter()
+        .map(|(activity_id, station_number, person_ids)| ValidationError::DuplicateStation { activity_id, station_number, person_ids })
+        .collect()
+}
+
+impl Competition {
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = self.validate_round_progression();
+        errors.extend(DOCUMENT_CHECKS.iter().flat_map(|check| check(self)));
+        errors
+    }
+
+    // Registrant ids, WCA accounts and WCA ids must each belong to a single person. Registrations imported twice
+    // lead to duplicates, see `Competition::merge_duplicate_persons`
+    pub fn validate_unique_persons(&self) -> Vec<ValidationError> {
+        let mut errors: Vec<ValidationError> = duplicate_persons(&self.persons, |p| p.registrant_id).into_iter()
+            .map(|(person_id, names)| ValidationError::DuplicateRegistrantId { person_id, names })
+            .collect();
+        errors.extend(duplicate_persons(&self.persons, |p| Some(p.wca_user_id)).into_iter()
+            .map(|(wca_user_id, names)| ValidationError::DuplicateWcaUserId { wca_user_id, names }));
+        errors.extend(duplicate_persons(&self.persons, |p| p.wca_id.clone()).into_iter()
+            .map(|(wca_id, names)| ValidationError::DuplicateWcaId { wca_id, names }));
+        errors
+    }
+
+    // Consecutive assignments of a person at different venues must leave enough time to travel between them. Not part
+    // of `validate`, since the time needed depends on the venues and the way competitors get around
+    pub fn validate_travel_times(&self, travel_time: &TravelTime) -> Vec<ValidationError> {
+        if self.schedule.venues.len() < 2 {
+            return vec![];
+        }
+        self.persons.iter()
+            .filter_map(|p| p.registrant_id)
+            .flat_map(|person_id| {
+                let assignments = self.assignments_for(person_id);
+                assignments.windows(2)
+                    .filter(|pair| pair[0].venue.id != pair[1].venue.id)
+                    .filter_map(|pair| {
+                        let available = pair[1].activity.start_time - pair[0].activity.end_time;
+                        let required = travel_time.required(pair[0].venue, pair[1].venue);
+                        (available < required).then(|| ValidationError::InsufficientTravelTime {
+                            person_id,
+                            first: pair[0].activity.id,
+                            second: pair[1].activity.id,
+                            available_minutes: available.num_minutes(),
+                            required_minutes: required.num_minutes(),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    pub fn validate_round_progression(&self) -> Vec<ValidationError> {
+        self.events.iter()
+            .flat_map(validate_event_progression)
+            .collect()
+    }
+
+    // Simultaneous activities in different rooms may only share a scramble set if they are the same group,
+    // e.g. group 1 spread across two stages. Those must then start and end at the same time on every stage
+    pub fn validate_scramble_set_usage(&self) -> Vec<ValidationError> {
+        let mut by_scramble_set: HashMap<ScrambleSetId, Vec<(RoomId, &Activity)>> = HashMap::new();
+        for room in self.schedule.venues.iter().flat_map(|v| v.rooms.iter()) {
+            for activity in room.activities.iter().flat_map(|a| a.all_activities()) {
+                if let Some(id) = activity.scramble_set_id {
+                    by_scramble_set.entry(id).or_default().push((room.id, activity));
+                }
+            }
+        }
+        let mut errors = vec![];
+        let mut scramble_set_ids: Vec<_> = by_scramble_set.keys().copied().collect();
+        scramble_set_ids.sort();
+        for scramble_set_id in scramble_set_ids {
+            let activities = &by_scramble_set[&scramble_set_id];
+            for (i, (first_room, first)) in activities.iter().enumerate() {
+                for (second_room, second) in activities.iter().skip(i + 1) {
+                    let simultaneous = first.start_time < second.end_time && second.start_time < first.end_time;
+                    if first_room == second_room {
+                        continue;
+                    }
+                    if simultaneous && scramble_key(first) != scramble_key(second) {
+                        errors.push(ValidationError::SharedScrambleSet {
+                            scramble_set_id,
+                            first: first.id,
+                            second: second.id,
+                        });
+                    } else if (first.start_time, first.end_time) != (second.start_time, second.end_time) {
+                        errors.push(ValidationError::MisalignedScrambleSet {
+                            scramble_set_id,
+                            first: first.id,
+                            second: second.id,
+                        });
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    // Rounds sharing a cumulative time limit must all declare the same set of rounds (including themselves),
+    // the same limit and the same format
+    pub fn validate_cumulative_time_limits(&self) -> Vec<ValidationError> {
+        let mut errors = vec![];
+        for round in self.events.iter().flat_map(|e| e.rounds.iter()) {
+            let Some(time_limit) = &round.time_limit else {
+                continue;
+            };
+            if time_limit.cumulative_round_ids.is_empty() {
+                continue;
+            }
+            let declared: HashSet<&RoundId> = time_limit.cumulative_round_ids.iter().collect();
+            if !declared.contains(&round.id) {
+                errors.push(ValidationError::AsymmetricCumulativeLimit { round_id: round.id.clone(), referenced: round.id.clone() });
+            }
+            for referenced_id in time_limit.cumulative_round_ids.iter().filter(|id| **id != round.id) {
+                let Some(referenced) = self.find_round(referenced_id) else {
+                    errors.push(ValidationError::UnknownCumulativeRound { round_id: round.id.clone(), referenced: referenced_id.clone() });
+                    continue;
+                };
+                let Some(referenced_limit) = &referenced.time_limit else {
+                    errors.push(ValidationError::AsymmetricCumulativeLimit { round_id: round.id.clone(), referenced: referenced_id.clone() });
+                    continue;
+                };
+                let referenced_declared: HashSet<&RoundId> = referenced_limit.cumulative_round_ids.iter().collect();
+                if declared != referenced_declared {
+                    errors.push(ValidationError::AsymmetricCumulativeLimit { round_id: round.id.clone(), referenced: referenced_id.clone() });
+                } else if referenced_limit.centiseconds != time_limit.centiseconds || referenced.format != round.format {
+                    errors.push(ValidationError::IncompatibleCumulativeLimit { round_id: round.id.clone(), referenced: referenced_id.clone() });
+                }
+            }
+        }
+        errors
+    }
+}
+
+impl Round {
+    // Maximum number of competitors allowed to proceed to the next round, see regulation 9p1
+    pub fn max_advancing(&self) -> usize {
+        self.results.len() * 3 / 4
+    }
+
+    // Persons who satisfy the advancement condition of this round, based on the rankings in the results.
+    // Returns None if the round has no advancement condition (i.e. it is a final)
+    pub fn advancing_persons(&self) -> Option<HashSet<PersonId>> {
+        let condition = self.advancement_condition.as_ref()?;
+        let result_type = self.format.sort_by();
+        let competitor_count = self.results.len();
+        let max_advancing = self.max_advancing();
+        let advancing = self.results.iter()
+            .filter(|r| r.best.is_success())
+            .filter(|r| {
+                let Some(ranking) = r.ranking else {
+                    return false;
+                };
+                let within_limit = self.results.iter()
+                    .filter(|o| o.ranking.is_some_and(|x| x <= ranking))
+                    .count() <= max_advancing;
+                within_limit && match condition {
+                    AdvancementCondition::Ranking { level } => ranking <= *level,
+                    AdvancementCondition::Percent { level } => ranking <= (competitor_count * *level as usize / 100) as u64,
+                    AdvancementCondition::AttemptResult { level } => r.get_result(&result_type) < level,
+                }
+            })
+            .map(|r| r.person_id)
+            .collect();
+        Some(advancing)
+    }
+}
+
+// Keys shared by multiple persons with the names of the persons, in the order the keys first appear
+fn duplicate_persons<K: Eq + Hash>(persons: &[Person], key: impl Fn(&Person) -> Option<K>) -> Vec<(K, Vec<String>)> {
+    let mut groups: HashMap<K, (usize, Vec<String>)> = HashMap::new();
+    for (i, person) in persons.iter().enumerate() {
+        if let Some(key) = key(person) {
+            groups.entry(key).or_insert_with(|| (i, vec![])).1.push(person.name.clone());
+        }
+    }
+    let mut duplicates: Vec<_> = groups.into_iter().filter(|(_, (_, names))| names.len() > 1).collect();
+    duplicates.sort_by_key(|(_, (first, _))| *first);
+    duplicates.into_iter().map(|(key, (_, names))| (key, names)).collect()
+}
+
+pub(crate) fn validate_event_progression(event: &Event) -> Vec<ValidationError> {
+    let mut errors = vec![];
+    for (i, round) in event.rounds.iter().enumerate() {
+        let expected = i as RoundIdType + 1;
+        if round.id.event != event.id || round.id.round != expected {
+            errors.push(ValidationError::RoundNumbering {
+                event_id: event.id.clone(),
+                expected,
+                found: round.id.clone(),
+            });
+        }
+    }
+    for (previous, next) in event.rounds.iter().zip(event.rounds.iter().skip(1)) {
+        if next.results.is_empty() {
+            continue;
+        }
+        let Some(advancing) = previous.advancing_persons() else {
+            errors.push(ValidationError::MissingAdvancementCondition { round_id: previous.id.clone() });
+            continue;
+        };
+        // Competitors who did not qualify may only replace qualified competitors who did not proceed,
+        // and only if they had a successful result in the previous round
+        let eligible_replacements: HashSet<PersonId> = previous.results.iter()
+            .filter(|r| r.best.is_success())
+            .map(|r| r.person_id)
+            .collect();
+        let proceeding: HashSet<PersonId> = next.results.iter()
+            .map(|r| r.person_id)
+            .collect();
+        let mut replacements = 0;
+        for person_id in next.results.iter().map(|r| r.person_id).filter(|p| !advancing.contains(p)) {
+            if eligible_replacements.contains(&person_id) {
+                replacements += 1;
+            } else {
+                errors.push(ValidationError::IllegalAdvancement {
+                    round_id: next.id.clone(),
+                    person_id,
+                });
+            }
+        }
+        let allowed = advancing.difference(&proceeding).count();
+        if replacements > allowed {
+            errors.push(ValidationError::TooManyReplacements {
+                round_id: next.id.clone(),
+                allowed,
+                found: replacements,
+            });
+        }
+    }
+    errors
+}
@@ -0,0 +1,117 @@
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::types::{AttemptResult, Competition, Extension, PersonId, Round, RoundId};
+
+pub const EXTENSION_ID: &str = crate::extensions::RESULT_VERIFICATION_ID;
+pub const SPEC_URL: &str = crate::extensions::RESULT_VERIFICATION_SPEC_URL;
+
+// Second, independent entry of the attempts of a round for double-entry scoretaking
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultVerificationExtension {
+    pub id: MustBe!("wcif-rs.ResultVerification"),
+    pub spec_url: String,
+    pub data: ResultVerification,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultVerification {
+    pub entries: Vec<VerificationEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationEntry {
+    pub person_id: PersonId,
+    pub attempts: Vec<AttemptResult>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub round_id: RoundId,
+    pub person_id: PersonId,
+    // Zero based
+    pub attempt_index: usize,
+    pub entered: Option<AttemptResult>,
+    pub verified: Option<AttemptResult>,
+}
+
+impl Round {
+    pub fn result_verification(&self) -> Option<&ResultVerification> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::ResultVerification(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    // Stores the second entry of a person's attempts, replacing a previous one
+    pub fn enter_verification(&mut self, person_id: PersonId, attempts: Vec<AttemptResult>) {
+        if self.result_verification().is_none() {
+            self.extensions.push(Extension::ResultVerification(ResultVerificationExtension {
+                id: Default::default(),
+                spec_url: SPEC_URL.to_string(),
+                data: ResultVerification::default(),
+            }));
+        }
+        let verification = self.extensions.iter_mut().find_map(|e| match e {
+            Extension::ResultVerification(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("verification extension was just added");
+        verification.entries.retain(|e| e.person_id != person_id);
+        verification.entries.push(VerificationEntry { person_id, attempts });
+    }
+
+    fn verification_entry(&self, person_id: PersonId) -> Option<&VerificationEntry> {
+        self.result_verification()?.entries.iter().find(|e| e.person_id == person_id)
+    }
+
+    // Persons whose entered attempts have not been entered a second time yet
+    pub fn unverified_results(&self) -> Vec<PersonId> {
+        self.results.iter()
+            .filter(|r| !r.attempts.is_empty())
+            .filter(|r| self.verification_entry(r.person_id).is_none_or(|e| e.attempts.len() < r.attempts.len()))
+            .map(|r| r.person_id)
+            .collect()
+    }
+
+    // Attempts where both entries exist and differ, or the second entry has an attempt that was not entered
+    pub fn mismatches(&self) -> Vec<Mismatch> {
+        let mut mismatches = vec![];
+        for entry in self.result_verification().iter().flat_map(|v| v.entries.iter()) {
+            let entered: Vec<AttemptResult> = self.results.iter()
+                .find(|r| r.person_id == entry.person_id)
+                .map(|r| r.attempts.iter().map(|a| a.result).collect())
+                .unwrap_or_default();
+            for (attempt_index, verified) in entry.attempts.iter().enumerate() {
+                let entered = entered.get(attempt_index).copied();
+                if entered != Some(*verified) {
+                    mismatches.push(Mismatch {
+                        round_id: self.id.clone(),
+                        person_id: entry.person_id,
+                        attempt_index,
+                        entered,
+                        verified: Some(*verified),
+                    });
+                }
+            }
+        }
+        mismatches
+    }
+}
+
+impl Competition {
+    pub fn unverified_results(&self) -> Vec<(RoundId, PersonId)> {
+        self.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .flat_map(|r| r.unverified_results().into_iter().map(|p| (r.id.clone(), p)))
+            .collect()
+    }
+
+    pub fn mismatches(&self) -> Vec<Mismatch> {
+        self.events.iter()
+            .flat_map(|e| e.rounds.iter())
+            .flat_map(|r| r.mismatches())
+            .collect()
+    }
+}
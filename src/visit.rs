@@ -0,0 +1,131 @@
+use crate::types::{Activity, Assignment, Competition, Event, Extension, Person, Room, Round, RoundId, RoundResult, Venue};
+
+// Called for every part of a competition by `Competition::visit`, parents before their children. All methods do
+// nothing by default, so visitors only implement what they are interested in. References live as long as the
+// competition, so they can be collected
+pub trait WcifVisitor<'a> {
+    fn visit_competition(&mut self, _competition: &'a Competition) {}
+    fn visit_person(&mut self, _person: &'a Person) {}
+    fn visit_assignment(&mut self, _person: &'a Person, _assignment: &'a Assignment) {}
+    fn visit_event(&mut self, _event: &'a Event) {}
+    fn visit_round(&mut self, _event: &'a Event, _round: &'a Round) {}
+    fn visit_result(&mut self, _round: &'a Round, _result: &'a RoundResult) {}
+    fn visit_venue(&mut self, _venue: &'a Venue) {}
+    fn visit_room(&mut self, _venue: &'a Venue, _room: &'a Room) {}
+    // Called for nested child activities too, after their parent
+    fn visit_activity(&mut self, _room: &'a Room, _activity: &'a Activity) {}
+    // Extensions of all of the above
+    fn visit_extension(&mut self, _extension: &'a Extension) {}
+}
+
+// Like `WcifVisitor`, for rewriting. Parents can't be borrowed along with their children, so only ids are passed
+// for context
+pub trait WcifVisitorMut {
+    fn visit_competition(&mut self, _competition: &mut Competition) {}
+    fn visit_person(&mut self, _person: &mut Person) {}
+    fn visit_event(&mut self, _event: &mut Event) {}
+    fn visit_round(&mut self, _round: &mut Round) {}
+    fn visit_result(&mut self, _round_id: &RoundId, _result: &mut RoundResult) {}
+    fn visit_venue(&mut self, _venue: &mut Venue) {}
+    fn visit_room(&mut self, _room: &mut Room) {}
+    fn visit_activity(&mut self, _activity: &mut Activity) {}
+    fn visit_extension(&mut self, _extension: &mut Extension) {}
+}
+
+fn visit_extensions<'a, V: WcifVisitor<'a>>(extensions: &'a [Extension], visitor: &mut V) {
+    for extension in extensions {
+        visitor.visit_extension(extension);
+    }
+}
+
+fn visit_activity<'a, V: WcifVisitor<'a>>(room: &'a Room, activity: &'a Activity, visitor: &mut V) {
+    visitor.visit_activity(room, activity);
+    visit_extensions(&activity.extensions, visitor);
+    for child in &activity.child_activities {
+        visit_activity(room, child, visitor);
+    }
+}
+
+fn visit_activity_mut<V: WcifVisitorMut>(activity: &mut Activity, visitor: &mut V) {
+    visitor.visit_activity(activity);
+    activity.extensions.iter_mut().for_each(|e| visitor.visit_extension(e));
+    for child in activity.child_activities.iter_mut() {
+        visit_activity_mut(child, visitor);
+    }
+}
+
+impl Competition {
+    // Walks the whole document: the competition, persons and their assignments, events, rounds and their results,
+    // then venues, rooms and activities
+    pub fn visit<'a, V: WcifVisitor<'a>>(&'a self, visitor: &mut V) {
+        visitor.visit_competition(self);
+        visit_extensions(&self.extensions, visitor);
+        for person in &self.persons {
+            visitor.visit_person(person);
+            for assignment in &person.assignments {
+                visitor.visit_assignment(person, assignment);
+            }
+            visit_extensions(&person.extensions, visitor);
+        }
+        for event in &self.events {
+            visitor.visit_event(event);
+            visit_extensions(&event.extensions, visitor);
+            for round in &event.rounds {
+                visitor.visit_round(event, round);
+                for result in &round.results {
+                    visitor.visit_result(round, result);
+                }
+                visit_extensions(&round.extensions, visitor);
+            }
+        }
+        for venue in &self.schedule.venues {
+            visitor.visit_venue(venue);
+            visit_extensions(&venue.extensions, visitor);
+            for room in &venue.rooms {
+                visitor.visit_room(venue, room);
+                visit_extensions(&room.extensions, visitor);
+                for activity in &room.activities {
+                    visit_activity(room, activity, visitor);
+                }
+            }
+        }
+    }
+
+    // Visits the competition and returns the visitor, e.g. `competition.fold(Counter::default()).count`
+    pub fn fold<'a, V: WcifVisitor<'a>>(&'a self, mut visitor: V) -> V {
+        self.visit(&mut visitor);
+        visitor
+    }
+
+    // Same order as `visit`
+    pub fn visit_mut<V: WcifVisitorMut>(&mut self, visitor: &mut V) {
+        visitor.visit_competition(self);
+        self.extensions.iter_mut().for_each(|e| visitor.visit_extension(e));
+        for person in self.persons.iter_mut() {
+            visitor.visit_person(person);
+            person.extensions.iter_mut().for_each(|e| visitor.visit_extension(e));
+        }
+        for event in self.events.iter_mut() {
+            visitor.visit_event(event);
+            event.extensions.iter_mut().for_each(|e| visitor.visit_extension(e));
+            for round in event.rounds.iter_mut() {
+                visitor.visit_round(round);
+                for result in round.results.iter_mut() {
+                    visitor.visit_result(&round.id, result);
+                }
+                round.extensions.iter_mut().for_each(|e| visitor.visit_extension(e));
+            }
+        }
+        for venue in self.schedule.venues.iter_mut() {
+            visitor.visit_venue(venue);
+            venue.extensions.iter_mut().for_each(|e| visitor.visit_extension(e));
+            for room in venue.rooms.iter_mut() {
+                visitor.visit_room(room);
+                room.extensions.iter_mut().for_each(|e| visitor.visit_extension(e));
+                for activity in room.activities.iter_mut() {
+                    visit_activity_mut(activity, visitor);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt::{Display, Formatter};
+use monostate::MustBe;
+use serde::{Deserialize, Serialize};
+use crate::types::{Competition, Extension, Person, PersonId, RegistrationStatus};
+
+pub const EXTENSION_ID: &str = crate::extensions::WAITING_LIST_ID;
+pub const SPEC_URL: &str = crate::extensions::WAITING_LIST_SPEC_URL;
+
+// Stored on the competition, WCIF itself has no notion of the order of pending registrations
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitingListExtension {
+    pub id: MustBe!("wcif-rs.WaitingList"),
+    pub spec_url: String,
+    pub data: WaitingList,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitingList {
+    pub person_ids: Vec<PersonId>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WaitingListError {
+    Empty,
+    CompetitorLimitReached { competitor_limit: u32 },
+}
+
+impl Display for WaitingListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "The waiting list is empty"),
+            Self::CompetitorLimitReached { competitor_limit } => write!(f, "The competitor limit of {competitor_limit} is reached"),
+        }
+    }
+}
+
+impl Competition {
+    pub fn waiting_list_order(&self) -> Option<&WaitingList> {
+        self.extensions.iter().find_map(|e| match e {
+            Extension::WaitingList(x) => Some(&x.data),
+            _ => None,
+        })
+    }
+
+    pub fn waiting_list_order_mut(&mut self) -> &mut WaitingList {
+        if self.waiting_list_order().is_none() {
+            self.extensions.push(Extension::WaitingList(WaitingListExtension {
+                id: Default::default(),
+                spec_url: SPEC_URL.to_string(),
+                data: WaitingList::default(),
+            }));
+        }
+        self.extensions.iter_mut().find_map(|e| match e {
+            Extension::WaitingList(x) => Some(&mut x.data),
+            _ => None,
+        }).expect("waiting list extension was just added")
+    }
+
+    // Persons with a pending registration, in the order of the extension. Persons not listed there follow
+    // ordered by registration id, which is the order they registered in
+    pub fn waiting_list(&self) -> Vec<&Person> {
+        let order = self.waiting_list_order().map(|w| w.person_ids.as_slice()).unwrap_or_default();
+        let mut waiting: Vec<&Person> = self.persons.iter()
+            .filter(|p| p.registration.as_ref().is_some_and(|r| r.status == RegistrationStatus::Pending))
+            .collect();
+        waiting.sort_by_key(|p| {
+            let position = p.registrant_id.and_then(|id| order.iter().position(|o| *o == id));
+            (position.unwrap_or(usize::MAX), p.registration.as_ref().map(|r| r.wca_registration_id))
+        });
+        waiting
+    }
+
+    pub fn accepted_competitors(&self) -> usize {
+        self.persons.iter()
+            .filter_map(|p| p.registration.as_ref())
+            .filter(|r| r.status == RegistrationStatus::Accepted && r.is_competing)
+            .count()
+    }
+
+    // Accepts the first person of the waiting list, unless that would exceed the competitor limit
+    pub fn promote_next(&mut self) -> Result<PersonId, WaitingListError> {
+        if let Some(competitor_limit) = self.competitor_limit {
+            if self.accepted_competitors() >= competitor_limit as usize {
+                return Err(WaitingListError::CompetitorLimitReached { competitor_limit });
+            }
+        }
+        let person_id = self.waiting_list().first()
+            .and_then(|p| p.registrant_id)
+            .ok_or(WaitingListError::Empty)?;
+        if let Some(registration) = self.find_person_mut(person_id).and_then(|p| p.registration.as_mut()) {
+            registration.status = RegistrationStatus::Accepted;
+        }
+        if self.waiting_list_order().is_some() {
+            self.waiting_list_order_mut().person_ids.retain(|id| *id != person_id);
+        }
+        trace_change!(person_id, "promoted from waiting list");
+        Ok(person_id)
+    }
+}
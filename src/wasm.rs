@@ -0,0 +1,30 @@
+use wasm_bindgen::prelude::*;
+use crate::types::Competition;
+
+fn parse_competition(json: &str) -> Result<Competition, JsError> {
+    serde_json::from_str(json).map_err(|e| JsError::new(&e.to_string()))
+}
+
+// Parses a WCIF document and returns it re-serialized in the shape this crate produces
+#[wasm_bindgen]
+pub fn parse(json: &str) -> Result<String, JsError> {
+    let competition = parse_competition(json)?;
+    serde_json::to_string(&competition).map_err(|e| JsError::new(&e.to_string()))
+}
+
+// Returns a human readable message for every validation error of the document
+#[wasm_bindgen]
+pub fn validate(json: &str) -> Result<Vec<String>, JsError> {
+    let competition = parse_competition(json)?;
+    Ok(competition.validate().iter()
+        .map(|e| e.to_string())
+        .collect())
+}
+
+// Returns the structural changes between two WCIF documents as a JSON array
+#[wasm_bindgen]
+pub fn diff(old: &str, new: &str) -> Result<String, JsError> {
+    let changes = parse_competition(old)?.diff(&parse_competition(new)?)
+        .map_err(|e| JsError::new(&e.to_string()))?;
+    serde_json::to_string(&changes).map_err(|e| JsError::new(&e.to_string()))
+}
@@ -0,0 +1,91 @@
+use crate::names::PersonName;
+use crate::types::WCAId;
+
+impl WCAId {
+    // Allocates the next free WCA ID for a newcomer following the WCA's rules: the year of the first competition,
+    // followed by the first four letters of the surname (filled up with letters of the given names) and the
+    // lowest discriminant not yet taken for that prefix. Returns None if all 99 discriminants are taken.
+    pub fn generate(name: &str, year: u16, existing: &[WCAId]) -> Option<WCAId> {
+        let name = Self::semi_id_letters(name);
+        (1..=99u8)
+            .map(|discriminant| WCAId { year, discriminant, name: name.clone() })
+            .find(|id| !existing.contains(id))
+    }
+
+    fn semi_id_letters(name: &str) -> String {
+        // Only the latin part of names like "Latin Name (Local Name)" is considered
+        let name = PersonName::parse(name);
+        let mut parts: Vec<&str> = name.latin().split_whitespace().collect();
+        let surname = parts.pop().unwrap_or_default();
+        let letters: String = std::iter::once(surname)
+            .chain(parts)
+            .flat_map(|p| transliterate(p).chars().collect::<Vec<_>>())
+            .filter(|c| c.is_ascii_alphabetic())
+            .map(|c| c.to_ascii_uppercase())
+            .take(4)
+            .collect();
+        format!("{letters:U<4}")
+    }
+}
+
+// Replaces accented and other non-ASCII latin letters with their closest ASCII representation.
+// Characters that have no latin representation are dropped
+pub fn transliterate(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii() {
+            result.push(c);
+            continue;
+        }
+        let replacement = match c {
+            'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+            'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+            'Æ' => "AE",
+            'æ' => "ae",
+            'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+            'Ð' | 'Ď' | 'Đ' => "D",
+            'ð' | 'ď' | 'đ' => "d",
+            'È'..='Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+            'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+            'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+            'Ĥ' | 'Ħ' => "H",
+            'ĥ' | 'ħ' => "h",
+            'Ì'..='Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+            'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+            'Ĵ' => "J",
+            'ĵ' => "j",
+            'Ķ' => "K",
+            'ķ' => "k",
+            'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+            'Ñ' | 'Ń' | 'Ņ' | 'Ň' => "N",
+            'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+            'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+            'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+            'Œ' => "OE",
+            'œ' => "oe",
+            'Ŕ' | 'Ŗ' | 'Ř' => "R",
+            'ŕ' | 'ŗ' | 'ř' => "r",
+            'Ś' | 'Ŝ' | 'Ş' | 'Š' | 'Ș' => "S",
+            'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+            'ß' => "ss",
+            'Ţ' | 'Ť' | 'Ŧ' | 'Ț' => "T",
+            'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+            'Þ' => "TH",
+            'þ' => "th",
+            'Ù'..='Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+            'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+            'Ŵ' => "W",
+            'ŵ' => "w",
+            'Ý' | 'Ŷ' | 'Ÿ' => "Y",
+            'ý' | 'ÿ' | 'ŷ' => "y",
+            'Ź' | 'Ż' | 'Ž' => "Z",
+            'ź' | 'ż' | 'ž' => "z",
+            _ => "",
+        };
+        result.push_str(replacement);
+    }
+    result
+}
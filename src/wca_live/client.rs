@@ -0,0 +1,104 @@
+use std::fmt::{Display, Formatter};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use crate::types::{DateTime, PersonId, Round};
+use crate::wca_live::{EnterResultsInput, LiveRound, ENTER_RESULTS_MUTATION, ROUND_QUERY};
+
+pub const DEFAULT_ENDPOINT: &str = "https://live.worldcubeassociation.org/api";
+
+#[derive(Debug)]
+pub enum ClientError {
+    Http(Box<ureq::Error>),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    // Errors returned by the API
+    GraphQl(Vec<String>),
+    NotFound,
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http(e) => write!(f, "{e}"),
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Json(e) => write!(f, "{e}"),
+            Self::GraphQl(messages) => write!(f, "{}", messages.join(", ")),
+            Self::NotFound => write!(f, "Not found"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+#[derive(Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RoundData {
+    round: Option<LiveRound>,
+}
+
+// Blocking client for the WCA Live GraphQL API. Entering results needs a scoretaking token
+#[derive(Clone, Debug)]
+pub struct WcaLiveClient {
+    endpoint: String,
+    token: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl WcaLiveClient {
+    pub fn new(endpoint: impl Into<String>, token: Option<String>) -> Self {
+        Self { endpoint: endpoint.into(), token, agent: ureq::Agent::new() }
+    }
+
+    pub fn request<T: DeserializeOwned>(&self, query: &str, variables: Value) -> Result<T, ClientError> {
+        let mut request = self.agent.post(&self.endpoint);
+        if let Some(token) = &self.token {
+            request = request.set("Authorization", &format!("Bearer {token}"));
+        }
+        let response: GraphQlResponse<T> = request.send_json(json!({ "query": query, "variables": variables }))
+            .map_err(|e| ClientError::Http(Box::new(e)))?
+            .into_json()
+            .map_err(ClientError::Io)?;
+        if !response.errors.is_empty() {
+            return Err(ClientError::GraphQl(response.errors.into_iter().map(|e| e.message).collect()));
+        }
+        response.data.ok_or(ClientError::NotFound)
+    }
+
+    pub fn fetch_round(&self, live_round_id: &str) -> Result<LiveRound, ClientError> {
+        self.request::<RoundData>(ROUND_QUERY, json!({ "id": live_round_id }))?
+            .round
+            .ok_or(ClientError::NotFound)
+    }
+
+    pub fn enter_results(&self, input: &EnterResultsInput) -> Result<(), ClientError> {
+        let input = serde_json::to_value(input).map_err(ClientError::Json)?;
+        self.request::<Value>(ENTER_RESULTS_MUTATION, json!({ "input": input }))?;
+        Ok(())
+    }
+
+    // Takes over the results of WCA Live, see `Round::apply_live_results`
+    pub fn pull_round(&self, round: &mut Round, live_round_id: &str) -> Result<Vec<PersonId>, ClientError> {
+        Ok(round.apply_live_results(&self.fetch_round(live_round_id)?))
+    }
+
+    // Enters the results that differ in WCA Live and returns how many were entered
+    pub fn push_round(&self, round: &Round, live_round_id: &str, now: DateTime) -> Result<usize, ClientError> {
+        let input = round.live_results_input(&self.fetch_round(live_round_id)?, now);
+        if !input.results.is_empty() {
+            self.enter_results(&input)?;
+        }
+        Ok(input.results.len())
+    }
+}
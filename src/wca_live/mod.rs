@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use crate::types::{AdvancementCondition, Attempt, AttemptResult, CountryCode, Cutoff, DateTime, EventId, PersonId, Round, RoundFormat, RoundId, RoundIdType, RoundResult, TimeLimit};
+
+#[cfg(feature = "wca_live_client")]
+pub mod client;
+
+// Selects the fields of the types below
+pub const ROUND_QUERY: &str = "query Round($id: ID!) { round(id: $id) { id number name open finished \
+    format { id numberOfAttempts sortBy } timeLimit { centiseconds cumulativeRoundWcifIds } \
+    cutoff { numberOfAttempts attemptResult } advancementCondition { type level } competitionEvent { event { id } } \
+    results { id ranking advancing attempts { result } best average person { id registrantId name wcaId country { iso2 } } } } }";
+pub const ENTER_RESULTS_MUTATION: &str = "mutation EnterResults($input: EnterResultsInput!) { enterResults(input: $input) { round { id } } }";
+
+// A round as returned by the WCA Live GraphQL API. Ids are WCA Live's own, persons are matched by registrant id
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRound {
+    pub id: String,
+    pub number: RoundIdType,
+    pub name: String,
+    pub open: bool,
+    pub finished: bool,
+    pub format: LiveFormat,
+    pub time_limit: Option<LiveTimeLimit>,
+    pub cutoff: Option<Cutoff>,
+    pub advancement_condition: Option<AdvancementCondition>,
+    pub competition_event: LiveCompetitionEvent,
+    #[serde(default)]
+    pub results: Vec<LiveResult>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveFormat {
+    pub id: RoundFormat,
+    pub number_of_attempts: u32,
+    pub sort_by: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveTimeLimit {
+    pub centiseconds: u32,
+    pub cumulative_round_wcif_ids: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LiveCompetitionEvent {
+    pub event: LiveEvent,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LiveEvent {
+    pub id: EventId,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveResult {
+    pub id: String,
+    pub ranking: Option<u64>,
+    pub advancing: bool,
+    pub attempts: Vec<LiveAttempt>,
+    pub best: AttemptResult,
+    pub average: AttemptResult,
+    pub person: LivePerson,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LiveAttempt {
+    pub result: AttemptResult,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LivePerson {
+    pub id: String,
+    pub registrant_id: Option<PersonId>,
+    pub name: String,
+    pub wca_id: Option<String>,
+    pub country: LiveCountry,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LiveCountry {
+    pub iso2: CountryCode,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnterResultsInput {
+    pub round_id: String,
+    pub results: Vec<LiveResultInput>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveResultInput {
+    // Id of the result in WCA Live
+    pub id: String,
+    pub attempts: Vec<LiveAttempt>,
+    pub entered_at: DateTime,
+}
+
+impl LiveRound {
+    pub fn round_id(&self) -> RoundId {
+        RoundId { event: self.competition_event.event.id.clone(), round: self.number }
+    }
+
+    // Cumulative round ids that can't be parsed are left out
+    pub fn to_time_limit(&self) -> Option<TimeLimit> {
+        self.time_limit.as_ref().map(|t| TimeLimit {
+            centiseconds: t.centiseconds,
+            cumulative_round_ids: t.cumulative_round_wcif_ids.iter().filter_map(|id| id.parse().ok()).collect(),
+        })
+    }
+
+    // A round with the settings and results of WCA Live. Results of persons without a registrant id are left out
+    pub fn to_round(&self) -> Round {
+        Round {
+            id: self.round_id(),
+            format: self.format.id.clone(),
+            time_limit: self.to_time_limit(),
+            cutoff: self.cutoff.clone(),
+            advancement_condition: self.advancement_condition.clone(),
+            results: self.results.iter().filter_map(LiveResult::to_round_result).collect(),
+            scramble_set_count: 1,
+            scramble_sets: vec![],
+            extensions: vec![],
+        }
+    }
+
+    fn find_result(&self, person_id: PersonId) -> Option<&LiveResult> {
+        self.results.iter().find(|r| r.person.registrant_id == Some(person_id))
+    }
+}
+
+impl LiveResult {
+    pub fn to_attempts(&self) -> Vec<Attempt> {
+        self.attempts.iter().map(|a| Attempt { result: a.result, reconstruction: None }).collect()
+    }
+
+    pub fn to_round_result(&self) -> Option<RoundResult> {
+        Some(RoundResult {
+            person_id: self.person.registrant_id?,
+            ranking: self.ranking,
+            attempts: self.to_attempts(),
+            best: self.best,
+            average: self.average,
+        })
+    }
+}
+
+// Attempts without the trailing skipped ones, which WCA Live doesn't return
+fn entered_results(attempts: &[Attempt]) -> Vec<AttemptResult> {
+    let mut results: Vec<AttemptResult> = attempts.iter().map(|a| a.result).collect();
+    while results.last() == Some(&AttemptResult::Skipped) {
+        results.pop();
+    }
+    results
+}
+
+impl Round {
+    // Takes over the attempts of results that differ in WCA Live. Persons WCA Live has no result for are left as
+    // they are, persons missing in the round are added. Returns the persons whose attempts changed
+    pub fn apply_live_results(&mut self, live: &LiveRound) -> Vec<PersonId> {
+        let mut changed = vec![];
+        for live_result in &live.results {
+            let Some(person_id) = live_result.person.registrant_id else {
+                continue;
+            };
+            let attempts = live_result.to_attempts();
+            match self.results.iter_mut().find(|r| r.person_id == person_id) {
+                Some(result) if entered_results(&result.attempts) == entered_results(&attempts) => continue,
+                Some(result) => result.attempts = attempts,
+                None => self.results.push(RoundResult {
+                    person_id,
+                    ranking: None,
+                    attempts,
+                    best: AttemptResult::Skipped,
+                    average: AttemptResult::Skipped,
+                }),
+            }
+            changed.push(person_id);
+        }
+        if !changed.is_empty() {
+            self.update_results();
+        }
+        changed
+    }
+
+    // Results whose attempts differ from WCA Live, for the enterResults mutation. Persons without a result in WCA
+    // Live can't be entered and are left out
+    pub fn live_results_input(&self, live: &LiveRound, entered_at: DateTime) -> EnterResultsInput {
+        let results = self.results.iter()
+            .filter_map(|result| {
+                let live_result = live.find_result(result.person_id)?;
+                let attempts = entered_results(&result.attempts);
+                if attempts == entered_results(&live_result.to_attempts()) {
+                    return None;
+                }
+                Some(LiveResultInput {
+                    id: live_result.id.clone(),
+                    attempts: attempts.into_iter().map(|result| LiveAttempt { result }).collect(),
+                    entered_at,
+                })
+            })
+            .collect();
+        EnterResultsInput { round_id: live.id.clone(), results }
+    }
+}